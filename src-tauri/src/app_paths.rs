@@ -0,0 +1,15 @@
+// Dossier de données de Nether Client selon le système : %APPDATA% sous Windows,
+// ~/.local/share (XDG_DATA_HOME) sous Linux, ~/Library/Application Support sous macOS
+use std::path::PathBuf;
+
+pub fn app_dir() -> Result<PathBuf, String> {
+    // Tests : dossier isolé, jamais la configuration ni le cache de l'utilisateur
+    if cfg!(test) {
+        return Ok(std::env::temp_dir().join(format!("nether-client-tests-{}", std::process::id())));
+    }
+    tauri::api::path::data_dir()
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+        .map(|dir| dir.join("NetherClient"))
+        .ok_or_else(|| "Dossier de données introuvable (APPDATA, XDG_DATA_HOME ou HOME non défini)".to_string())
+}
@@ -0,0 +1,391 @@
+// Cache d'artefacts partagé (JAR serveurs, installateurs, JDK) adressé par empreinte SHA-256
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::app_paths;
+use crate::downloader::Checksum;
+
+// Taille maximale par défaut du cache : 5 Go
+const DEFAULT_MAX_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+lazy_static::lazy_static! {
+    // Verrou sur index.json : plusieurs téléchargements peuvent finir en même temps
+    static ref INDEX_LOCK: Arc<Mutex<()>> = Arc::new(Mutex::new(()));
+}
+
+// Métadonnées d'un artefact stocké dans le cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactEntry {
+    pub sha256: String,
+    // URL de la première mise en cache
+    pub source_url: String,
+    // Autres URLs ayant servi le même contenu (miroirs, alias de version)
+    #[serde(default)]
+    pub other_urls: Vec<String>,
+    pub size: u64,
+    pub created_at: i64,
+    pub last_used: i64,
+    // Autres empreintes connues (sha1, sha512) pour retrouver l'artefact depuis un manifest
+    #[serde(default)]
+    pub hashes: HashMap<String, String>,
+}
+
+impl ArtifactEntry {
+    fn has_url(&self, url: &str) -> bool {
+        self.source_url == url || self.other_urls.iter().any(|other| other == url)
+    }
+
+    // Détacher une URL de l'artefact. Renvoie false s'il n'a plus aucune URL.
+    fn forget_url(&mut self, url: &str) -> bool {
+        self.other_urls.retain(|other| other != url);
+        if self.source_url == url {
+            if self.other_urls.is_empty() {
+                return false;
+            }
+            self.source_url = self.other_urls.remove(0);
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArtifactIndex {
+    max_size: u64,
+    entries: HashMap<String, ArtifactEntry>,
+}
+
+impl Default for ArtifactIndex {
+    fn default() -> Self {
+        ArtifactIndex {
+            max_size: DEFAULT_MAX_SIZE,
+            entries: HashMap::new(),
+        }
+    }
+}
+
+// Résumé du cache renvoyé à l'interface
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub path: String,
+    pub entries: usize,
+    pub total_size: u64,
+    pub max_size: u64,
+}
+
+fn cache_dir() -> Result<PathBuf, String> {
+    Ok(app_paths::app_dir()?.join("cache").join("artifacts"))
+}
+
+fn artifact_path(root: &Path, sha256: &str) -> PathBuf {
+    root.join(&sha256[..2]).join(sha256)
+}
+
+fn load_index(root: &Path) -> ArtifactIndex {
+    fs::read_to_string(root.join("index.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(root: &Path, index: &ArtifactIndex) -> Result<(), String> {
+    fs::create_dir_all(root)
+        .map_err(|e| format!("Erreur création dossier cache: {}", e))?;
+
+    let json = serde_json::to_string_pretty(index)
+        .map_err(|e| format!("Erreur sérialisation index cache: {}", e))?;
+
+    // Écriture atomique pour ne jamais laisser un index tronqué
+    let tmp_path = root.join("index.json.tmp");
+    fs::write(&tmp_path, json)
+        .map_err(|e| format!("Erreur écriture index cache: {}", e))?;
+    fs::rename(&tmp_path, root.join("index.json"))
+        .map_err(|e| format!("Erreur écriture index cache: {}", e))
+}
+
+fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// Copie vers un dossier serveur : jamais de lien physique, le serveur peut modifier
+// son JAR sur place (Paperclip, mises à jour manuelles) et corromprait le cache
+fn copy_out(source: &Path, destination: &Path) -> Result<(), String> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Erreur création dossier {}: {}", parent.display(), e))?;
+    }
+
+    // Ne jamais écrire à travers un lien physique existant (caches créés par d'anciennes versions)
+    if destination.exists() {
+        fs::remove_file(destination)
+            .map_err(|e| format!("Erreur remplacement {}: {}", destination.display(), e))?;
+    }
+
+    fs::copy(source, destination)
+        .map_err(|e| format!("Erreur copie depuis le cache: {}", e))?;
+
+    Ok(())
+}
+
+// Copie dans le cache via un fichier temporaire : jamais d'artefact tronqué sous son nom définitif
+fn copy_into_cache(source: &Path, cached_path: &Path) -> Result<(), String> {
+    if let Some(parent) = cached_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Erreur création dossier cache: {}", e))?;
+    }
+    let tmp_path = cached_path.with_extension("tmp");
+    fs::copy(source, &tmp_path).map_err(|e| format!("Erreur copie vers le cache: {}", e))?;
+    fs::rename(&tmp_path, cached_path).map_err(|e| format!("Erreur copie vers le cache: {}", e))
+}
+
+fn find_entry<'a>(index: &'a ArtifactIndex, url: &str, checksum: Option<&Checksum>) -> Option<&'a ArtifactEntry> {
+    match checksum {
+        Some(Checksum::Sha256(value)) => index.entries.get(value),
+        Some(other) => index
+            .entries
+            .values()
+            .find(|entry| entry.hashes.get(other.algorithm()).map(|v| v == other.value()).unwrap_or(false)),
+        // Sans empreinte, l'URL sert de clé (uniquement pour les URLs immuables)
+        None => index
+            .entries
+            .values()
+            .filter(|entry| entry.has_url(url))
+            .max_by_key(|entry| entry.last_used),
+    }
+}
+
+// Placer un artefact du cache à la destination demandée. Renvoie None si absent ou invalide.
+pub fn restore(url: &str, checksum: Option<&Checksum>, destination: &Path) -> Option<ArtifactEntry> {
+    let root = cache_dir().ok()?;
+    let mut entry = {
+        let _guard = INDEX_LOCK.lock().unwrap();
+        find_entry(&load_index(&root), url, checksum)?.clone()
+    };
+    let cached_path = artifact_path(&root, &entry.sha256);
+
+    // Copie et vérification hors du verrou : hacher un gros JAR ne doit pas bloquer les autres téléchargements
+    let present = fs::metadata(&cached_path).map(|m| m.len() == entry.size).unwrap_or(false);
+    let valid = present
+        && match copy_out(&cached_path, destination) {
+            Ok(()) => sha256_file(destination).map(|h| h == entry.sha256).unwrap_or(false),
+            Err(e) => {
+                println!("⚠️ {}", e);
+                return None;
+            }
+        };
+
+    let _guard = INDEX_LOCK.lock().unwrap();
+    let mut index = load_index(&root);
+
+    if !valid {
+        println!("⚠️ Artefact en cache invalide, suppression: {}", entry.sha256);
+        if present {
+            let _ = fs::remove_file(destination);
+        }
+        let _ = fs::remove_file(&cached_path);
+        index.entries.remove(&entry.sha256);
+        let _ = save_index(&root, &index);
+        return None;
+    }
+
+    // L'entrée a pu être évincée pendant la copie : la copie reste valide, seul l'index est ignoré
+    if let Some(current) = index.entries.get_mut(&entry.sha256) {
+        current.last_used = chrono::Utc::now().timestamp();
+        entry = current.clone();
+        let _ = save_index(&root, &index);
+    }
+
+    println!("♻️ Artefact récupéré depuis le cache: {}", destination.display());
+    Some(entry)
+}
+
+// Ajouter un fichier téléchargé et vérifié au cache
+pub fn store(file: &Path, url: &str, sha256: &str, checksum: Option<&Checksum>) -> Result<(), String> {
+    let root = cache_dir()?;
+    let _guard = INDEX_LOCK.lock().unwrap();
+    let mut index = load_index(&root);
+
+    let size = fs::metadata(file)
+        .map_err(|e| format!("Erreur lecture {}: {}", file.display(), e))?
+        .len();
+
+    // Un artefact plus gros que le cache entier n'y a pas sa place
+    if size > index.max_size {
+        return Ok(());
+    }
+
+    // Toujours une copie : un lien vers le fichier du serveur serait modifié avec lui (Paperclip, édition)
+    let cached_path = artifact_path(&root, sha256);
+    if !cached_path.exists() {
+        copy_into_cache(file, &cached_path)?;
+    }
+
+    record(&mut index, sha256, url, size, checksum);
+    evict(&root, &mut index, Some(sha256));
+    save_index(&root, &index)
+}
+
+// Ajouter une ressource téléchargée en mémoire (manifests, métadonnées) au cache.
+// Seule la dernière version d'une URL est gardée : les manifests changent souvent
+// et leurs anciennes versions évinceraient de vrais artefacts
+pub fn store_bytes(url: &str, bytes: &[u8]) -> Result<(), String> {
+    let root = cache_dir()?;
+    let _guard = INDEX_LOCK.lock().unwrap();
+    let mut index = load_index(&root);
+
+    let sha256 = format!("{:x}", Sha256::digest(bytes));
+    let mut replaced = Vec::new();
+    for entry in index.entries.values_mut() {
+        if entry.sha256 != sha256 && entry.has_url(url) && !entry.forget_url(url) {
+            replaced.push(entry.sha256.clone());
+        }
+    }
+    for old in replaced {
+        let _ = fs::remove_file(artifact_path(&root, &old));
+        index.entries.remove(&old);
+    }
+    let cached_path = artifact_path(&root, &sha256);
+    if !cached_path.exists() {
+        if let Some(parent) = cached_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Erreur création dossier cache: {}", e))?;
+        }
+        fs::write(&cached_path, bytes)
+            .map_err(|e| format!("Erreur écriture cache: {}", e))?;
+    }
+
+    record(&mut index, &sha256, url, bytes.len() as u64, None);
+    evict(&root, &mut index, Some(&sha256));
+    save_index(&root, &index)
+}
+
+// Lire la dernière version en cache d'une ressource identifiée par son URL
+pub fn read_by_url(url: &str) -> Option<Vec<u8>> {
+    let root = cache_dir().ok()?;
+    let _guard = INDEX_LOCK.lock().unwrap();
+    let mut index = load_index(&root);
+
+    let sha256 = find_entry(&index, url, None)?.sha256.clone();
+    let bytes = fs::read(artifact_path(&root, &sha256)).ok()?;
+    if format!("{:x}", Sha256::digest(&bytes)) != sha256 {
+        return None;
+    }
+
+    if let Some(entry) = index.entries.get_mut(&sha256) {
+        entry.last_used = chrono::Utc::now().timestamp();
+    }
+    let _ = save_index(&root, &index);
+
+    Some(bytes)
+}
+
+// Créer ou rafraîchir l'entrée d'index d'un artefact
+fn record(index: &mut ArtifactIndex, sha256: &str, url: &str, size: u64, checksum: Option<&Checksum>) {
+    let now = chrono::Utc::now().timestamp();
+    let entry = index.entries.entry(sha256.to_string()).or_insert_with(|| ArtifactEntry {
+        sha256: sha256.to_string(),
+        source_url: url.to_string(),
+        other_urls: Vec::new(),
+        size,
+        created_at: now,
+        last_used: now,
+        hashes: HashMap::new(),
+    });
+    if !entry.has_url(url) {
+        entry.other_urls.push(url.to_string());
+    }
+    entry.last_used = now;
+    if let Some(checksum) = checksum {
+        if !matches!(checksum, Checksum::Sha256(_)) {
+            entry.hashes.insert(checksum.algorithm().to_string(), checksum.value().to_string());
+        }
+    }
+}
+
+// Supprimer les artefacts les moins récemment utilisés jusqu'à respecter la limite
+fn evict(root: &Path, index: &mut ArtifactIndex, keep: Option<&str>) -> u64 {
+    let mut total: u64 = index.entries.values().map(|e| e.size).sum();
+    let mut freed = 0u64;
+
+    let mut candidates: Vec<(i64, String, u64)> = index
+        .entries
+        .values()
+        .filter(|e| Some(e.sha256.as_str()) != keep)
+        .map(|e| (e.last_used, e.sha256.clone(), e.size))
+        .collect();
+    candidates.sort();
+
+    for (_, sha256, size) in candidates {
+        if total <= index.max_size {
+            break;
+        }
+        let _ = fs::remove_file(artifact_path(root, &sha256));
+        index.entries.remove(&sha256);
+        total = total.saturating_sub(size);
+        freed += size;
+        println!("🗑️ Artefact évincé du cache: {} ({} octets)", sha256, size);
+    }
+
+    freed
+}
+
+pub fn stats() -> Result<CacheStats, String> {
+    let root = cache_dir()?;
+    let _guard = INDEX_LOCK.lock().unwrap();
+    let index = load_index(&root);
+
+    Ok(CacheStats {
+        path: root.to_string_lossy().to_string(),
+        entries: index.entries.len(),
+        total_size: index.entries.values().map(|e| e.size).sum(),
+        max_size: index.max_size,
+    })
+}
+
+// Modifier la taille maximale du cache et évincer si nécessaire
+pub fn set_max_size(max_size: u64) -> Result<CacheStats, String> {
+    {
+        let root = cache_dir()?;
+        let _guard = INDEX_LOCK.lock().unwrap();
+        let mut index = load_index(&root);
+        index.max_size = max_size;
+        evict(&root, &mut index, None);
+        save_index(&root, &index)?;
+    }
+
+    stats()
+}
+
+// Vider complètement le cache d'artefacts
+pub fn clear() -> Result<u64, String> {
+    let root = cache_dir()?;
+    let _guard = INDEX_LOCK.lock().unwrap();
+    let mut index = load_index(&root);
+
+    let freed = index.entries.values().map(|e| e.size).sum();
+    for sha256 in index.entries.keys() {
+        let _ = fs::remove_file(artifact_path(&root, sha256));
+    }
+    index.entries.clear();
+
+    if root.exists() {
+        save_index(&root, &index)?;
+    }
+
+    Ok(freed)
+}
@@ -0,0 +1,516 @@
+// Réglages courants de bukkit.yml, spigot.yml et de la configuration Paper (config/paper-*.yml) :
+// schéma typé, lecture/écriture sans perte des commentaires et préréglages d'optimisation
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::yaml_file::{self, YamlFile};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFile {
+    Bukkit,
+    Spigot,
+    PaperGlobal,
+    PaperWorldDefaults,
+}
+
+impl ConfigFile {
+    pub const ALL: [ConfigFile; 4] = [ConfigFile::Bukkit, ConfigFile::Spigot, ConfigFile::PaperGlobal, ConfigFile::PaperWorldDefaults];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            ConfigFile::Bukkit => "bukkit",
+            ConfigFile::Spigot => "spigot",
+            ConfigFile::PaperGlobal => "paper-global",
+            ConfigFile::PaperWorldDefaults => "paper-world-defaults",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|file| file.id() == id)
+    }
+
+    // Chemin relatif au dossier du serveur (Paper 1.19+ range sa configuration dans config/)
+    pub fn relative_path(&self) -> &'static str {
+        match self {
+            ConfigFile::Bukkit => "bukkit.yml",
+            ConfigFile::Spigot => "spigot.yml",
+            ConfigFile::PaperGlobal => "config/paper-global.yml",
+            ConfigFile::PaperWorldDefaults => "config/paper-world-defaults.yml",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SettingKind {
+    Bool,
+    Int { min: i64, max: i64 },
+    Decimal { min: f64, max: f64 },
+    // Nombre ou "default" (valeur reprise de server.properties)
+    IntOrDefault { min: i64, max: i64 },
+    Enum(&'static [&'static str]),
+    Text,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SettingDef {
+    pub file: ConfigFile,
+    pub path: &'static str,
+    pub kind: SettingKind,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+const COUNT: SettingKind = SettingKind::Int { min: 0, max: i32::MAX as i64 };
+const RANGE: SettingKind = SettingKind::Int { min: 0, max: 512 };
+const DISTANCE: SettingKind = SettingKind::IntOrDefault { min: 2, max: 32 };
+const TICKS: SettingKind = SettingKind::Int { min: 1, max: i32::MAX as i64 };
+const THREADS: SettingKind = SettingKind::Int { min: -1, max: 256 };
+
+const fn setting(file: ConfigFile, path: &'static str, kind: SettingKind, default: &'static str, description: &'static str) -> SettingDef {
+    SettingDef { file, path, kind, default, description }
+}
+
+use ConfigFile::{Bukkit, PaperGlobal, PaperWorldDefaults, Spigot};
+
+// Réglages couramment ajustés, par fichier
+pub const SETTINGS: &[SettingDef] = &[
+    setting(Bukkit, "spawn-limits.monsters", COUNT, "70", "Monstres par joueur"),
+    setting(Bukkit, "spawn-limits.animals", COUNT, "10", "Animaux par joueur"),
+    setting(Bukkit, "spawn-limits.water-animals", COUNT, "5", "Animaux aquatiques par joueur"),
+    setting(Bukkit, "spawn-limits.water-ambient", COUNT, "20", "Poissons par joueur"),
+    setting(Bukkit, "spawn-limits.ambient", COUNT, "15", "Chauves-souris par joueur"),
+    setting(Bukkit, "chunk-gc.period-in-ticks", COUNT, "600", "Intervalle de déchargement des chunks inutilisés (ticks, 0 = désactivé)"),
+    setting(Bukkit, "ticks-per.monster-spawns", COUNT, "1", "Ticks entre deux tentatives d'apparition de monstres"),
+    setting(Bukkit, "ticks-per.animal-spawns", COUNT, "400", "Ticks entre deux tentatives d'apparition d'animaux"),
+    setting(Bukkit, "ticks-per.autosave", COUNT, "6000", "Ticks entre deux sauvegardes automatiques"),
+    setting(Spigot, "world-settings.default.view-distance", DISTANCE, "default", "Distance d'affichage en chunks"),
+    setting(Spigot, "world-settings.default.simulation-distance", DISTANCE, "default", "Distance de simulation en chunks"),
+    setting(Spigot, "world-settings.default.mob-spawn-range", SettingKind::Int { min: 0, max: 128 }, "8", "Rayon d'apparition des monstres autour des joueurs (chunks)"),
+    setting(Spigot, "world-settings.default.entity-activation-range.animals", RANGE, "32", "Activation des animaux (blocs)"),
+    setting(Spigot, "world-settings.default.entity-activation-range.monsters", RANGE, "32", "Activation des monstres (blocs)"),
+    setting(Spigot, "world-settings.default.entity-activation-range.raiders", RANGE, "48", "Activation des pillards (blocs)"),
+    setting(Spigot, "world-settings.default.entity-activation-range.misc", RANGE, "16", "Activation des autres entités (blocs)"),
+    setting(Spigot, "world-settings.default.entity-activation-range.water", RANGE, "16", "Activation des entités aquatiques (blocs)"),
+    setting(Spigot, "world-settings.default.entity-activation-range.villagers", RANGE, "32", "Activation des villageois (blocs)"),
+    setting(Spigot, "world-settings.default.entity-activation-range.flying-monsters", RANGE, "32", "Activation des monstres volants (blocs)"),
+    setting(Spigot, "world-settings.default.entity-activation-range.tick-inactive-villagers", SettingKind::Bool, "true", "Continuer à faire vivre les villageois inactifs"),
+    setting(Spigot, "world-settings.default.entity-tracking-range.players", RANGE, "128", "Envoi des joueurs aux clients (blocs)"),
+    setting(Spigot, "world-settings.default.entity-tracking-range.animals", RANGE, "96", "Envoi des animaux aux clients (blocs)"),
+    setting(Spigot, "world-settings.default.entity-tracking-range.monsters", RANGE, "96", "Envoi des monstres aux clients (blocs)"),
+    setting(Spigot, "world-settings.default.entity-tracking-range.misc", RANGE, "96", "Envoi des autres entités aux clients (blocs)"),
+    setting(Spigot, "world-settings.default.entity-tracking-range.other", RANGE, "64", "Envoi des entités restantes aux clients (blocs)"),
+    setting(Spigot, "world-settings.default.merge-radius.item", SettingKind::Decimal { min: -1.0, max: 16.0 }, "0.5", "Rayon de fusion des objets au sol (blocs)"),
+    setting(Spigot, "world-settings.default.merge-radius.exp", SettingKind::Decimal { min: -1.0, max: 16.0 }, "-1.0", "Rayon de fusion des orbes d'expérience (blocs, -1 = vanilla)"),
+    setting(Spigot, "world-settings.default.nerf-spawner-mobs", SettingKind::Bool, "false", "Désactiver l'IA des monstres issus de spawners"),
+    setting(PaperGlobal, "chunk-system.worker-threads", THREADS, "-1", "Threads de génération/chargement des chunks (-1 = automatique)"),
+    setting(PaperGlobal, "chunk-system.io-threads", THREADS, "-1", "Threads d'entrées/sorties des chunks (-1 = automatique)"),
+    setting(PaperGlobal, "chunk-loading-basic.player-max-chunk-send-rate", SettingKind::Decimal { min: -1.0, max: 10000.0 }, "75.0", "Chunks envoyés par seconde à chaque joueur (-1 = illimité)"),
+    setting(PaperGlobal, "misc.max-joins-per-tick", SettingKind::Int { min: 1, max: 1000 }, "5", "Connexions traitées par tick"),
+    setting(PaperGlobal, "collisions.enable-player-collisions", SettingKind::Bool, "true", "Collisions entre joueurs"),
+    setting(PaperWorldDefaults, "anticheat.anti-xray.enabled", SettingKind::Bool, "false", "Anti-xray"),
+    setting(PaperWorldDefaults, "anticheat.anti-xray.engine-mode", SettingKind::Int { min: 1, max: 3 }, "1", "Mode anti-xray (1 = masquer, 2 = leurres, 3 = leurres en couches)"),
+    setting(PaperWorldDefaults, "anticheat.anti-xray.max-block-height", SettingKind::Int { min: -64, max: 320 }, "64", "Hauteur maximale des blocs masqués"),
+    setting(PaperWorldDefaults, "anticheat.anti-xray.update-radius", SettingKind::Int { min: 0, max: 2 }, "2", "Rayon de révélation autour des blocs cassés"),
+    setting(PaperWorldDefaults, "anticheat.anti-xray.lava-obscures", SettingKind::Bool, "false", "La lave masque les blocs"),
+    setting(PaperWorldDefaults, "chunks.delay-chunk-unloads-by", SettingKind::Text, "10s", "Délai avant déchargement d'un chunk quitté"),
+    setting(PaperWorldDefaults, "chunks.max-auto-save-chunks-per-tick", COUNT, "24", "Chunks sauvegardés par tick pendant l'autosave"),
+    setting(PaperWorldDefaults, "chunks.prevent-moving-into-unloaded-chunks", SettingKind::Bool, "false", "Bloquer les joueurs au bord des chunks non chargés"),
+    setting(PaperWorldDefaults, "collisions.max-entity-collisions", COUNT, "8", "Collisions calculées par entité et par tick"),
+    setting(PaperWorldDefaults, "entities.armor-stands.tick", SettingKind::Bool, "true", "Faire vivre les supports d'armure"),
+    setting(PaperWorldDefaults, "entities.armor-stands.do-collision-entity-lookups", SettingKind::Bool, "true", "Collisions des supports d'armure"),
+    setting(PaperWorldDefaults, "environment.optimize-explosions", SettingKind::Bool, "false", "Calcul optimisé des explosions"),
+    setting(PaperWorldDefaults, "environment.treasure-maps.enabled", SettingKind::Bool, "true", "Cartes au trésor (recherche de structures coûteuse)"),
+    setting(PaperWorldDefaults, "misc.redstone-implementation", SettingKind::Enum(&["VANILLA", "EIGENCRAFT", "ALTERNATE_CURRENT"]), "VANILLA", "Moteur de redstone"),
+    setting(PaperWorldDefaults, "hopper.ignore-occluding-blocks", SettingKind::Bool, "false", "Les entonnoirs ignorent les conteneurs recouverts d'un bloc plein"),
+    setting(PaperWorldDefaults, "tick-rates.mob-spawner", TICKS, "1", "Ticks entre deux activations des spawners"),
+    setting(PaperWorldDefaults, "tick-rates.grass-spread", TICKS, "1", "Ticks entre deux propagations de l'herbe"),
+    setting(PaperWorldDefaults, "tick-rates.container-update", TICKS, "1", "Ticks entre deux mises à jour des conteneurs ouverts"),
+];
+
+pub struct Preset {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub values: &'static [(ConfigFile, &'static str, &'static str)],
+}
+
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        id: "performance",
+        name: "Serveur public",
+        description: "Réduit le coût des entités et des chunks sans changement visible pour les joueurs",
+        values: &[
+            (Bukkit, "spawn-limits.monsters", "50"),
+            (Bukkit, "spawn-limits.animals", "8"),
+            (Bukkit, "spawn-limits.water-animals", "3"),
+            (Bukkit, "spawn-limits.water-ambient", "10"),
+            (Bukkit, "spawn-limits.ambient", "5"),
+            (Bukkit, "chunk-gc.period-in-ticks", "400"),
+            (Bukkit, "ticks-per.monster-spawns", "2"),
+            (Spigot, "world-settings.default.view-distance", "8"),
+            (Spigot, "world-settings.default.simulation-distance", "6"),
+            (Spigot, "world-settings.default.entity-activation-range.animals", "16"),
+            (Spigot, "world-settings.default.entity-activation-range.monsters", "24"),
+            (Spigot, "world-settings.default.entity-activation-range.misc", "8"),
+            (Spigot, "world-settings.default.entity-activation-range.water", "8"),
+            (Spigot, "world-settings.default.entity-activation-range.villagers", "16"),
+            (Spigot, "world-settings.default.entity-activation-range.flying-monsters", "48"),
+            (Spigot, "world-settings.default.entity-tracking-range.players", "48"),
+            (Spigot, "world-settings.default.entity-tracking-range.animals", "48"),
+            (Spigot, "world-settings.default.entity-tracking-range.monsters", "48"),
+            (Spigot, "world-settings.default.entity-tracking-range.misc", "32"),
+            (Spigot, "world-settings.default.merge-radius.item", "3.5"),
+            (Spigot, "world-settings.default.merge-radius.exp", "4.0"),
+            (PaperWorldDefaults, "chunks.max-auto-save-chunks-per-tick", "8"),
+            (PaperWorldDefaults, "chunks.prevent-moving-into-unloaded-chunks", "true"),
+            (PaperWorldDefaults, "collisions.max-entity-collisions", "2"),
+            (PaperWorldDefaults, "environment.optimize-explosions", "true"),
+            (PaperWorldDefaults, "environment.treasure-maps.enabled", "false"),
+            (PaperWorldDefaults, "misc.redstone-implementation", "ALTERNATE_CURRENT"),
+            (PaperWorldDefaults, "hopper.ignore-occluding-blocks", "true"),
+            (PaperWorldDefaults, "tick-rates.mob-spawner", "2"),
+            (PaperWorldDefaults, "tick-rates.grass-spread", "4"),
+        ],
+    },
+    Preset {
+        id: "low-end",
+        name: "Petite machine",
+        description: "Réglages agressifs pour un serveur hébergé sur un PC modeste (distances et entités réduites)",
+        values: &[
+            (Bukkit, "spawn-limits.monsters", "30"),
+            (Bukkit, "spawn-limits.animals", "5"),
+            (Bukkit, "spawn-limits.water-animals", "2"),
+            (Bukkit, "spawn-limits.water-ambient", "5"),
+            (Bukkit, "spawn-limits.ambient", "1"),
+            (Bukkit, "chunk-gc.period-in-ticks", "200"),
+            (Bukkit, "ticks-per.monster-spawns", "4"),
+            (Spigot, "world-settings.default.view-distance", "6"),
+            (Spigot, "world-settings.default.simulation-distance", "4"),
+            (Spigot, "world-settings.default.mob-spawn-range", "6"),
+            (Spigot, "world-settings.default.entity-activation-range.animals", "8"),
+            (Spigot, "world-settings.default.entity-activation-range.monsters", "16"),
+            (Spigot, "world-settings.default.entity-activation-range.raiders", "32"),
+            (Spigot, "world-settings.default.entity-activation-range.misc", "4"),
+            (Spigot, "world-settings.default.entity-activation-range.water", "4"),
+            (Spigot, "world-settings.default.entity-activation-range.villagers", "8"),
+            (Spigot, "world-settings.default.entity-activation-range.flying-monsters", "32"),
+            (Spigot, "world-settings.default.entity-activation-range.tick-inactive-villagers", "false"),
+            (Spigot, "world-settings.default.entity-tracking-range.players", "32"),
+            (Spigot, "world-settings.default.entity-tracking-range.animals", "32"),
+            (Spigot, "world-settings.default.entity-tracking-range.monsters", "32"),
+            (Spigot, "world-settings.default.entity-tracking-range.misc", "16"),
+            (Spigot, "world-settings.default.entity-tracking-range.other", "48"),
+            (Spigot, "world-settings.default.merge-radius.item", "4.0"),
+            (Spigot, "world-settings.default.merge-radius.exp", "6.0"),
+            (Spigot, "world-settings.default.nerf-spawner-mobs", "true"),
+            (PaperGlobal, "chunk-loading-basic.player-max-chunk-send-rate", "40.0"),
+            (PaperWorldDefaults, "chunks.max-auto-save-chunks-per-tick", "6"),
+            (PaperWorldDefaults, "chunks.prevent-moving-into-unloaded-chunks", "true"),
+            (PaperWorldDefaults, "collisions.max-entity-collisions", "2"),
+            (PaperWorldDefaults, "entities.armor-stands.tick", "false"),
+            (PaperWorldDefaults, "entities.armor-stands.do-collision-entity-lookups", "false"),
+            (PaperWorldDefaults, "environment.optimize-explosions", "true"),
+            (PaperWorldDefaults, "environment.treasure-maps.enabled", "false"),
+            (PaperWorldDefaults, "misc.redstone-implementation", "ALTERNATE_CURRENT"),
+            (PaperWorldDefaults, "hopper.ignore-occluding-blocks", "true"),
+            (PaperWorldDefaults, "tick-rates.mob-spawner", "4"),
+            (PaperWorldDefaults, "tick-rates.grass-spread", "8"),
+        ],
+    },
+    Preset {
+        id: "anti-xray",
+        name: "Anti-xray",
+        description: "Active l'anti-xray de Paper en mode leurres (engine-mode 2)",
+        values: &[
+            (PaperWorldDefaults, "anticheat.anti-xray.enabled", "true"),
+            (PaperWorldDefaults, "anticheat.anti-xray.engine-mode", "2"),
+            (PaperWorldDefaults, "anticheat.anti-xray.max-block-height", "64"),
+            (PaperWorldDefaults, "anticheat.anti-xray.update-radius", "2"),
+            (PaperWorldDefaults, "anticheat.anti-xray.lava-obscures", "false"),
+        ],
+    },
+];
+
+// Préréglage calculé : toutes les valeurs par défaut du schéma
+const DEFAULTS_PRESET: &str = "defaults";
+
+// Réglage tel qu'affiché dans l'éditeur
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingState {
+    pub file: String,
+    pub path: String,
+    // "boolean", "integer", "decimal", "integer_or_default", "enum" ou "string"
+    pub kind: String,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub values: Vec<String>,
+    pub default: String,
+    pub description: String,
+    // false si le fichier n'a pas encore été généré par le serveur
+    pub file_present: bool,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SettingChange {
+    pub file: String,
+    pub path: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingDiff {
+    pub file: String,
+    pub path: String,
+    pub description: String,
+    // None si la clé est absente du fichier
+    pub current: Option<String>,
+    pub proposed: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub settings: usize,
+}
+
+pub fn find(file: ConfigFile, path: &str) -> Option<&'static SettingDef> {
+    SETTINGS.iter().find(|s| s.file == file && s.path == path)
+}
+
+fn parse_int(text: &str, min: i64, max: i64) -> Result<i64, String> {
+    let number: i64 = text.parse().map_err(|_| format!("nombre entier attendu, \"{}\" reçu", text))?;
+    if number < min || number > max {
+        return Err(format!("{} hors limites ({} à {})", number, min, max));
+    }
+    Ok(number)
+}
+
+impl SettingDef {
+    // Valeur normalisée, prête à être écrite dans le YAML
+    fn normalize(&self, value: &serde_json::Value) -> Result<String, String> {
+        let text = match value {
+            serde_json::Value::String(s) => s.trim().to_string(),
+            serde_json::Value::Bool(b) => b.to_string(),
+            serde_json::Value::Number(n) => n.to_string(),
+            _ => return Err(format!("{}: valeur invalide", self.path)),
+        };
+        let error = |e: String| format!("{}: {}", self.path, e);
+
+        match self.kind {
+            SettingKind::Bool => match text.to_lowercase().as_str() {
+                "true" | "false" => Ok(text.to_lowercase()),
+                _ => Err(error(format!("true ou false attendu, \"{}\" reçu", text))),
+            },
+            SettingKind::Int { min, max } => parse_int(&text, min, max).map(|n| n.to_string()).map_err(error),
+            SettingKind::IntOrDefault { .. } if text.eq_ignore_ascii_case("default") => Ok("default".to_string()),
+            SettingKind::IntOrDefault { min, max } => parse_int(&text, min, max).map(|n| n.to_string()).map_err(error),
+            SettingKind::Decimal { min, max } => {
+                let number: f64 = text.parse().map_err(|_| error(format!("nombre attendu, \"{}\" reçu", text)))?;
+                if !number.is_finite() || number < min || number > max {
+                    return Err(error(format!("{} hors limites ({} à {})", number, min, max)));
+                }
+                // Toujours écrire un décimal ("3.0" et non "3"), comme le fait Paper
+                Ok(if number.fract() == 0.0 { format!("{:.1}", number) } else { number.to_string() })
+            }
+            SettingKind::Enum(values) => values
+                .iter()
+                .find(|v| v.eq_ignore_ascii_case(&text))
+                .map(|v| v.to_string())
+                .ok_or_else(|| error(format!("valeur parmi {} attendue", values.join(", ")))),
+            SettingKind::Text if text.contains(['\n', '\r']) => Err(error("retour à la ligne interdit".to_string())),
+            SettingKind::Text => Ok(yaml_file::quote(&text)),
+        }
+    }
+
+    // Comparer une valeur du fichier à une valeur normalisée (3 == 3.0, vanilla == VANILLA)
+    fn same_value(&self, current: &str, proposed: &str) -> bool {
+        match self.kind {
+            SettingKind::Decimal { .. } => current.parse::<f64>().ok() == proposed.parse::<f64>().ok(),
+            SettingKind::Text => yaml_file::quote(current) == proposed,
+            _ => current.trim().eq_ignore_ascii_case(proposed),
+        }
+    }
+
+    fn state(&self, document: Option<&YamlFile>) -> SettingState {
+        let (kind, min, max, values) = match self.kind {
+            SettingKind::Bool => ("boolean", None, None, Vec::new()),
+            SettingKind::Int { min, max } => ("integer", Some(min as f64), Some(max as f64), Vec::new()),
+            SettingKind::Decimal { min, max } => ("decimal", Some(min), Some(max), Vec::new()),
+            SettingKind::IntOrDefault { min, max } => ("integer_or_default", Some(min as f64), Some(max as f64), Vec::new()),
+            SettingKind::Enum(values) => ("enum", None, None, values.iter().map(|v| v.to_string()).collect()),
+            SettingKind::Text => ("string", None, None, Vec::new()),
+        };
+        SettingState {
+            file: self.file.id().to_string(),
+            path: self.path.to_string(),
+            kind: kind.to_string(),
+            min,
+            max,
+            values,
+            default: self.default.to_string(),
+            description: self.description.to_string(),
+            file_present: document.is_some(),
+            value: document.and_then(|d| d.get(self.path)),
+        }
+    }
+}
+
+fn load_file(server_dir: &Path, file: ConfigFile) -> Option<YamlFile> {
+    YamlFile::load(&server_dir.join(file.relative_path())).ok()
+}
+
+// Valeurs actuelles de tous les réglages du schéma
+pub fn read(server_dir: &Path) -> Vec<SettingState> {
+    let documents: Vec<(ConfigFile, Option<YamlFile>)> = ConfigFile::ALL
+        .into_iter()
+        .map(|file| (file, load_file(server_dir, file)))
+        .collect();
+
+    SETTINGS
+        .iter()
+        .map(|setting| {
+            let document = documents.iter().find(|(f, _)| *f == setting.file).and_then(|(_, d)| d.as_ref());
+            setting.state(document)
+        })
+        .collect()
+}
+
+// Écrire des valeurs déjà normalisées, un fichier à la fois ; renvoie le nombre de valeurs modifiées
+fn write(server_dir: &Path, values: &[(&SettingDef, String)]) -> Result<usize, String> {
+    let mut modified = 0;
+
+    for file in ConfigFile::ALL {
+        let for_file: Vec<&(&SettingDef, String)> = values.iter().filter(|(s, _)| s.file == file).collect();
+        if for_file.is_empty() {
+            continue;
+        }
+
+        // Un fichier incomplet créé à la main empêcherait le serveur de générer le sien
+        let path = server_dir.join(file.relative_path());
+        let mut document = YamlFile::load(&path)
+            .map_err(|_| format!("{} introuvable : démarrez le serveur une première fois pour le générer", file.relative_path()))?;
+
+        let mut changed = 0;
+        for (setting, value) in for_file {
+            if document.set(setting.path, value)? {
+                changed += 1;
+            }
+        }
+        if changed > 0 {
+            document.save(&path)?;
+            println!("✏️ {}: {} réglage(s) modifié(s)", file.relative_path(), changed);
+        }
+        modified += changed;
+    }
+
+    Ok(modified)
+}
+
+// Valider puis écrire les modifications de l'éditeur ; toutes les erreurs sont renvoyées ensemble
+pub fn update(server_dir: &Path, changes: &[SettingChange]) -> Result<usize, String> {
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+
+    for change in changes {
+        // null : réglage laissé tel quel
+        if change.value.is_null() {
+            continue;
+        }
+        let Some(setting) = ConfigFile::from_id(&change.file).and_then(|file| find(file, &change.path)) else {
+            errors.push(format!("Réglage inconnu: {} ({})", change.path, change.file));
+            continue;
+        };
+        match setting.normalize(&change.value) {
+            Ok(value) => values.push((setting, value)),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(format!("Réglages invalides: {}", errors.join("; ")));
+    }
+    write(server_dir, &values)
+}
+
+pub fn presets() -> Vec<PresetInfo> {
+    let mut list = vec![PresetInfo {
+        id: DEFAULTS_PRESET.to_string(),
+        name: "Valeurs par défaut".to_string(),
+        description: "Remet chaque réglage du schéma à sa valeur d'origine".to_string(),
+        settings: SETTINGS.len(),
+    }];
+    list.extend(PRESETS.iter().map(|p| PresetInfo {
+        id: p.id.to_string(),
+        name: p.name.to_string(),
+        description: p.description.to_string(),
+        settings: p.values.len(),
+    }));
+    list
+}
+
+fn preset_values(preset_id: &str) -> Result<Vec<(&'static SettingDef, String)>, String> {
+    if preset_id == DEFAULTS_PRESET {
+        return SETTINGS
+            .iter()
+            .map(|s| Ok((s, s.normalize(&serde_json::Value::String(s.default.to_string()))?)))
+            .collect();
+    }
+
+    let preset = PRESETS
+        .iter()
+        .find(|p| p.id == preset_id)
+        .ok_or_else(|| format!("Préréglage inconnu: {}", preset_id))?;
+    preset
+        .values
+        .iter()
+        .map(|(file, path, value)| {
+            let setting = find(*file, path).ok_or_else(|| format!("Réglage absent du schéma: {}", path))?;
+            Ok((setting, setting.normalize(&serde_json::Value::String(value.to_string()))?))
+        })
+        .collect()
+}
+
+// Différences entre un préréglage et les valeurs actuelles (fichiers absents ignorés)
+pub fn preview(server_dir: &Path, preset_id: &str) -> Result<Vec<SettingDiff>, String> {
+    let values = preset_values(preset_id)?;
+    let mut diffs = Vec::new();
+
+    for file in ConfigFile::ALL {
+        let Some(document) = load_file(server_dir, file) else {
+            if values.iter().any(|(s, _)| s.file == file) {
+                println!("⚠️ {} absent, réglages ignorés", file.relative_path());
+            }
+            continue;
+        };
+
+        for (setting, proposed) in values.iter().filter(|(s, _)| s.file == file) {
+            let current = document.get(setting.path);
+            if current.as_deref().is_some_and(|c| setting.same_value(c, proposed)) {
+                continue;
+            }
+            diffs.push(SettingDiff {
+                file: file.id().to_string(),
+                path: setting.path.to_string(),
+                description: setting.description.to_string(),
+                current,
+                proposed: proposed.clone(),
+            });
+        }
+    }
+
+    Ok(diffs)
+}
+
+// Appliquer un préréglage ; renvoie les différences effectivement écrites
+pub fn apply_preset(server_dir: &Path, preset_id: &str) -> Result<Vec<SettingDiff>, String> {
+    let diffs = preview(server_dir, preset_id)?;
+    let values: Vec<(&SettingDef, String)> = preset_values(preset_id)?
+        .into_iter()
+        .filter(|(s, _)| diffs.iter().any(|d| d.file == s.file.id() && d.path == s.path))
+        .collect();
+    write(server_dir, &values)?;
+    Ok(diffs)
+}
@@ -0,0 +1,687 @@
+// Module de téléchargement : streaming vers un fichier temporaire, reprise HTTP Range,
+// vérification d'empreinte et événements de progression
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+
+use crate::artifact_cache;
+use crate::endpoints;
+
+const MAX_ATTEMPTS: u32 = 5;
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(60);
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+// Attente maximale acceptée pour un Retry-After (408/429/503)
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+type ProgressListener = Box<dyn Fn(&DownloadProgress) + Send + Sync>;
+
+lazy_static::lazy_static! {
+    // Écouteur global des événements de progression (branché sur la fenêtre Tauri au démarrage)
+    static ref PROGRESS_LISTENER: Arc<Mutex<Option<ProgressListener>>> = Arc::new(Mutex::new(None));
+}
+
+// Empreinte attendue fournie par la source (manifest Mojang, API PaperMC, Modrinth, Maven...)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "algorithm", content = "value", rename_all = "lowercase")]
+pub enum Checksum {
+    Sha1(String),
+    Sha256(String),
+    Sha512(String),
+}
+
+impl Checksum {
+    pub fn algorithm(&self) -> &'static str {
+        match self {
+            Checksum::Sha1(_) => "sha1",
+            Checksum::Sha256(_) => "sha256",
+            Checksum::Sha512(_) => "sha512",
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        match self {
+            Checksum::Sha1(v) | Checksum::Sha256(v) | Checksum::Sha512(v) => v,
+        }
+    }
+
+    pub fn from_algorithm(algorithm: &str, value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+        if value.is_empty() {
+            return None;
+        }
+        match algorithm.to_lowercase().replace('-', "").as_str() {
+            "sha1" => Some(Checksum::Sha1(value)),
+            "sha256" => Some(Checksum::Sha256(value)),
+            "sha512" => Some(Checksum::Sha512(value)),
+            _ => None,
+        }
+    }
+
+    // Choisir l'empreinte la plus forte parmi un objet de hashes (format Modrinth: {"sha1": ..., "sha512": ...})
+    pub fn strongest(hashes: &HashMap<String, String>) -> Option<Self> {
+        ["sha512", "sha256", "sha1"]
+            .iter()
+            .find_map(|algo| hashes.get(*algo).and_then(|v| Checksum::from_algorithm(algo, v)))
+    }
+}
+
+// Événement de progression émis pendant un téléchargement
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadProgress {
+    pub id: String,
+    pub label: String,
+    pub url: String,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+    pub attempt: u32,
+    // "downloading", "verifying", "retrying", "completed" ou "failed"
+    pub status: String,
+    pub message: Option<String>,
+}
+
+// Description d'un téléchargement à effectuer
+#[derive(Debug, Clone)]
+pub struct DownloadRequest {
+    pub url: String,
+    pub destination: PathBuf,
+    pub checksum: Option<Checksum>,
+    pub label: String,
+    // Passer par le cache d'artefacts partagé (URL immuable ou empreinte connue)
+    pub cache: bool,
+}
+
+impl DownloadRequest {
+    pub fn new(url: &str, destination: &Path) -> Self {
+        let label = destination
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| url.to_string());
+
+        DownloadRequest {
+            url: url.to_string(),
+            destination: destination.to_path_buf(),
+            checksum: None,
+            label,
+            cache: false,
+        }
+    }
+
+    pub fn with_checksum(mut self, checksum: Option<Checksum>) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = label.to_string();
+        self
+    }
+
+    pub fn cached(mut self) -> Self {
+        self.cache = true;
+        self
+    }
+}
+
+// Résultat d'un téléchargement vérifié
+#[derive(Debug, Clone)]
+pub struct DownloadResult {
+    pub size: u64,
+    pub sha256: String,
+}
+
+// Erreur interne : permet de distinguer ce qui mérite une nouvelle tentative
+enum AttemptError {
+    Retryable(String),
+    // Serveur surchargé ou délai dépassé (408, 429, 503) : attendre le Retry-After s'il est fourni
+    Throttled(String, Option<Duration>),
+    Fatal(String),
+}
+
+impl AttemptError {
+    fn into_message(self) -> String {
+        match self {
+            AttemptError::Retryable(message) | AttemptError::Throttled(message, _) | AttemptError::Fatal(message) => message,
+        }
+    }
+}
+
+// Brancher l'écouteur de progression (appelé une seule fois au démarrage de l'application)
+pub fn set_progress_listener<F>(listener: F)
+where
+    F: Fn(&DownloadProgress) + Send + Sync + 'static,
+{
+    let mut current = PROGRESS_LISTENER.lock().unwrap();
+    *current = Some(Box::new(listener));
+}
+
+fn emit_progress(progress: &DownloadProgress) {
+    if let Some(listener) = PROGRESS_LISTENER.lock().unwrap().as_ref() {
+        listener(progress);
+    }
+}
+
+fn http_client() -> Result<reqwest::Client, String> {
+    // Pas de timeout global : les gros fichiers peuvent prendre longtemps, on surveille chaque bloc
+    reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Erreur création client HTTP: {}", e))
+}
+
+fn partial_path(destination: &Path) -> PathBuf {
+    let file_name = destination
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "download".to_string());
+    destination.with_file_name(format!("{}.part", file_name))
+}
+
+// Télécharger un fichier avec reprise, vérification et nouvelles tentatives
+pub async fn download(request: &DownloadRequest) -> Result<DownloadResult, String> {
+    if let Some(parent) = request.destination.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Erreur création dossier {}: {}", parent.display(), e))?;
+    }
+
+    // URL effective : miroir ou dossier local configuré pour cette source
+    let source = endpoints::rewrite(&request.url);
+    let id = uuid::Uuid::new_v4().to_string();
+    let mut progress = DownloadProgress {
+        id,
+        label: request.label.clone(),
+        url: source.clone(),
+        downloaded: 0,
+        total: None,
+        attempt: 0,
+        status: "downloading".to_string(),
+        message: None,
+    };
+
+    if request.cache {
+        // Vérification du SHA-256 de l'artefact en cache : hors du runtime async
+        let (url, checksum, destination) = (request.url.clone(), request.checksum.clone(), request.destination.clone());
+        let restored = tokio::task::spawn_blocking(move || artifact_cache::restore(&url, checksum.as_ref(), &destination))
+            .await
+            .unwrap_or(None);
+        if let Some(entry) = restored {
+            progress.downloaded = entry.size;
+            progress.total = Some(entry.size);
+            progress.status = "completed".to_string();
+            progress.message = Some("Récupéré depuis le cache".to_string());
+            emit_progress(&progress);
+            return Ok(DownloadResult {
+                size: entry.size,
+                sha256: entry.sha256,
+            });
+        }
+    }
+
+    if endpoints::is_offline() {
+        let message = format!("Mode hors ligne : {} n'est pas disponible dans le cache d'artefacts ({})", request.label, request.url);
+        progress.status = "failed".to_string();
+        progress.message = Some(message.clone());
+        emit_progress(&progress);
+        return Err(message);
+    }
+
+    // Dossier miroir local : simple copie vérifiée, sans nouvelle tentative
+    if let Some(local) = endpoints::local_path(&source) {
+        let local_request = request.clone();
+        let copied = tokio::task::spawn_blocking(move || copy_local(&local, &local_request))
+            .await
+            .unwrap_or_else(|e| Err(format!("Erreur copie depuis le miroir local: {}", e)));
+        return match copied {
+            Ok(result) => {
+                complete(request, &mut progress, &result).await;
+                Ok(result)
+            }
+            Err(e) => {
+                progress.status = "failed".to_string();
+                progress.message = Some(e.clone());
+                emit_progress(&progress);
+                Err(e)
+            }
+        };
+    }
+
+    let client = http_client()?;
+    let mut last_error = String::new();
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        progress.attempt = attempt;
+
+        match attempt_download(&client, request, &source, &mut progress).await {
+            Ok(result) => {
+                complete(request, &mut progress, &result).await;
+                return Ok(result);
+            }
+            Err(AttemptError::Fatal(e)) => {
+                last_error = e;
+                break;
+            }
+            Err(error) => {
+                // Attente exponentielle : 1s, 2s, 4s, 8s, sauf Retry-After explicite du serveur
+                let backoff = Duration::from_secs(1 << (attempt - 1));
+                let delay = match &error {
+                    AttemptError::Throttled(_, Some(retry_after)) => (*retry_after).min(MAX_RETRY_AFTER),
+                    _ => backoff,
+                };
+                let e = error.into_message();
+                println!("⚠️ Téléchargement {} (tentative {}/{}) échoué: {}", request.label, attempt, MAX_ATTEMPTS, e);
+                last_error = e;
+
+                if attempt < MAX_ATTEMPTS {
+                    progress.status = "retrying".to_string();
+                    progress.message = Some(last_error.clone());
+                    emit_progress(&progress);
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    progress.status = "failed".to_string();
+    progress.message = Some(last_error.clone());
+    emit_progress(&progress);
+
+    Err(format!("Échec du téléchargement de {}: {}", source, last_error))
+}
+
+// Ajouter le fichier au cache si demandé et signaler la fin du téléchargement
+async fn complete(request: &DownloadRequest, progress: &mut DownloadProgress, result: &DownloadResult) {
+    if request.cache {
+        // Copie dans le cache : hors du runtime async
+        let (destination, url, sha256, checksum) = (request.destination.clone(), request.url.clone(), result.sha256.clone(), request.checksum.clone());
+        let stored = tokio::task::spawn_blocking(move || artifact_cache::store(&destination, &url, &sha256, checksum.as_ref()))
+            .await
+            .unwrap_or_else(|e| Err(e.to_string()));
+        if let Err(e) = stored {
+            println!("⚠️ Impossible d'ajouter {} au cache: {}", request.label, e);
+        }
+    }
+    progress.downloaded = result.size;
+    progress.total = Some(result.size);
+    progress.status = "completed".to_string();
+    progress.message = None;
+    emit_progress(progress);
+}
+
+fn copy_local(local: &Path, request: &DownloadRequest) -> Result<DownloadResult, String> {
+    let part_path = partial_path(&request.destination);
+    fs::copy(local, &part_path)
+        .map_err(|e| format!("Erreur copie depuis le miroir local {}: {}", local.display(), e))?;
+
+    finalize(&part_path, &request.destination, request.checksum.as_ref()).map_err(AttemptError::into_message)
+}
+
+// Délai demandé par l'en-tête Retry-After (secondes ou date HTTP)
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let value = response.headers().get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some((date.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
+
+async fn attempt_download(
+    client: &reqwest::Client,
+    request: &DownloadRequest,
+    url: &str,
+    progress: &mut DownloadProgress,
+) -> Result<DownloadResult, AttemptError> {
+    let part_path = partial_path(&request.destination);
+    let already = tokio::fs::metadata(&part_path).await.map(|m| m.len()).unwrap_or(0);
+
+    let mut http_request = client.get(url);
+    if already > 0 {
+        http_request = http_request.header(reqwest::header::RANGE, format!("bytes={}-", already));
+    }
+
+    let mut response = http_request
+        .send()
+        .await
+        .map_err(|e| AttemptError::Retryable(format!("Erreur requête: {}", e)))?;
+
+    let status = response.status();
+
+    // Le fichier partiel est déjà complet ou invalide : on repart de zéro
+    if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        let _ = tokio::fs::remove_file(&part_path).await;
+        return Err(AttemptError::Retryable("Reprise impossible, redémarrage du téléchargement".to_string()));
+    }
+
+    // 408 et 429 : le serveur demande de réessayer plus tard ; les autres 4xx sont définitifs
+    if matches!(
+        status,
+        reqwest::StatusCode::REQUEST_TIMEOUT | reqwest::StatusCode::TOO_MANY_REQUESTS | reqwest::StatusCode::SERVICE_UNAVAILABLE
+    ) {
+        return Err(AttemptError::Throttled(format!("Erreur HTTP {}", status), retry_after(&response)));
+    }
+    if status.is_client_error() {
+        return Err(AttemptError::Fatal(format!("Erreur HTTP {}", status)));
+    }
+    if !status.is_success() {
+        return Err(AttemptError::Retryable(format!("Erreur HTTP {}", status)));
+    }
+
+    // 206 : le serveur accepte la reprise, sinon on réécrit le fichier depuis le début
+    let resumed = already > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT;
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&part_path)
+        .await
+        .map_err(|e| AttemptError::Fatal(format!("Erreur création fichier temporaire: {}", e)))?;
+
+    let offset = if resumed { already } else { 0 };
+    progress.downloaded = offset;
+    progress.total = response.content_length().map(|len| len + offset);
+    progress.status = "downloading".to_string();
+    progress.message = None;
+    emit_progress(progress);
+
+    let mut last_emit = Instant::now();
+
+    loop {
+        let chunk = tokio::time::timeout(CHUNK_TIMEOUT, response.chunk())
+            .await
+            .map_err(|_| AttemptError::Retryable("Aucune donnée reçue depuis 60 secondes".to_string()))?
+            .map_err(|e| AttemptError::Retryable(format!("Erreur lecture: {}", e)))?;
+
+        let Some(chunk) = chunk else { break };
+
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| AttemptError::Fatal(format!("Erreur écriture: {}", e)))?;
+        progress.downloaded += chunk.len() as u64;
+
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            emit_progress(progress);
+            last_emit = Instant::now();
+        }
+    }
+
+    file.flush()
+        .await
+        .map_err(|e| AttemptError::Fatal(format!("Erreur écriture: {}", e)))?;
+    drop(file);
+
+    if let Some(total) = progress.total {
+        if progress.downloaded < total {
+            return Err(AttemptError::Retryable(format!(
+                "Téléchargement incomplet ({} / {} octets)",
+                progress.downloaded, total
+            )));
+        }
+    }
+
+    progress.status = "verifying".to_string();
+    emit_progress(progress);
+
+    // Calcul d'empreinte d'un fichier potentiellement volumineux : hors du runtime async
+    let (destination, checksum) = (request.destination.clone(), request.checksum.clone());
+    tokio::task::spawn_blocking(move || finalize(&part_path, &destination, checksum.as_ref()))
+        .await
+        .map_err(|e| AttemptError::Fatal(format!("Erreur vérification: {}", e)))?
+}
+
+// Vérifier le fichier temporaire puis le déplacer vers sa destination finale
+fn finalize(part_path: &Path, destination: &Path, checksum: Option<&Checksum>) -> Result<DownloadResult, AttemptError> {
+    let digests = hash_file(part_path, checksum)
+        .map_err(|e| AttemptError::Fatal(format!("Erreur calcul empreinte: {}", e)))?;
+
+    if let (Some(expected), Some(actual)) = (checksum, digests.expected.as_ref()) {
+        if !expected.value().eq_ignore_ascii_case(actual) {
+            // Fichier corrompu : on le supprime pour repartir de zéro à la prochaine tentative
+            let _ = fs::remove_file(part_path);
+            return Err(AttemptError::Retryable(format!(
+                "Empreinte {} invalide (attendue {}, obtenue {})",
+                expected.algorithm(),
+                expected.value(),
+                actual
+            )));
+        }
+    }
+
+    // Supprimer la destination d'abord : elle peut être un lien physique vers un autre fichier
+    if destination.exists() {
+        fs::remove_file(destination)
+            .map_err(|e| AttemptError::Fatal(format!("Erreur remplacement {}: {}", destination.display(), e)))?;
+    }
+    fs::rename(part_path, destination)
+        .map_err(|e| AttemptError::Fatal(format!("Erreur déplacement vers {}: {}", destination.display(), e)))?;
+
+    Ok(DownloadResult {
+        size: digests.size,
+        sha256: digests.sha256,
+    })
+}
+
+struct FileDigests {
+    size: u64,
+    sha256: String,
+    expected: Option<String>,
+}
+
+// Calculer le SHA-256 (clé du cache) et l'empreinte avec l'algorithme attendu
+fn hash_file(path: &Path, checksum: Option<&Checksum>) -> std::io::Result<FileDigests> {
+    let mut file = fs::File::open(path)?;
+    let mut sha256 = Sha256::new();
+    let mut sha1 = matches!(checksum, Some(Checksum::Sha1(_))).then(Sha1::new);
+    let mut sha512 = matches!(checksum, Some(Checksum::Sha512(_))).then(Sha512::new);
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut size = 0u64;
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        let data = &buffer[..read];
+        sha256.update(data);
+        if let Some(hasher) = sha1.as_mut() {
+            hasher.update(data);
+        }
+        if let Some(hasher) = sha512.as_mut() {
+            hasher.update(data);
+        }
+        size += read as u64;
+    }
+
+    let sha256 = format!("{:x}", sha256.finalize());
+    let expected = match checksum {
+        Some(Checksum::Sha1(_)) => sha1.map(|h| format!("{:x}", h.finalize())),
+        Some(Checksum::Sha256(_)) => Some(sha256.clone()),
+        Some(Checksum::Sha512(_)) => sha512.map(|h| format!("{:x}", h.finalize())),
+        None => None,
+    };
+
+    Ok(FileDigests { size, sha256, expected })
+}
+
+// Récupérer l'empreinte publiée à côté d'un artefact Maven (fichiers .sha512/.sha256/.sha1)
+pub async fn fetch_maven_checksum(artifact_url: &str) -> Option<Checksum> {
+    for algorithm in ["sha512", "sha256", "sha1"] {
+        let url = format!("{}.{}", artifact_url, algorithm);
+        let Ok(bytes) = endpoints::fetch_bytes(&url).await else { continue };
+        // Certains fichiers contiennent "<hash>  <nom du fichier>"
+        let text = String::from_utf8_lossy(&bytes);
+        if let Some(value) = text.split_whitespace().next() {
+            if let Some(checksum) = Checksum::from_algorithm(algorithm, value) {
+                return Some(checksum);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    type Handler = Box<dyn Fn(usize, &str) -> Vec<u8> + Send + Sync>;
+
+    const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    // Serveur HTTP local : une réponse par connexion, en-têtes des requêtes conservés
+    async fn serve(handler: Handler) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/artifact.jar", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { return };
+                let mut head = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match socket.read(&mut buffer).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => head.extend_from_slice(&buffer[..read]),
+                    }
+                }
+                let head = String::from_utf8_lossy(&head).to_lowercase();
+                let number = {
+                    let mut seen = seen.lock().unwrap();
+                    seen.push(head.clone());
+                    seen.len()
+                };
+                let _ = socket.write_all(&handler(number, &head)).await;
+                let _ = socket.shutdown().await;
+            }
+        });
+
+        (url, requests)
+    }
+
+    fn response(status: &str, headers: &[String], body: &[u8]) -> Vec<u8> {
+        let mut out = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n", status, body.len());
+        for header in headers {
+            out.push_str(&format!("{}\r\n", header));
+        }
+        out.push_str("\r\n");
+        let mut out = out.into_bytes();
+        out.extend_from_slice(body);
+        out
+    }
+
+    fn range_start(head: &str) -> Option<usize> {
+        head.lines().find_map(|line| line.strip_prefix("range: bytes=")?.trim_end_matches('-').parse().ok())
+    }
+
+    fn destination(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nether-downloader-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    fn sha256_of(data: &[u8]) -> Checksum {
+        Checksum::Sha256(format!("{:x}", Sha256::digest(data)))
+    }
+
+    #[tokio::test]
+    async fn resumes_partial_file_with_range() {
+        let (url, requests) = serve(Box::new(|_, head| match range_start(head) {
+            Some(start) => response(
+                "206 Partial Content",
+                &[format!("Content-Range: bytes {}-{}/{}", start, BODY.len() - 1, BODY.len())],
+                &BODY[start..],
+            ),
+            None => response("200 OK", &[], BODY),
+        }))
+        .await;
+        let target = destination("resume.jar");
+        fs::write(partial_path(&target), &BODY[..10]).unwrap();
+
+        let request = DownloadRequest::new(&url, &target).with_checksum(Some(sha256_of(BODY)));
+        let result = download(&request).await.unwrap();
+
+        assert_eq!(fs::read(&target).unwrap(), BODY);
+        assert_eq!(result.size, BODY.len() as u64);
+        assert_eq!(range_start(&requests.lock().unwrap()[0]), Some(10));
+        assert!(!partial_path(&target).exists());
+    }
+
+    #[tokio::test]
+    async fn restarts_when_server_ignores_range() {
+        let (url, requests) = serve(Box::new(|_, _| response("200 OK", &[], BODY))).await;
+        let target = destination("ignored-range.jar");
+        fs::write(partial_path(&target), b"garbage").unwrap();
+
+        let request = DownloadRequest::new(&url, &target).with_checksum(Some(sha256_of(BODY)));
+        download(&request).await.unwrap();
+
+        assert_eq!(fs::read(&target).unwrap(), BODY);
+        assert_eq!(range_start(&requests.lock().unwrap()[0]), Some(7));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_after_checksum_mismatch() {
+        let (url, requests) = serve(Box::new(|number, _| {
+            if number == 1 {
+                response("200 OK", &[], b"corrupted content of the same size!!")
+            } else {
+                response("200 OK", &[], BODY)
+            }
+        }))
+        .await;
+        let target = destination("mismatch.jar");
+
+        let request = DownloadRequest::new(&url, &target).with_checksum(Some(sha256_of(BODY)));
+        download(&request).await.unwrap();
+
+        assert_eq!(fs::read(&target).unwrap(), BODY);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+        // Le fichier corrompu est supprimé : la seconde tentative ne reprend pas dessus
+        assert_eq!(range_start(&requests.lock().unwrap()[1]), None);
+    }
+
+    #[tokio::test]
+    async fn not_found_is_fatal() {
+        let (url, requests) = serve(Box::new(|_, _| response("404 Not Found", &[], b"missing"))).await;
+        let target = destination("missing.jar");
+
+        let error = download(&DownloadRequest::new(&url, &target)).await.unwrap_err();
+
+        assert!(error.contains("404"), "{}", error);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert!(!target.exists());
+    }
+
+    #[tokio::test]
+    async fn too_many_requests_honours_retry_after() {
+        let (url, requests) = serve(Box::new(|number, _| {
+            if number == 1 {
+                response("429 Too Many Requests", &["Retry-After: 0".to_string()], b"")
+            } else {
+                response("200 OK", &[], BODY)
+            }
+        }))
+        .await;
+        let target = destination("throttled.jar");
+
+        let started = Instant::now();
+        download(&DownloadRequest::new(&url, &target)).await.unwrap();
+
+        assert_eq!(fs::read(&target).unwrap(), BODY);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+        // Retry-After: 0 remplace l'attente exponentielle d'une seconde
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,346 @@
+// Configuration centrale des sources de téléchargement (miroirs, dossiers locaux, mode hors ligne)
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::app_paths;
+use crate::artifact_cache;
+
+lazy_static::lazy_static! {
+    static ref ENDPOINT_CONFIG: Arc<Mutex<Option<EndpointConfig>>> = Arc::new(Mutex::new(None));
+}
+
+// Sources externes utilisées par l'application
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Upstream {
+    Mojang,
+    PaperMc,
+    Purpur,
+    Mohist,
+    ForgeMaven,
+    NeoForgeMaven,
+    Adoptium,
+    Temurin,
+    Playit,
+}
+
+impl Upstream {
+    pub const ALL: [Upstream; 9] = [
+        Upstream::Mojang,
+        Upstream::PaperMc,
+        Upstream::Purpur,
+        Upstream::Mohist,
+        Upstream::ForgeMaven,
+        Upstream::NeoForgeMaven,
+        Upstream::Adoptium,
+        Upstream::Temurin,
+        Upstream::Playit,
+    ];
+
+    // Identifiant utilisé comme clé dans endpoints.json
+    pub fn id(&self) -> &'static str {
+        match self {
+            Upstream::Mojang => "mojang",
+            Upstream::PaperMc => "papermc",
+            Upstream::Purpur => "purpur",
+            Upstream::Mohist => "mohist",
+            Upstream::ForgeMaven => "forge",
+            Upstream::NeoForgeMaven => "neoforge",
+            Upstream::Adoptium => "adoptium",
+            Upstream::Temurin => "temurin",
+            Upstream::Playit => "playit",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Upstream::Mojang => "Mojang",
+            Upstream::PaperMc => "PaperMC",
+            Upstream::Purpur => "Purpur",
+            Upstream::Mohist => "MohistMC",
+            Upstream::ForgeMaven => "Forge Maven",
+            Upstream::NeoForgeMaven => "NeoForge Maven",
+            Upstream::Adoptium => "Adoptium",
+            Upstream::Temurin => "Temurin (GitHub)",
+            Upstream::Playit => "Playit.gg (GitHub)",
+        }
+    }
+
+    // URL de base officielle
+    pub fn default_base(&self) -> &'static str {
+        match self {
+            Upstream::Mojang => "https://launchermeta.mojang.com",
+            Upstream::PaperMc => "https://api.papermc.io",
+            Upstream::Purpur => "https://api.purpurmc.org",
+            Upstream::Mohist => "https://mohistmc.com",
+            Upstream::ForgeMaven => "https://maven.minecraftforge.net",
+            Upstream::NeoForgeMaven => "https://maven.neoforged.net",
+            Upstream::Adoptium => "https://api.adoptium.net",
+            // Archives JDK référencées par l'API Adoptium (temurinXX-binaries)
+            Upstream::Temurin => "https://github.com/adoptium",
+            Upstream::Playit => "https://github.com/playit-cloud/playit-agent",
+        }
+    }
+
+    // Hôtes supplémentaires dont les URLs (issues des manifests) sont redirigées vers le miroir
+    fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Upstream::Mojang => &[
+                "https://piston-meta.mojang.com",
+                "https://piston-data.mojang.com",
+                "https://launcher.mojang.com",
+            ],
+            _ => &[],
+        }
+    }
+
+    // API REST : ses réponses n'ont pas de nom de fichier
+    fn serves_api(&self) -> bool {
+        matches!(self, Upstream::PaperMc | Upstream::Purpur | Upstream::Mohist | Upstream::Adoptium)
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        Upstream::ALL.iter().copied().find(|u| u.id() == id)
+    }
+}
+
+// Contenu de endpoints.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EndpointConfig {
+    // Mode hors ligne : tout est résolu depuis le cache d'artefacts
+    #[serde(default)]
+    pub offline: bool,
+    // Remplacement de l'URL de base par source (miroir HTTP, serveur de test ou dossier local)
+    #[serde(default)]
+    pub overrides: HashMap<String, String>,
+}
+
+// Vue d'une source pour l'interface
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointInfo {
+    pub id: String,
+    pub name: String,
+    pub default_url: String,
+    pub url: String,
+    pub overridden: bool,
+}
+
+fn config_path() -> Result<PathBuf, String> {
+    Ok(app_paths::app_dir()?.join("endpoints.json"))
+}
+
+// Configuration enregistrée : un fichier absent donne les sources officielles,
+// un fichier illisible ou invalide est une erreur
+pub fn config() -> Result<EndpointConfig, String> {
+    let mut cached = ENDPOINT_CONFIG.lock().unwrap();
+    if let Some(current) = cached.as_ref() {
+        return Ok(current.clone());
+    }
+
+    let path = config_path()?;
+    let loaded: EndpointConfig = match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| format!("endpoints.json invalide ({}): {}", path.display(), e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => EndpointConfig::default(),
+        Err(e) => return Err(format!("Erreur lecture {}: {}", path.display(), e)),
+    };
+    *cached = Some(loaded.clone());
+    Ok(loaded)
+}
+
+// Configuration utilisée pour résoudre une requête : en cas d'erreur elle est signalée
+// et les sources officielles sont utilisées
+fn effective_config() -> EndpointConfig {
+    config().unwrap_or_else(|e| {
+        println!("⚠️ Configuration des sources ignorée: {}", e);
+        EndpointConfig::default()
+    })
+}
+
+pub fn save_config(mut new_config: EndpointConfig) -> Result<EndpointConfig, String> {
+    // Valider les clés et ignorer les valeurs vides (retour à l'URL officielle)
+    for id in new_config.overrides.keys() {
+        if Upstream::from_id(id).is_none() {
+            return Err(format!("Source inconnue: {}", id));
+        }
+    }
+    new_config.overrides.retain(|_, base| !base.trim().is_empty());
+    for base in new_config.overrides.values_mut() {
+        *base = base.trim().trim_end_matches(['/', '\\']).to_string();
+    }
+
+    let path = config_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Erreur création dossier: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(&new_config)
+        .map_err(|e| format!("Erreur sérialisation: {}", e))?;
+    fs::write(&path, json)
+        .map_err(|e| format!("Erreur écriture endpoints.json: {}", e))?;
+
+    *ENDPOINT_CONFIG.lock().unwrap() = Some(new_config.clone());
+    println!("Configuration des sources mise à jour (hors ligne: {})", new_config.offline);
+
+    Ok(new_config)
+}
+
+pub fn is_offline() -> bool {
+    effective_config().offline
+}
+
+pub fn list() -> Result<Vec<EndpointInfo>, String> {
+    let current = config()?;
+    Ok(Upstream::ALL
+        .iter()
+        .map(|upstream| {
+            let custom = current.overrides.get(upstream.id());
+            EndpointInfo {
+                id: upstream.id().to_string(),
+                name: upstream.display_name().to_string(),
+                default_url: upstream.default_base().to_string(),
+                url: custom.cloned().unwrap_or_else(|| upstream.default_base().to_string()),
+                overridden: custom.is_some(),
+            }
+        })
+        .collect())
+}
+
+// URL officielle d'une ressource (ex: "/v2/projects/paper"). La redirection vers le miroir
+// est faite au moment de la requête pour que les clés du cache restent les mêmes.
+pub fn url(upstream: Upstream, path: &str) -> String {
+    format!("{}{}", upstream.default_base(), path)
+}
+
+// Source officielle d'une URL et reste du chemin après sa base
+fn split_upstream(url: &str) -> Option<(Upstream, &str)> {
+    for upstream in Upstream::ALL {
+        let prefixes = std::iter::once(upstream.default_base()).chain(upstream.aliases().iter().copied());
+        for prefix in prefixes {
+            // Le préfixe doit s'arrêter à une limite de chemin (pas "https://api.papermc.io.evil/")
+            let Some(rest) = url.strip_prefix(prefix) else { continue };
+            if rest.is_empty() || rest.starts_with('/') || rest.starts_with('?') {
+                return Some((upstream, rest));
+            }
+        }
+    }
+    None
+}
+
+// Rediriger une URL officielle vers le miroir configuré (les autres URLs sont inchangées)
+pub fn rewrite(url: &str) -> String {
+    let current = effective_config();
+
+    let Some((upstream, rest)) = split_upstream(url) else { return url.to_string() };
+    let Some(base) = current.overrides.get(upstream.id()) else { return url.to_string() };
+
+    if base.contains("://") {
+        format!("{}{}", base, rest)
+    } else {
+        // Dossier local : même arborescence que le serveur d'origine
+        format!("file://{}{}", base, rest)
+    }
+}
+
+// Dans un dossier miroir, une réponse d'API est rangée dans <chemin>/index.json :
+// "/v2/projects/paper" est aussi le parent de "/v2/projects/paper/versions/..."
+const API_RESPONSE_FILE: &str = "index.json";
+
+// Chemin local si l'URL pointe vers un dossier miroir
+pub fn local_path(url: &str) -> Option<PathBuf> {
+    let path = url.strip_prefix("file://")?;
+    // Retirer les paramètres de requête qui n'ont pas de sens sur disque
+    let path = path.split('?').next().unwrap_or(path);
+    Some(PathBuf::from(path))
+}
+
+// Réponse d'une requête conditionnelle (If-None-Match)
+pub enum Fetched {
+    NotModified,
+    Modified { bytes: Vec<u8>, etag: Option<String> },
+}
+
+// Récupérer une ressource (métadonnées JSON, fichiers d'empreinte) en passant par le miroir
+// et le cache d'artefacts. En ligne, chaque réponse est mise en cache pour le mode hors ligne.
+pub async fn fetch_bytes(url: &str) -> Result<Vec<u8>, String> {
+    match fetch_conditional(url, None).await? {
+        Fetched::Modified { bytes, .. } => Ok(bytes),
+        Fetched::NotModified => Err(format!("Réponse 304 inattendue pour {}", url)),
+    }
+}
+
+// Variante avec revalidation : renvoie NotModified si l'ETag fourni est toujours valide
+pub async fn fetch_conditional(url: &str, etag: Option<&str>) -> Result<Fetched, String> {
+    if is_offline() {
+        return artifact_cache::read_by_url(url)
+            .map(|bytes| Fetched::Modified { bytes, etag: None })
+            .ok_or_else(|| format!("Mode hors ligne : {} n'est pas disponible dans le cache d'artefacts", url));
+    }
+
+    let source = rewrite(url);
+
+    let (bytes, new_etag) = if let Some(mut path) = local_path(&source) {
+        if split_upstream(url).map(|(upstream, _)| upstream.serves_api()).unwrap_or(false) {
+            path.push(API_RESPONSE_FILE);
+        }
+        let bytes = tokio::fs::read(&path)
+            .await
+            .map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+        (bytes, None)
+    } else {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| format!("Erreur création client HTTP: {}", e))?;
+
+        let mut request = client.get(&source).header("Accept", "application/json");
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Erreur requête {}: {}", source, e))?;
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(Fetched::NotModified);
+        }
+        if !response.status().is_success() {
+            return Err(format!("Erreur HTTP {} pour {}", response.status(), source));
+        }
+
+        let new_etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| format!("Erreur lecture réponse {}: {}", source, e))?
+            .to_vec();
+
+        (bytes, new_etag)
+    };
+
+    // Écriture du cache et de son index : hors du runtime async
+    let cache_url = url.to_string();
+    let bytes = tokio::task::spawn_blocking(move || {
+        if let Err(e) = artifact_cache::store_bytes(&cache_url, &bytes) {
+            println!("⚠️ Impossible de mettre {} en cache: {}", cache_url, e);
+        }
+        bytes
+    })
+    .await
+    .map_err(|e| format!("Erreur mise en cache de {}: {}", url, e))?;
+
+    Ok(Fetched::Modified { bytes, etag: new_etag })
+}
+
+pub async fn fetch_json(url: &str) -> Result<serde_json::Value, String> {
+    let bytes = fetch_bytes(url).await?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("Erreur parsing JSON ({}): {}", url, e))
+}
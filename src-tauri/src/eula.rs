@@ -0,0 +1,94 @@
+// Acceptation de l'EULA Minecraft : eula.txt n'est passé à true que sur demande explicite,
+// et l'auteur et la date de l'acceptation sont conservés dans nether-config.json
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::properties_file::PropertiesFile;
+use crate::server_config;
+
+pub const EULA_URL: &str = "https://aka.ms/MinecraftEULA";
+const EULA_FILE: &str = "eula.txt";
+const RECORD_KEY: &str = "eula";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EulaAcceptance {
+    pub accepted_by: String,
+    // Date RFC 3339
+    pub accepted_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EulaStatus {
+    // eula=true dans eula.txt
+    pub accepted: bool,
+    // Absent pour une EULA acceptée hors de Nether Client (serveur importé)
+    pub record: Option<EulaAcceptance>,
+    pub url: String,
+}
+
+// Utilisateur du système, à défaut d'un nom fourni
+fn system_user() -> String {
+    std::env::var("USERNAME")
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or_else(|_| "inconnu".to_string())
+}
+
+// Valider l'acceptation demandée à la création d'un serveur ; renvoie qui l'accepte
+pub fn require(accepted: bool, accepted_by: Option<&str>) -> Result<String, String> {
+    if !accepted {
+        return Err(format!("L'EULA Minecraft doit être acceptée pour créer un serveur ({})", EULA_URL));
+    }
+    Ok(accepted_by
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string())
+        .unwrap_or_else(system_user))
+}
+
+pub fn is_accepted(server_dir: &Path) -> bool {
+    PropertiesFile::load(&server_dir.join(EULA_FILE))
+        .ok()
+        .and_then(|eula| eula.get("eula").map(|value| value.trim().eq_ignore_ascii_case("true")))
+        .unwrap_or(false)
+}
+
+pub fn record(server_dir: &Path) -> Option<EulaAcceptance> {
+    let content = fs::read_to_string(server_dir.join(server_config::CONFIG_FILE)).ok()?;
+    let config: serde_json::Value = serde_json::from_str(&content).ok()?;
+    serde_json::from_value(config.get(RECORD_KEY)?.clone()).ok()
+}
+
+pub fn status(server_dir: &Path) -> EulaStatus {
+    EulaStatus {
+        accepted: is_accepted(server_dir),
+        record: record(server_dir),
+        url: EULA_URL.to_string(),
+    }
+}
+
+// Écrire eula=true (en gardant l'en-tête d'un eula.txt existant) et enregistrer l'acceptation
+pub fn accept(server_dir: &Path, accepted_by: &str) -> Result<EulaAcceptance, String> {
+    let eula_path = server_dir.join(EULA_FILE);
+    let mut eula = if eula_path.exists() {
+        PropertiesFile::load(&eula_path)?
+    } else {
+        PropertiesFile::parse(&format!(
+            "#By changing the setting below to TRUE you are indicating your agreement to our EULA ({}).\n",
+            EULA_URL
+        ))
+    };
+    eula.set("eula", "true");
+    eula.save(&eula_path)?;
+
+    let acceptance = EulaAcceptance {
+        accepted_by: accepted_by.to_string(),
+        accepted_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let record = serde_json::to_value(&acceptance).map_err(|e| format!("Erreur sérialisation: {}", e))?;
+    server_config::update(server_dir, |config| config[RECORD_KEY] = record)?;
+
+    println!("📜 EULA acceptée par {} pour {}", acceptance.accepted_by, server_dir.display());
+    Ok(acceptance)
+}
@@ -0,0 +1,39 @@
+// Copie récursive de dossiers serveur (clones, modèles, imports, vérifications de mise à jour)
+use std::fs;
+use std::path::Path;
+
+// Verrou du monde tenu par un serveur démarré : jamais copié
+const SESSION_LOCK: &str = "session.lock";
+
+// Nom utilisable comme entrée directe d'un dossier : un seul composant, ni ".", ni "..", ni séparateur
+pub fn is_plain_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(components.next(), Some(std::path::Component::Normal(_)))
+        && components.next().is_none()
+        && !name.contains(['/', '\\'])
+}
+
+pub fn copy_tree(from: &Path, to: &Path) -> Result<(), String> {
+    copy_tree_except(from, to, &|_| false)
+}
+
+// Copie en ignorant les entrées (chemins source) pour lesquelles `skip` renvoie true
+pub fn copy_tree_except(from: &Path, to: &Path, skip: &dyn Fn(&Path) -> bool) -> Result<(), String> {
+    if from.is_file() {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Erreur création de {}: {}", parent.display(), e))?;
+        }
+        fs::copy(from, to).map_err(|e| format!("Erreur copie de {}: {}", from.display(), e))?;
+        return Ok(());
+    }
+
+    fs::create_dir_all(to).map_err(|e| format!("Erreur création de {}: {}", to.display(), e))?;
+    for entry in fs::read_dir(from).map_err(|e| format!("Erreur lecture de {}: {}", from.display(), e))?.flatten() {
+        let path = entry.path();
+        if entry.file_name() == SESSION_LOCK || skip(&path) {
+            continue;
+        }
+        copy_tree_except(&path, &to.join(entry.file_name()), skip)?;
+    }
+    Ok(())
+}
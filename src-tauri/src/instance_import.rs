@@ -0,0 +1,300 @@
+// Import d'instances client (Prism Launcher / MultiMC, CurseForge) vers un serveur :
+// loader et version depuis les fichiers d'instance, copie des mods sans les mods client uniquement
+use crate::file_tree;
+use serde::Serialize;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+// Dossiers de l'instance copiés dans le serveur
+const CONTENT_DIRS: [&str; 2] = ["config", "defaultconfigs"];
+
+// Mods connus pour ne fonctionner que côté client (identifiants ou préfixes de nom de fichier)
+const CLIENT_ONLY_MODS: [&str; 48] = [
+    "optifine", "optifabric", "sodium", "sodium-extra", "reeses-sodium-options", "embeddium", "rubidium",
+    "magnesium", "iris", "oculus", "nvidium", "immediatelyfast", "entityculling", "cull-leaves",
+    "lambdynamiclights", "dynamiclights", "dynamic-lights", "modmenu", "controlling", "mousetweaks",
+    "mouse-tweaks", "zoomify", "ok-zoomer", "okzoomer", "betterf3", "legendarytooltips", "fancymenu",
+    "drippyloadingscreen", "notenoughanimations", "skinlayers3d", "3dskinlayers", "chat_heads", "chat-heads",
+    "torohealth", "citresewn", "continuity", "entity_model_features", "entity_texture_features",
+    "presencefootsteps", "betterthirdperson", "firstperson", "fpsreducer", "reauth", "catalogue",
+    "ambientsounds", "blur", "enhancedvisuals", "xaerominimap",
+];
+
+// Instance client lue depuis le disque
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientInstance {
+    pub path: String,
+    // "prism" (Prism Launcher / MultiMC) ou "curseforge"
+    pub launcher: String,
+    pub name: String,
+    pub minecraft_version: String,
+    // "vanilla", "forge", "neoforge", "fabric" ou "quilt"
+    pub loader: String,
+    pub loader_version: Option<String>,
+    // Dossier de jeu (.minecraft pour Prism, racine de l'instance pour CurseForge)
+    pub game_dir: String,
+}
+
+impl ClientInstance {
+    // Type de serveur et version attendus par les commandes de création
+    pub fn server_target(&self) -> Result<(String, String), String> {
+        match (self.loader.as_str(), &self.loader_version) {
+            ("vanilla", _) => Ok(("vanilla".to_string(), self.minecraft_version.clone())),
+            ("forge", Some(version)) => Ok(("forge".to_string(), loader_coordinates(&self.minecraft_version, version))),
+            // NeoForge 1.20.1 publie sous les coordonnées Forge ("47.1.84" -> "1.20.1-47.1.84")
+            ("neoforge", Some(version)) if self.minecraft_version == "1.20.1" => {
+                Ok(("neoforge".to_string(), loader_coordinates(&self.minecraft_version, version)))
+            }
+            ("neoforge", Some(version)) => Ok(("neoforge".to_string(), version.clone())),
+            ("forge" | "neoforge", None) => Err(format!("Version de {} introuvable dans l'instance", self.loader)),
+            (loader, _) => Err(format!("Les serveurs {} ne sont pas encore pris en charge", loader)),
+        }
+    }
+}
+
+// Version Maven "<minecraft>-<loader>" quel que soit le format d'origine : "47.2.0" (Prism),
+// "1.7.10-10.13.4.1614" ou "forge-1.12.2-14.23.5.2859" (anciens instance.cfg MultiMC)
+fn loader_coordinates(minecraft_version: &str, version: &str) -> String {
+    let version = version.trim();
+    let version = version.strip_prefix("forge-").unwrap_or(version);
+    if version.starts_with(&format!("{}-", minecraft_version)) {
+        version.to_string()
+    } else {
+        format!("{}-{}", minecraft_version, version)
+    }
+}
+
+// Classement d'un mod de l'instance
+#[derive(Debug, Clone, Serialize)]
+pub struct InstanceMod {
+    pub file_name: String,
+    pub mod_id: Option<String>,
+    pub client_only: bool,
+    // Raison du classement client uniquement ("métadonnées", "liste connue")
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InstanceCopyReport {
+    pub copied_mods: Vec<String>,
+    pub skipped_mods: Vec<InstanceMod>,
+    pub copied_folders: Vec<String>,
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+// Lire instance.cfg (clé=valeur)
+fn instance_cfg(path: &Path, key: &str) -> Option<String> {
+    fs::read_to_string(path.join("instance.cfg"))
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix(key).and_then(|rest| rest.strip_prefix('=')).map(|v| v.trim().to_string()))
+        .filter(|v| !v.is_empty())
+}
+
+fn read_prism(path: &Path) -> Result<ClientInstance, String> {
+    let folder_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let name = instance_cfg(path, "name").unwrap_or(folder_name);
+
+    let mut minecraft_version = None;
+    let mut loader = ("vanilla", None);
+
+    if let Some(pack) = read_json(&path.join("mmc-pack.json")) {
+        for component in pack["components"].as_array().into_iter().flatten() {
+            let version = component["version"].as_str().map(|v| v.to_string());
+            match component["uid"].as_str().unwrap_or_default() {
+                "net.minecraft" => minecraft_version = version,
+                "net.minecraftforge" => loader = ("forge", version),
+                "net.neoforged" => loader = ("neoforge", version),
+                "net.fabricmc.fabric-loader" => loader = ("fabric", version),
+                "org.quiltmc.quilt-loader" => loader = ("quilt", version),
+                _ => {}
+            }
+        }
+    } else {
+        // Anciennes instances MultiMC : versions dans instance.cfg
+        minecraft_version = instance_cfg(path, "IntendedVersion");
+        if let Some(forge) = instance_cfg(path, "ForgeVersion") {
+            loader = ("forge", Some(forge));
+        }
+    }
+
+    let game_dir = [".minecraft", "minecraft"]
+        .iter()
+        .map(|dir| path.join(dir))
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| path.join(".minecraft"));
+
+    Ok(ClientInstance {
+        path: path.to_string_lossy().to_string(),
+        launcher: "prism".to_string(),
+        name,
+        minecraft_version: minecraft_version.ok_or("Version Minecraft introuvable dans l'instance")?,
+        loader: loader.0.to_string(),
+        loader_version: loader.1,
+        game_dir: game_dir.to_string_lossy().to_string(),
+    })
+}
+
+fn read_curseforge(path: &Path) -> Result<ClientInstance, String> {
+    let data = read_json(&path.join("minecraftinstance.json"))
+        .ok_or("minecraftinstance.json illisible")?;
+
+    // baseModLoader.name : "forge-47.2.0", "neoforge-20.4.80", "fabric-0.15.3-1.20.1"
+    let (loader, loader_version) = match data["baseModLoader"]["name"].as_str() {
+        Some(name) => {
+            let (kind, version) = name.split_once('-').unwrap_or((name, ""));
+            let version = match kind {
+                "forge" => data["baseModLoader"]["forgeVersion"].as_str().unwrap_or(version).to_string(),
+                "fabric" | "quilt" => version.split('-').next().unwrap_or(version).to_string(),
+                _ => version.to_string(),
+            };
+            (kind.to_lowercase(), (!version.is_empty()).then_some(version))
+        }
+        None => ("vanilla".to_string(), None),
+    };
+
+    let minecraft_version = data["gameVersion"]
+        .as_str()
+        .or(data["baseModLoader"]["minecraftVersion"].as_str())
+        .ok_or("Version Minecraft introuvable dans l'instance")?
+        .to_string();
+
+    Ok(ClientInstance {
+        path: path.to_string_lossy().to_string(),
+        launcher: "curseforge".to_string(),
+        name: data["name"].as_str().map(|n| n.to_string()).unwrap_or_else(|| {
+            path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+        }),
+        minecraft_version,
+        loader,
+        loader_version,
+        game_dir: path.to_string_lossy().to_string(),
+    })
+}
+
+pub fn read_instance(instance_path: &str) -> Result<ClientInstance, String> {
+    let path = Path::new(instance_path);
+    if path.join("minecraftinstance.json").exists() {
+        read_curseforge(path)
+    } else if path.join("mmc-pack.json").exists() || path.join("instance.cfg").exists() {
+        read_prism(path)
+    } else {
+        Err("Aucune instance Prism/MultiMC (mmc-pack.json, instance.cfg) ou CurseForge (minecraftinstance.json) trouvée".to_string())
+    }
+}
+
+// Identifiant et environnement déclarés dans les métadonnées du mod
+fn mod_metadata(jar: &Path) -> (Option<String>, bool) {
+    let Ok(file) = fs::File::open(jar) else { return (None, false) };
+    let Ok(mut archive) = zip::ZipArchive::new(file) else { return (None, false) };
+
+    let mut read = |name: &str| {
+        let mut content = String::new();
+        archive.by_name(name).ok()?.read_to_string(&mut content).ok()?;
+        Some(content)
+    };
+
+    // Fabric / Quilt : "environment": "client"
+    if let Some(json) = read("fabric.mod.json").and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok()) {
+        let client_only = json["environment"].as_str() == Some("client");
+        return (json["id"].as_str().map(|s| s.to_string()), client_only);
+    }
+
+    // Forge / NeoForge : clientSideOnly=true (1.20.4+)
+    let toml = read("META-INF/mods.toml").or_else(|| read("META-INF/neoforge.mods.toml"));
+    if let Some(toml) = toml {
+        let value = |key: &str| {
+            toml.lines().find_map(|line| {
+                let (k, v) = line.split_once('=')?;
+                (k.trim() == key).then(|| v.trim().trim_matches('"').to_string())
+            })
+        };
+        let client_only = value("clientSideOnly").as_deref() == Some("true");
+        return (value("modId"), client_only);
+    }
+
+    (None, false)
+}
+
+fn is_known_client_mod(file_name: &str, mod_id: Option<&str>) -> bool {
+    let lower = file_name.to_lowercase();
+    CLIENT_ONLY_MODS.iter().any(|known| {
+        mod_id.map(|id| id.eq_ignore_ascii_case(known)).unwrap_or(false)
+            // "sodium-fabric-0.5.3.jar" mais pas "sodiumdynamiclights" ni "irisflowers"
+            || lower
+                .strip_prefix(known)
+                .map(|rest| rest.starts_with(['-', '_', '+', ' ', '.']))
+                .unwrap_or(false)
+    })
+}
+
+// Classer les mods du dossier mods/ de l'instance
+pub fn classify_mods(instance: &ClientInstance) -> Vec<InstanceMod> {
+    let mods_dir = Path::new(&instance.game_dir).join("mods");
+    let Ok(entries) = fs::read_dir(&mods_dir) else { return Vec::new() };
+
+    let mut mods: Vec<InstanceMod> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map(|ext| ext.eq_ignore_ascii_case("jar")).unwrap_or(false))
+        .map(|path| {
+            let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let (mod_id, declared_client) = mod_metadata(&path);
+            let reason = if declared_client {
+                Some("métadonnées".to_string())
+            } else if is_known_client_mod(&file_name, mod_id.as_deref()) {
+                Some("liste connue".to_string())
+            } else {
+                None
+            };
+            InstanceMod {
+                file_name,
+                mod_id,
+                client_only: reason.is_some(),
+                reason,
+            }
+        })
+        .collect();
+    mods.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    mods
+}
+
+// Copier mods (sans les mods client uniquement), config et defaultconfigs dans le serveur
+pub fn copy_content(instance: &ClientInstance, server_dir: &Path) -> Result<InstanceCopyReport, String> {
+    let game_dir = PathBuf::from(&instance.game_dir);
+    let mut report = InstanceCopyReport::default();
+
+    let mods_dir = server_dir.join("mods");
+    fs::create_dir_all(&mods_dir).map_err(|e| format!("Erreur création dossier mods: {}", e))?;
+
+    for instance_mod in classify_mods(instance) {
+        if instance_mod.client_only {
+            println!("Mod client ignoré: {} ({})", instance_mod.file_name, instance_mod.reason.as_deref().unwrap_or_default());
+            report.skipped_mods.push(instance_mod);
+            continue;
+        }
+        fs::copy(game_dir.join("mods").join(&instance_mod.file_name), mods_dir.join(&instance_mod.file_name))
+            .map_err(|e| format!("Erreur copie du mod {}: {}", instance_mod.file_name, e))?;
+        report.copied_mods.push(instance_mod.file_name);
+    }
+
+    for folder in CONTENT_DIRS {
+        let source = game_dir.join(folder);
+        if source.is_dir() {
+            file_tree::copy_tree(&source, &server_dir.join(folder))?;
+            report.copied_folders.push(folder.to_string());
+        }
+    }
+
+    println!(
+        "Instance {} importée: {} mods copiés, {} mods client ignorés",
+        instance.name,
+        report.copied_mods.len(),
+        report.skipped_mods.len()
+    );
+    Ok(report)
+}
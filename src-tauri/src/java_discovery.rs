@@ -0,0 +1,256 @@
+// Découverte des installations Java (Windows, Linux, macOS) : chaque runtime est identifié par son
+// dossier JAVA_HOME réel, décrit par son fichier `release` et signalé une seule fois
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::java_requirement::JavaRequirement;
+use crate::java_version::JavaVersion;
+
+use crate::app_paths;
+
+// Sous-dossier du dossier de données où sont installés les runtimes gérés
+pub const MANAGED_DIR: &str = "java";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JavaRuntime {
+    // Dossier JAVA_HOME
+    pub path: String,
+    // bin/java (bin/java.exe sous Windows)
+    pub executable: String,
+    // Version complète telle qu'annoncée ("17.0.9+9", "1.8.0_392-b08")
+    pub version: String,
+    // Version décomposée (majeure, mise à jour, build, éditeur)
+    pub details: JavaVersion,
+    // OS_ARCH du fichier release ("x86_64", "aarch64"...)
+    pub architecture: Option<String>,
+    // "JDK" ou "JRE"
+    #[serde(rename = "type")]
+    pub kind: String,
+    // Emplacement où il a été trouvé ("Nether Client", "/usr/lib/jvm", "SDKMAN", "PATH"...)
+    pub source: String,
+    // Installé par Nether Client
+    pub managed: bool,
+}
+
+fn java_binary() -> &'static str {
+    if cfg!(windows) { "java.exe" } else { "java" }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).map(PathBuf::from)
+}
+
+// Dossier des runtimes installés par Nether Client (dossier de données propre au système)
+pub fn managed_root() -> Option<PathBuf> {
+    app_paths::app_dir().ok().map(|dir| dir.join(MANAGED_DIR))
+}
+
+// JAVA_HOME d'un dossier candidat : lui-même, son bundle macOS (Contents/Home)
+// ou un sous-dossier unique issu d'une archive (jdk-17/jdk-17.0.9+9/)
+fn resolve_home(dir: &Path) -> Option<PathBuf> {
+    let is_home = |path: &Path| path.join("bin").join(java_binary()).is_file();
+
+    let bundle = dir.join("Contents").join("Home");
+    if is_home(dir) {
+        return Some(dir.to_path_buf());
+    }
+    if is_home(&bundle) {
+        return Some(bundle);
+    }
+
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .find_map(|path| {
+            let bundle = path.join("Contents").join("Home");
+            if is_home(&path) {
+                Some(path)
+            } else if is_home(&bundle) {
+                Some(bundle)
+            } else {
+                None
+            }
+        })
+}
+
+// Sous-dossiers d'un emplacement d'installation (un runtime par dossier)
+fn children(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else { return Vec::new() };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        // Dossiers cachés : téléchargements en cours, métadonnées des gestionnaires de versions
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+// Champs du fichier `release` (KEY="valeur")
+fn read_release(home: &Path) -> Vec<(String, String)> {
+    fs::read_to_string(home.join("release"))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            Some((key.trim().to_string(), value.trim().trim_matches('"').to_string()))
+        })
+        .collect()
+}
+
+fn describe(home: &Path, source: &str, managed: bool) -> Option<JavaRuntime> {
+    let executable = home.join("bin").join(java_binary());
+    let release = read_release(home);
+    let details = JavaVersion::from_release(&release).or_else(|| JavaVersion::probe(&executable))?;
+    let kind = if home.join("bin").join(if cfg!(windows) { "javac.exe" } else { "javac" }).is_file() { "JDK" } else { "JRE" };
+
+    Some(JavaRuntime {
+        path: home.to_string_lossy().to_string(),
+        executable: executable.to_string_lossy().to_string(),
+        version: details.raw.clone(),
+        details,
+        architecture: release.iter().find(|(k, _)| k == "OS_ARCH").map(|(_, v)| v.clone()).filter(|v| !v.is_empty()),
+        kind: kind.to_string(),
+        source: source.to_string(),
+        managed,
+    })
+}
+
+fn add_children(found: &mut Vec<(PathBuf, String, bool)>, dir: PathBuf, source: &str, managed: bool) {
+    found.extend(children(&dir).into_iter().map(|path| (path, source.to_string(), managed)));
+}
+
+// Emplacements connus, dans l'ordre de priorité de leur étiquette
+fn candidates() -> Vec<(PathBuf, String, bool)> {
+    let mut found: Vec<(PathBuf, String, bool)> = Vec::new();
+
+    if let Some(root) = managed_root() {
+        add_children(&mut found, root, "Nether Client", true);
+    }
+
+    if let Some(java_home) = std::env::var_os("JAVA_HOME") {
+        found.push((PathBuf::from(java_home), "JAVA_HOME".to_string(), false));
+    }
+
+    if cfg!(windows) {
+        for (variable, default) in [("PROGRAMFILES", "C:\\Program Files"), ("PROGRAMFILES(X86)", "C:\\Program Files (x86)")] {
+            let program_files = PathBuf::from(std::env::var(variable).unwrap_or_else(|_| default.to_string()));
+            for vendor in ["Java", "Eclipse Adoptium", "Microsoft", "Zulu", "Amazon Corretto", "BellSoft"] {
+                add_children(&mut found, program_files.join(vendor), &format!("{}\\{}", default.trim_start_matches("C:\\"), vendor), false);
+            }
+        }
+    } else {
+        for dir in ["/usr/lib/jvm", "/usr/java", "/usr/local/java", "/opt/java", "/opt/jdk"] {
+            add_children(&mut found, PathBuf::from(dir), dir, false);
+        }
+        // /opt contient aussi d'autres logiciels : seuls les dossiers qui résolvent vers un JAVA_HOME comptent
+        add_children(&mut found, PathBuf::from("/opt"), "/opt", false);
+
+        // Homebrew (Apple Silicon puis Intel) et JDK macOS
+        for prefix in ["/opt/homebrew", "/usr/local", "/home/linuxbrew/.linuxbrew"] {
+            for formula in children(&Path::new(prefix).join("opt")) {
+                let name = formula.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                if name.starts_with("openjdk") || name.contains("temurin") || name.starts_with("java") {
+                    // libexec/bin (Linuxbrew) ou libexec/openjdk.jdk/Contents/Home (macOS)
+                    found.push((formula.join("libexec"), "Homebrew".to_string(), false));
+                }
+            }
+        }
+        add_children(&mut found, PathBuf::from("/Library/Java/JavaVirtualMachines"), "/Library/Java/JavaVirtualMachines", false);
+
+        if let Some(home) = home_dir() {
+            add_children(&mut found, home.join("Library/Java/JavaVirtualMachines"), "~/Library/Java/JavaVirtualMachines", false);
+            let sdkman = std::env::var_os("SDKMAN_DIR").map(PathBuf::from).unwrap_or_else(|| home.join(".sdkman"));
+            add_children(&mut found, sdkman.join("candidates").join("java"), "SDKMAN", false);
+            let asdf = std::env::var_os("ASDF_DATA_DIR").map(PathBuf::from).unwrap_or_else(|| home.join(".asdf"));
+            add_children(&mut found, asdf.join("installs").join("java"), "asdf", false);
+            add_children(&mut found, home.join(".jabba").join("jdk"), "jabba", false);
+        }
+    }
+
+    // PATH en dernier : /usr/bin/java n'est souvent qu'un lien vers l'un des dossiers précédents
+    if let Some(path) = std::env::var_os("PATH") {
+        for dir in std::env::split_paths(&path) {
+            let java = dir.join(java_binary());
+            if java.is_file() {
+                // bin/java → JAVA_HOME, après résolution des liens (alternatives, shims)
+                let target = fs::canonicalize(&java).unwrap_or(java);
+                if let Some(home) = target.parent().and_then(|bin| bin.parent()) {
+                    found.push((home.to_path_buf(), "PATH".to_string(), false));
+                }
+            }
+        }
+    }
+
+    found
+}
+
+// Décrire les runtimes des dossiers candidats, sans doublon (liens symboliques, "current" de SDKMAN...)
+fn collect(candidates: Vec<(PathBuf, String, bool)>) -> Vec<JavaRuntime> {
+    let mut seen = HashSet::new();
+    let mut runtimes = Vec::new();
+
+    for (dir, source, managed) in candidates {
+        let Some(home) = resolve_home(&dir) else { continue };
+        // Même exécutable réel = même runtime, quel que soit le chemin (lien) par lequel il a été trouvé
+        let executable = home.join("bin").join(java_binary());
+        if !seen.insert(fs::canonicalize(&executable).unwrap_or(executable)) {
+            continue;
+        }
+        // Chemin réel plutôt que le lien (Windows ajouterait le préfixe \\?\ à un chemin canonique)
+        let home = if cfg!(windows) { home } else { fs::canonicalize(&home).unwrap_or(home) };
+        if let Some(runtime) = describe(&home, &source, managed) {
+            runtimes.push(runtime);
+        }
+    }
+
+    runtimes
+}
+
+// Tous les runtimes Java installés, du plus récent au plus ancien
+pub fn discover() -> Vec<JavaRuntime> {
+    let mut runtimes = collect(candidates());
+    runtimes.sort_by(|a, b| b.details.cmp(&a.details));
+    runtimes
+}
+
+// Runtime le plus adapté à une exigence : la version recommandée (dernière mise à jour), sinon la plus
+// proche dans les limites, sinon la plus récente disponible (signalée au démarrage)
+pub fn best_match<'a>(runtimes: &'a [JavaRuntime], requirement: &JavaRequirement) -> Option<&'a JavaRuntime> {
+    let preferred = requirement.recommended();
+    let newest = |a: &&JavaRuntime, b: &&JavaRuntime| a.details.cmp(&b.details);
+    runtimes
+        .iter()
+        .filter(|runtime| runtime.details.feature == preferred && requirement.accepts(runtime.details.feature))
+        .max_by(newest)
+        .or_else(|| {
+            runtimes
+                .iter()
+                .filter(|runtime| requirement.accepts(runtime.details.feature))
+                .min_by(|a, b| {
+                    a.details.feature.abs_diff(preferred).cmp(&b.details.feature.abs_diff(preferred))
+                        .then_with(|| b.details.feature.cmp(&a.details.feature))
+                        .then_with(|| b.details.cmp(&a.details))
+                })
+        })
+        .or_else(|| runtimes.iter().max_by(newest))
+}
+
+// Runtime d'un dossier choisi à la main (hors des emplacements connus)
+pub fn inspect(dir: &Path) -> Option<JavaRuntime> {
+    collect(vec![(dir.to_path_buf(), "Manuel".to_string(), false)]).pop()
+}
+
+// Runtimes installés par Nether Client uniquement
+pub fn managed() -> Vec<JavaRuntime> {
+    let mut found = Vec::new();
+    if let Some(root) = managed_root() {
+        add_children(&mut found, root, "Nether Client", true);
+    }
+    collect(found)
+}
@@ -0,0 +1,244 @@
+// Runtime Java épinglé par serveur (installation précise ou politique "dernière 17.x"), enregistré dans
+// nether-config.json et résolu à chaque démarrage : start.bat suit les mises à jour et déplacements de Java
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::java_discovery::{self, JavaRuntime};
+use crate::java_requirement::{self, JavaRequirement};
+use crate::jvm_profile;
+use crate::server_config;
+use crate::server_import;
+
+const PIN_KEY: &str = "java_pin";
+const START_SCRIPT: &str = "start.bat";
+// Script ponctuel d'un démarrage sur le runtime de remplacement (start.bat garde le runtime épinglé)
+const FALLBACK_SCRIPT: &str = "start-fallback.bat";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum JavaPin {
+    // Installation précise (dossier JAVA_HOME)
+    Install { path: String },
+    // Dernière mise à jour installée d'une version majeure
+    Latest { feature: u32 },
+    // Runtime le plus adapté à l'exigence Java du serveur
+    Auto,
+}
+
+impl JavaPin {
+    pub fn label(&self) -> String {
+        match self {
+            JavaPin::Install { path } => path.clone(),
+            JavaPin::Latest { feature } => format!("dernière Java {}.x", feature),
+            JavaPin::Auto => "automatique".to_string(),
+        }
+    }
+}
+
+// État de l'épinglage d'un serveur
+#[derive(Debug, Clone, Serialize)]
+pub struct PinStatus {
+    pub server: String,
+    pub path: String,
+    pub pin: Option<JavaPin>,
+    // Exécutable actuellement utilisé par start.bat
+    pub configured: Option<String>,
+    // Runtime désigné par l'épinglage (None s'il a disparu)
+    pub resolved: Option<JavaRuntime>,
+    // Runtime utilisé à la place d'un runtime épinglé disparu
+    pub fallback: Option<JavaRuntime>,
+    pub warning: Option<String>,
+}
+
+// Résultat d'un ré-épinglage pour un serveur
+#[derive(Debug, Clone, Serialize)]
+pub struct RepinResult {
+    pub server: String,
+    pub path: String,
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn load(server_dir: &Path) -> Option<JavaPin> {
+    let content = fs::read_to_string(server_dir.join(server_config::CONFIG_FILE)).ok()?;
+    let config: serde_json::Value = serde_json::from_str(&content).ok()?;
+    serde_json::from_value(config.get(PIN_KEY)?.clone()).ok()
+}
+
+pub fn save(server_dir: &Path, pin: &JavaPin) -> Result<(), String> {
+    let value = serde_json::to_value(pin).map_err(|e| format!("Erreur sérialisation: {}", e))?;
+    server_config::update(server_dir, |config| config[PIN_KEY] = value)
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    a == b || matches!((fs::canonicalize(a), fs::canonicalize(b)), (Ok(a), Ok(b)) if a == b)
+}
+
+// Runtime désigné par un épinglage parmi les runtimes installés
+pub fn resolve(pin: &JavaPin, runtimes: &[JavaRuntime], requirement: Option<&JavaRequirement>) -> Option<JavaRuntime> {
+    match pin {
+        JavaPin::Install { path } => {
+            let pinned = Path::new(path);
+            runtimes
+                .iter()
+                .find(|runtime| same_path(Path::new(&runtime.path), pinned) || same_path(Path::new(&runtime.executable), pinned))
+                .cloned()
+                // Dossier hors des emplacements connus
+                .or_else(|| java_discovery::inspect(pinned))
+        }
+        JavaPin::Latest { feature } => runtimes
+            .iter()
+            .filter(|runtime| runtime.details.feature == *feature)
+            .max_by(|a, b| a.details.cmp(&b.details))
+            .cloned(),
+        JavaPin::Auto => match requirement {
+            Some(requirement) => java_discovery::best_match(runtimes, requirement).cloned(),
+            None => runtimes.first().cloned(),
+        },
+    }
+}
+
+async fn status_with(server_dir: &Path, runtimes: &[JavaRuntime]) -> PinStatus {
+    let pin = load(server_dir);
+    let mut status = PinStatus {
+        server: server_import::server_name(server_dir),
+        path: server_dir.to_string_lossy().to_string(),
+        pin: pin.clone(),
+        configured: jvm_profile::launch_java(server_dir),
+        resolved: None,
+        fallback: None,
+        warning: None,
+    };
+    let Some(pin) = pin else { return status };
+
+    // L'exigence du serveur n'est utile qu'au choix automatique et au remplacement
+    let auto = pin == JavaPin::Auto;
+    status.resolved = if auto { None } else { resolve(&pin, runtimes, None) };
+    let requirement = match status.resolved {
+        None => java_requirement::for_server(server_dir).await,
+        Some(_) => None,
+    };
+    if auto {
+        status.resolved = resolve(&pin, runtimes, requirement.as_ref());
+    }
+
+    if status.resolved.is_none() {
+        status.fallback = requirement.as_ref().and_then(|requirement| java_discovery::best_match(runtimes, requirement).cloned());
+        status.warning = Some(match &status.fallback {
+            Some(fallback) => format!("Runtime Java épinglé introuvable ({}), Java {} utilisé à la place", pin.label(), fallback.version),
+            None => format!("Runtime Java épinglé introuvable ({}) et aucun Java de remplacement installé", pin.label()),
+        });
+    }
+    status
+}
+
+pub async fn status(server_dir: &Path) -> PinStatus {
+    status_with(server_dir, &java_discovery::discover()).await
+}
+
+// Épinglage de tous les serveurs (dossier Serveurs et serveurs adoptés)
+pub async fn list() -> Vec<PinStatus> {
+    let runtimes = java_discovery::discover();
+    let mut statuses = Vec::new();
+    for server_dir in server_import::server_dirs() {
+        statuses.push(status_with(&server_dir, &runtimes).await);
+    }
+    statuses
+}
+
+// Faire pointer start.bat vers un runtime, profil JVM réappliqué pour sa version
+fn switch(server_dir: &Path, runtime: &JavaRuntime) -> Result<(), String> {
+    if jvm_profile::launch_java(server_dir).as_deref() == Some(runtime.executable.as_str()) {
+        return Ok(());
+    }
+    // Profil vérifié pour ce runtime avant toute écriture (GC non supporté : start.bat reste inchangé)
+    let profile = jvm_profile::load(server_dir);
+    if let Some(profile) = &profile {
+        jvm_profile::arguments(profile, Some(runtime.details.feature))
+            .map_err(|e| format!("Java {} incompatible avec le profil JVM du serveur: {}", runtime.version, e))?;
+    }
+    jvm_profile::set_launch_java(server_dir, &runtime.executable)?;
+    if let Some(profile) = &profile {
+        jvm_profile::apply(server_dir, profile, Some(runtime.details.feature))?;
+    }
+    println!("☕ {} utilise maintenant Java {} ({})", server_import::server_name(server_dir), runtime.version, runtime.executable);
+    Ok(())
+}
+
+// Avant un démarrage : résoudre l'épinglage et renvoyer le script à lancer. Runtime épinglé trouvé :
+// start.bat est mis à jour ; sinon le runtime de remplacement ne sert qu'à ce démarrage
+pub async fn prepare(server_dir: &Path) -> Result<String, String> {
+    let _ = fs::remove_file(server_dir.join(FALLBACK_SCRIPT));
+    if load(server_dir).is_none() {
+        return Ok(START_SCRIPT.to_string());
+    }
+    let status = status(server_dir).await;
+    if let Some(runtime) = &status.resolved {
+        switch(server_dir, runtime)?;
+    } else if let Some(fallback) = &status.fallback {
+        jvm_profile::write_one_off_script(server_dir, FALLBACK_SCRIPT, &fallback.executable, Some(fallback.details.feature))?;
+        println!("☕ {} démarre avec Java {} ({}) pour cette fois", status.server, fallback.version, fallback.executable);
+        return Ok(FALLBACK_SCRIPT.to_string());
+    }
+    Ok(START_SCRIPT.to_string())
+}
+
+// Épingler un serveur : le runtime doit exister et convenir au serveur
+async fn pin_server(server_dir: &Path, pin: &JavaPin, runtimes: &[JavaRuntime]) -> Result<JavaRuntime, String> {
+    let requirement = java_requirement::for_server(server_dir).await;
+    let runtime = resolve(pin, runtimes, requirement.as_ref())
+        .ok_or_else(|| format!("Aucun runtime Java installé pour: {}", pin.label()))?;
+    if let Some(requirement) = &requirement {
+        requirement.check(runtime.details.feature)?;
+    }
+
+    switch(server_dir, &runtime)?;
+    save(server_dir, pin)?;
+    Ok(runtime)
+}
+
+pub async fn set(server_dir: &Path, pin: &JavaPin) -> Result<PinStatus, String> {
+    let runtimes = java_discovery::discover();
+    let runtime = pin_server(server_dir, pin, &runtimes).await?;
+    println!("📌 {} épinglé sur {} (Java {})", server_import::server_name(server_dir), pin.label(), runtime.version);
+    Ok(status_with(server_dir, &runtimes).await)
+}
+
+// Ré-épingler plusieurs serveurs (tous par défaut) ; ceux que le runtime ne peut pas faire tourner
+// (version Java hors limites, GC du profil non supporté) ou que `reserve` refuse (démarrés, en cours
+// de mise à jour) sont ignorés et signalés un par un
+pub async fn repin<F, R>(pin: &JavaPin, server_paths: Option<Vec<String>>, reserve: F) -> Vec<RepinResult>
+where
+    F: Fn(&str) -> Result<R, String>,
+{
+    let server_dirs: Vec<PathBuf> = match server_paths {
+        Some(paths) => paths.into_iter().map(PathBuf::from).collect(),
+        None => server_import::server_dirs(),
+    };
+    let runtimes = java_discovery::discover();
+
+    let mut results = Vec::new();
+    for server_dir in server_dirs {
+        let result = match reserve(&server_import::server_name(&server_dir)) {
+            Ok(_reservation) => pin_server(&server_dir, pin, &runtimes).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = &result {
+            println!("⚠️ {} non ré-épinglé: {}", server_import::server_name(&server_dir), e);
+        }
+        results.push(RepinResult {
+            server: server_import::server_name(&server_dir),
+            path: server_dir.to_string_lossy().to_string(),
+            success: result.is_ok(),
+            message: match result {
+                Ok(runtime) => format!("Java {} ({})", runtime.version, runtime.executable),
+                Err(e) => e,
+            },
+        });
+    }
+
+    let pinned = results.iter().filter(|result| result.success).count();
+    println!("📌 {}/{} serveurs épinglés sur {}", pinned, results.len(), pin.label());
+    results
+}
@@ -0,0 +1,267 @@
+// Version Java exigée par un serveur : javaVersion du JSON de version Mojang, à défaut version du
+// bytecode (classe principale du JAR serveur, bootstrap Forge/NeoForge), plus le maximum supporté par le loader
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::server_import;
+use crate::updates;
+use crate::version_catalog;
+
+// Dernière LTS, pour une version inconnue (snapshot sans JSON de version, hors ligne)
+const DEFAULT_JAVA: u32 = 21;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JavaRequirement {
+    pub min: u32,
+    // Version maximale supportée (anciens Forge), None si aucune limite connue
+    pub max: Option<u32>,
+    // "mojang" (JSON de version), "bytecode" (classes du JAR) ou "version" (estimation d'après le numéro)
+    pub source: String,
+    // Fichier ou version d'où vient le minimum
+    pub detail: String,
+    // Raison du maximum
+    pub max_reason: Option<String>,
+}
+
+impl JavaRequirement {
+    pub fn accepts(&self, major: u32) -> bool {
+        major >= self.min && self.max.is_none_or(|max| major <= max)
+    }
+
+    // "Java 17+", "Java 8" ou "Java 8 à 16"
+    pub fn label(&self) -> String {
+        match self.max {
+            Some(max) if max == self.min => format!("Java {}", max),
+            Some(max) => format!("Java {} à {}", self.min, max),
+            None => format!("Java {}+", self.min),
+        }
+    }
+
+    // Version à installer de préférence : la LTS couvrant le minimum, sans dépasser le maximum
+    pub fn recommended(&self) -> u32 {
+        let lts = match self.min {
+            0..=8 => 8,
+            9..=11 => 11,
+            12..=17 => 17,
+            18..=21 => 21,
+            other => other,
+        };
+        self.max.map_or(lts, |max| lts.min(max).max(self.min))
+    }
+
+    // Message d'erreur si la version majeure est hors limites
+    pub fn check(&self, major: u32) -> Result<(), String> {
+        if major < self.min {
+            return Err(format!("{} requis ({}), Java {} configuré", self.label(), self.detail, major));
+        }
+        match (self.max, &self.max_reason) {
+            (Some(max), Some(reason)) if major > max => Err(format!("{} ; Java {} configuré", reason, major)),
+            (Some(max), None) if major > max => Err(format!("{} requis, Java {} configuré", self.label(), major)),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Version Java d'un fichier .class : octets 6-7 (52 = Java 8, 61 = Java 17, 65 = Java 21)
+fn class_java(bytes: &[u8]) -> Option<u32> {
+    if bytes.len() < 8 || bytes[..4] != [0xCA, 0xFE, 0xBA, 0xBE] {
+        return None;
+    }
+    let major = u16::from_be_bytes([bytes[6], bytes[7]]) as u32;
+    major.checked_sub(44).filter(|java| *java > 0)
+}
+
+fn read_header(entry: &mut impl Read) -> Option<u32> {
+    let mut header = [0u8; 8];
+    entry.read_exact(&mut header).ok()?;
+    class_java(&header)
+}
+
+// Version Java d'un JAR : sa classe Main-Class, sinon la plus haute de ses classes
+// (hors module-info et classes multi-release de META-INF/versions, chargées seulement sur un Java récent)
+fn jar_java(jar: &Path) -> Option<u32> {
+    let main_class = server_import::read_jar_entry(jar, "META-INF/MANIFEST.MF")
+        .and_then(|manifest| server_import::manifest_attribute(&manifest, "Main-Class"));
+    let file = fs::File::open(jar).ok()?;
+    let mut archive = zip::ZipArchive::new(file).ok()?;
+
+    if let Some(main_class) = main_class {
+        let entry = format!("{}.class", main_class.replace('.', "/"));
+        if let Some(java) = archive.by_name(&entry).ok().and_then(|mut entry| read_header(&mut entry)) {
+            return Some(java);
+        }
+    }
+
+    (0..archive.len())
+        .filter_map(|i| {
+            let mut entry = archive.by_index(i).ok()?;
+            let name = entry.name();
+            if !name.ends_with(".class") || name.ends_with("module-info.class") || name.starts_with("META-INF/") {
+                return None;
+            }
+            read_header(&mut entry)
+        })
+        .max()
+}
+
+// JAR d'un dossier de libraries/ (loader Forge/NeoForge, bootstrap)
+fn library_jars(dir: &Path) -> Vec<PathBuf> {
+    let mut versions: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect())
+        .unwrap_or_default();
+    let name = |path: &PathBuf| path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    versions.sort_by(|a, b| updates::compare_versions(&name(a), &name(b)));
+    let Some(latest) = versions.pop() else { return Vec::new() };
+
+    fs::read_dir(latest)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("jar")))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Minimum lu dans le bytecode : JAR lancé avec -jar, ou loader et bootstrap Forge/NeoForge (win_args.txt)
+fn bytecode_requirement(server_dir: &Path, fp: &server_import::ServerFingerprint) -> Option<(u32, String)> {
+    let mut jars: Vec<PathBuf> = fp.server_jar.iter().map(|jar| server_dir.join(jar)).collect();
+    if fp.args_file.is_some() {
+        let libraries = server_dir.join("libraries");
+        for group in ["net/minecraftforge/forge", "net/neoforged/neoforge", "net/neoforged/forge", "cpw/mods/bootstraplauncher"] {
+            jars.extend(library_jars(&libraries.join(group)));
+        }
+    }
+
+    jars.iter()
+        .filter_map(|jar| {
+            let java = jar_java(jar)?;
+            let name = jar.strip_prefix(server_dir).unwrap_or(jar).to_string_lossy().replace('\\', "/");
+            Some((java, name))
+        })
+        .max_by_key(|(java, _)| *java)
+}
+
+// Estimation d'après le numéro de version, en dernier recours (1.20.5+ → 21, 1.18+ → 17, 1.17 → 16)
+fn from_version_number(minecraft: &str) -> u32 {
+    let mut parts = minecraft.split(['.', '-']).map(|part| part.parse::<u32>().ok());
+    let (Some(Some(major)), Some(Some(minor))) = (parts.next(), parts.next()) else { return DEFAULT_JAVA };
+    let patch = parts.next().flatten().unwrap_or(0);
+    match (major, minor, patch) {
+        (1, 0..=16, _) => 8,
+        (1, 17, _) => 16,
+        (1, 18..=19, _) | (1, 20, 0..=4) => 17,
+        (1, _, _) => 21,
+        // Numérotation annuelle (26.1...) : Java 25
+        _ => 25,
+    }
+}
+
+// Version Minecraft d'une version de loader ("1.20.1-47.2.0" pour Forge, "20.4.80" pour NeoForge)
+fn minecraft_of(version: &str, loader: &str) -> String {
+    // Une version Minecraft déjà résolue ("1.20.4") est gardée telle quelle
+    if loader == "neoforge" && !version.starts_with("1.") {
+        if let Some(minecraft) = updates::neoforge_minecraft_version(version) {
+            return minecraft;
+        }
+    }
+    version.split('-').next().unwrap_or(version).to_string()
+}
+
+// Anciens Forge (et Mohist, qui en dérive) : LaunchWrapper avant 1.13, ModLauncher sans support de Java 17 avant 1.17
+fn loader_max(loader: &str, minecraft: &str) -> Option<(u32, String)> {
+    if !matches!(loader, "forge" | "mohist") || !minecraft.starts_with("1.") {
+        return None;
+    }
+    let name = if loader == "forge" { "Forge" } else { "MohistMC" };
+    if updates::compare_versions(minecraft, "1.13") == Ordering::Less {
+        Some((8, format!("{} {} ne démarre pas au-delà de Java 8", name, minecraft)))
+    } else if updates::compare_versions(minecraft, "1.17") == Ordering::Less {
+        Some((16, format!("{} {} ne démarre pas avec Java 17 ou plus récent", name, minecraft)))
+    } else {
+        None
+    }
+}
+
+fn with_loader_max(mut requirement: JavaRequirement, loader: &str, minecraft: &str) -> JavaRequirement {
+    if let Some((max, reason)) = loader_max(loader, minecraft) {
+        requirement.max = Some(max);
+        requirement.max_reason = Some(reason);
+    }
+    requirement
+}
+
+async fn from_mojang(version: &str, minecraft: &str) -> Option<JavaRequirement> {
+    let min = match version_catalog::required_java(version).await {
+        Some(min) => min,
+        None if version != minecraft => version_catalog::required_java(minecraft).await?,
+        None => return None,
+    };
+    Some(JavaRequirement {
+        min,
+        max: None,
+        source: "mojang".to_string(),
+        detail: format!("Minecraft {}", minecraft),
+        max_reason: None,
+    })
+}
+
+// Exigence pour une version à installer (pas encore de JAR à inspecter)
+pub async fn for_version(version: &str, loader: &str) -> JavaRequirement {
+    let minecraft = minecraft_of(version, loader);
+    let requirement = match from_mojang(version, &minecraft).await {
+        Some(requirement) => requirement,
+        None => JavaRequirement {
+            min: from_version_number(&minecraft),
+            max: None,
+            source: "version".to_string(),
+            detail: format!("Minecraft {}", minecraft),
+            max_reason: None,
+        },
+    };
+    with_loader_max(requirement, loader, &minecraft)
+}
+
+// Exigence d'un serveur installé : Mojang, sinon bytecode de ses JAR, sinon numéro de version
+pub async fn for_server(server_dir: &Path) -> Option<JavaRequirement> {
+    let fp = server_import::fingerprint(server_dir);
+    let minecraft = fp.minecraft_version.clone().or_else(|| {
+        let content = fs::read_to_string(server_dir.join("nether-config.json")).ok()?;
+        let config: serde_json::Value = serde_json::from_str(&content).ok()?;
+        Some(minecraft_of(config.get("version")?.as_str()?, &fp.loader))
+    });
+
+    let requirement = match &minecraft {
+        Some(minecraft) => from_mojang(minecraft, minecraft).await,
+        None => None,
+    };
+    let requirement = requirement.or_else(|| {
+        bytecode_requirement(server_dir, &fp).map(|(min, jar)| JavaRequirement {
+            min,
+            max: None,
+            source: "bytecode".to_string(),
+            detail: jar,
+            max_reason: None,
+        })
+    });
+    let requirement = match (requirement, &minecraft) {
+        (Some(requirement), _) => requirement,
+        (None, Some(minecraft)) => JavaRequirement {
+            min: from_version_number(minecraft),
+            max: None,
+            source: "version".to_string(),
+            detail: format!("Minecraft {}", minecraft),
+            max_reason: None,
+        },
+        (None, None) => return None,
+    };
+
+    Some(match &minecraft {
+        Some(minecraft) => with_loader_max(requirement, &fp.loader, minecraft),
+        None => requirement,
+    })
+}
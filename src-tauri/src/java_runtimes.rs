@@ -0,0 +1,300 @@
+// Runtimes Java gérés par Nether Client : installation depuis l'API Adoptium (toute version,
+// système et architecture courants), empreinte SHA-256 vérifiée, archive aplatie dans java/jdk-<version>
+use serde::Serialize;
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use crate::app_paths;
+use crate::downloader::{self, Checksum, DownloadRequest};
+use crate::endpoints::{self, Upstream};
+use crate::java_discovery::{self, JavaRuntime};
+use crate::java_pin::{self, JavaPin};
+use crate::jvm_profile;
+use crate::server_import;
+
+// Dossier de travail (téléchargements, extraction en cours), ignoré par la découverte
+const STAGING_DIR: &str = ".staging";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AvailableReleases {
+    pub releases: Vec<u32>,
+    pub lts: Vec<u32>,
+    pub most_recent_lts: Option<u32>,
+}
+
+// Paquet Adoptium retenu pour ce système
+struct Package {
+    name: String,
+    link: String,
+    checksum: String,
+    release_name: String,
+}
+
+fn adoptium_os() -> Result<&'static str, String> {
+    match std::env::consts::OS {
+        "windows" => Ok("windows"),
+        "linux" => Ok("linux"),
+        "macos" => Ok("mac"),
+        other => Err(format!("Système non pris en charge par Adoptium: {}", other)),
+    }
+}
+
+fn adoptium_arch() -> Result<&'static str, String> {
+    match std::env::consts::ARCH {
+        "x86_64" => Ok("x64"),
+        "aarch64" => Ok("aarch64"),
+        "x86" => Ok("x32"),
+        "arm" => Ok("arm"),
+        other => Err(format!("Architecture non prise en charge par Adoptium: {}", other)),
+    }
+}
+
+// Même dossier que la découverte, avec l'erreur réelle si le dossier de données est introuvable
+fn managed_root() -> Result<PathBuf, String> {
+    Ok(app_paths::app_dir()?.join(java_discovery::MANAGED_DIR))
+}
+
+// Versions majeures publiées par Adoptium
+pub async fn available_releases() -> Result<AvailableReleases, String> {
+    let info = endpoints::fetch_json(&endpoints::url(Upstream::Adoptium, "/v3/info/available_releases")).await?;
+    let numbers = |key: &str| -> Vec<u32> {
+        info[key]
+            .as_array()
+            .map(|values| values.iter().filter_map(|v| v.as_u64()).map(|v| v as u32).collect())
+            .unwrap_or_default()
+    };
+
+    Ok(AvailableReleases {
+        releases: numbers("available_releases"),
+        lts: numbers("available_lts_releases"),
+        most_recent_lts: info["most_recent_lts"].as_u64().map(|v| v as u32),
+    })
+}
+
+async fn latest_package(feature: u32) -> Result<Package, String> {
+    let (os, arch) = (adoptium_os()?, adoptium_arch()?);
+    let url = endpoints::url(
+        Upstream::Adoptium,
+        &format!("/v3/assets/latest/{}/hotspot?architecture={}&image_type=jdk&os={}&vendor=eclipse", feature, arch, os),
+    );
+    let assets = endpoints::fetch_json(&url).await?;
+
+    let asset = assets
+        .as_array()
+        .and_then(|assets| assets.first())
+        .ok_or_else(|| format!("Aucun JDK {} publié par Adoptium pour {} {}", feature, os, arch))?;
+    let package = &asset["binary"]["package"];
+    let text = |value: &serde_json::Value| value.as_str().map(|s| s.to_string());
+
+    Ok(Package {
+        name: text(&package["name"]).ok_or("Réponse Adoptium invalide: nom du paquet absent")?,
+        link: text(&package["link"]).ok_or("Réponse Adoptium invalide: lien du paquet absent")?,
+        // Sans empreinte publiée, le paquet n'est pas installé
+        checksum: text(&package["checksum"]).ok_or_else(|| format!("Empreinte SHA-256 absente pour le JDK {}", feature))?,
+        release_name: text(&asset["release_name"]).unwrap_or_else(|| format!("jdk-{}", feature)),
+    })
+}
+
+// Chemin d'une entrée d'archive sans son dossier racine ("jdk-17.0.9+9/bin/java" → "bin/java") ;
+// None pour le dossier racine lui-même et les chemins qui sortiraient du dossier cible
+fn strip_top_level(path: &Path) -> Option<PathBuf> {
+    let mut components = path.components().filter(|c| !matches!(c, Component::CurDir));
+    components.next()?;
+    let rest: PathBuf = components.collect();
+    if rest.as_os_str().is_empty() || rest.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return None;
+    }
+    Some(rest)
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+        .map_err(|e| format!("Erreur permissions {}: {}", path.display(), e))
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) -> Result<(), String> {
+    Ok(())
+}
+
+fn extract_zip(archive_path: &Path, target: &Path) -> Result<(), String> {
+    let file = fs::File::open(archive_path).map_err(|e| format!("Erreur ouverture ZIP: {}", e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Erreur lecture ZIP: {}", e))?;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| format!("Erreur extraction: {}", e))?;
+        let Some(relative) = entry.enclosed_name().and_then(strip_top_level) else { continue };
+        let out = target.join(relative);
+
+        if entry.is_dir() {
+            fs::create_dir_all(&out).map_err(|e| format!("Erreur création dossier: {}", e))?;
+        } else {
+            if let Some(parent) = out.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Erreur création dossier: {}", e))?;
+            }
+            let mut outfile = fs::File::create(&out).map_err(|e| format!("Erreur création fichier: {}", e))?;
+            std::io::copy(&mut entry, &mut outfile).map_err(|e| format!("Erreur copie: {}", e))?;
+        }
+        if let Some(mode) = entry.unix_mode() {
+            set_mode(&out, mode)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn extract_tar_gz(archive_path: &Path, target: &Path) -> Result<(), String> {
+    let file = fs::File::open(archive_path).map_err(|e| format!("Erreur ouverture archive: {}", e))?;
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+    archive.set_preserve_permissions(true);
+
+    // unpack_in refuse les chemins, liens symboliques et liens physiques qui sortiraient du dossier
+    let unpacked = target.with_extension("unpack");
+    if unpacked.exists() {
+        fs::remove_dir_all(&unpacked).map_err(|e| format!("Erreur nettoyage {}: {}", unpacked.display(), e))?;
+    }
+    fs::create_dir_all(&unpacked).map_err(|e| format!("Erreur création dossier: {}", e))?;
+
+    let result = unpack_tar_entries(&mut archive, &unpacked).and_then(|_| {
+        // Dossier racine de l'archive ("jdk-17.0.9+9/") renommé en dossier cible
+        let top_level = fs::read_dir(&unpacked)
+            .map_err(|e| format!("Erreur lecture {}: {}", unpacked.display(), e))?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| path.is_dir())
+            .ok_or_else(|| "Archive sans dossier racine".to_string())?;
+        fs::rename(&top_level, target).map_err(|e| format!("Erreur extraction {}: {}", target.display(), e))
+    });
+    let _ = fs::remove_dir_all(&unpacked);
+    result
+}
+
+fn unpack_tar_entries<R: Read>(archive: &mut tar::Archive<R>, dir: &Path) -> Result<(), String> {
+    for entry in archive.entries().map_err(|e| format!("Erreur lecture archive: {}", e))? {
+        let mut entry = entry.map_err(|e| format!("Erreur lecture archive: {}", e))?;
+        entry.unpack_in(dir).map_err(|e| format!("Erreur extraction: {}", e))?;
+    }
+    Ok(())
+}
+
+// Signature gzip, pour ne pas se fier qu'à l'extension
+fn is_gzip(path: &Path) -> bool {
+    let mut magic = [0u8; 2];
+    fs::File::open(path).and_then(|mut f| f.read_exact(&mut magic)).is_ok() && magic == [0x1f, 0x8b]
+}
+
+// Installer (ou mettre à jour sur place) le dernier JDK Adoptium d'une version majeure
+pub async fn install(feature: u32) -> Result<JavaRuntime, String> {
+    let package = latest_package(feature).await?;
+    let root = managed_root()?;
+    let staging = root.join(STAGING_DIR);
+    fs::create_dir_all(&staging).map_err(|e| format!("Erreur création dossier Java: {}", e))?;
+
+    println!("☕ Installation de {} ({})", package.release_name, package.name);
+
+    let archive = staging.join(&package.name);
+    let checksum = Checksum::from_algorithm("sha256", &package.checksum)
+        .ok_or_else(|| format!("Empreinte SHA-256 invalide: {}", package.checksum))?;
+    downloader::download(
+        &DownloadRequest::new(&package.link, &archive)
+            .with_checksum(Some(checksum))
+            .with_label(&format!("Java {} ({})", feature, package.release_name)),
+    )
+    .await?;
+
+    let name = format!("jdk-{}", feature);
+    let extracted = staging.join(&name);
+    if extracted.exists() {
+        fs::remove_dir_all(&extracted).map_err(|e| format!("Erreur nettoyage {}: {}", extracted.display(), e))?;
+    }
+    let result = if package.name.ends_with(".zip") {
+        extract_zip(&archive, &extracted)
+    } else if package.name.ends_with(".tar.gz") || is_gzip(&archive) {
+        extract_tar_gz(&archive, &extracted)
+    } else {
+        Err(format!("Format d'archive non pris en charge: {}", package.name))
+    };
+    let _ = fs::remove_file(&archive);
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&extracted);
+        return Err(e);
+    }
+
+    // Même dossier d'une mise à jour à l'autre : les scripts des serveurs restent valides.
+    // L'ancien runtime est mis de côté (et remis en place en cas d'échec) avant d'être supprimé
+    let target = root.join(&name);
+    let previous = staging.join(format!("{}.old", name));
+    if previous.exists() {
+        fs::remove_dir_all(&previous).map_err(|e| format!("Erreur nettoyage {}: {}", previous.display(), e))?;
+    }
+    if target.exists() {
+        if let Err(e) = fs::rename(&target, &previous) {
+            let _ = fs::remove_dir_all(&extracted);
+            return Err(format!("Erreur remplacement de {} (runtime en cours d'utilisation ?): {}", target.display(), e));
+        }
+    }
+    if let Err(e) = fs::rename(&extracted, &target) {
+        if previous.exists() {
+            let _ = fs::rename(&previous, &target);
+        }
+        return Err(format!("Erreur installation de {}: {}", name, e));
+    }
+    if previous.exists() {
+        if let Err(e) = fs::remove_dir_all(&previous) {
+            println!("⚠️ Ancien runtime non supprimé ({}): {}", previous.display(), e);
+        }
+    }
+
+    println!("✅ {} installé dans {}", package.release_name, target.display());
+    installed_runtime(&target).ok_or_else(|| format!("Runtime installé mais illisible: {}", target.display()))
+}
+
+fn installed_runtime(dir: &Path) -> Option<JavaRuntime> {
+    java_discovery::managed().into_iter().find(|runtime| Path::new(&runtime.path).starts_with(dir))
+}
+
+pub fn list() -> Vec<JavaRuntime> {
+    java_discovery::managed()
+}
+
+// Dossier d'installation (enfant direct de java/) contenant ce runtime
+fn install_dir(runtime_path: &str) -> Result<PathBuf, String> {
+    let root = managed_root()?;
+    let path = Path::new(runtime_path);
+    // Chemin tel quel, ou chemin réel (la découverte résout les liens)
+    let relative = path.strip_prefix(&root).map(Path::to_path_buf).ok().or_else(|| {
+        let real_root = fs::canonicalize(&root).ok()?;
+        fs::canonicalize(path).ok()?.strip_prefix(&real_root).ok().map(Path::to_path_buf)
+    });
+    let name = relative
+        .and_then(|relative| relative.components().next().map(|c| c.as_os_str().to_owned()))
+        .filter(|name| name != STAGING_DIR)
+        .ok_or_else(|| format!("{} n'est pas un runtime installé par Nether Client", runtime_path))?;
+    Ok(root.join(name))
+}
+
+// Serveurs dont le script de lancement utilise ce dossier Java, ou épinglés dessus
+fn servers_using(dir: &Path) -> Vec<String> {
+    let pinned = |server: &PathBuf| matches!(java_pin::load(server), Some(JavaPin::Install { path }) if Path::new(&path).starts_with(dir));
+    server_import::server_dirs()
+        .into_iter()
+        .filter(|server| jvm_profile::launch_java(server).is_some_and(|java| Path::new(&java).starts_with(dir)) || pinned(server))
+        .filter_map(|server| Some(server.file_name()?.to_string_lossy().to_string()))
+        .collect()
+}
+
+// Supprimer un runtime géré, sauf s'il est encore utilisé par un serveur
+pub fn remove(runtime_path: &str) -> Result<(), String> {
+    let dir = install_dir(runtime_path)?;
+    let users = servers_using(&dir);
+    if !users.is_empty() {
+        return Err(format!("Runtime utilisé par: {}", users.join(", ")));
+    }
+
+    fs::remove_dir_all(&dir).map_err(|e| format!("Erreur suppression de {}: {}", dir.display(), e))?;
+    println!("🗑️ Runtime Java supprimé: {}", dir.display());
+    Ok(())
+}
@@ -0,0 +1,119 @@
+// Version d'un runtime Java : ancien schéma "1.8.0_392-b08" et schéma moderne "21.0.1+12" (JEP 223),
+// lue depuis `java -XshowSettings:properties -version` ou le fichier `release` du runtime
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JavaVersion {
+    // Version majeure (8, 17, 21...)
+    pub feature: u32,
+    pub interim: u32,
+    // Mise à jour de sécurité (392 pour 1.8.0_392, 9 pour 17.0.9)
+    pub update: u32,
+    pub patch: u32,
+    // Numéro de build (+12, -b08)
+    pub build: Option<u32>,
+    pub vendor: Option<String>,
+    // Chaîne d'origine
+    pub raw: String,
+}
+
+// Entier en tête de chaîne ("12-LTS" → 12)
+fn leading_number(text: &str) -> Option<u32> {
+    let end = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    text[..end].parse().ok()
+}
+
+impl JavaVersion {
+    pub fn parse(text: &str) -> Option<Self> {
+        let raw = text.trim().trim_matches('"').trim();
+        if raw.is_empty() {
+            return None;
+        }
+
+        // Ancien schéma : 1.<feature>.0_<update>-b<build>
+        if let Some(rest) = raw.strip_prefix("1.") {
+            let feature = leading_number(rest)?;
+            if feature <= 8 {
+                let update = rest.split_once('_').and_then(|(_, update)| leading_number(update)).unwrap_or(0);
+                let build = rest.split_once("-b").and_then(|(_, build)| leading_number(build));
+                return Some(JavaVersion { feature, interim: 0, update, patch: 0, build, vendor: None, raw: raw.to_string() });
+            }
+        }
+
+        // Schéma moderne : <feature>[.<interim>[.<update>[.<patch>]]][-pre][+build][-opt]
+        let (version, build) = match raw.split_once('+') {
+            Some((version, build)) => (version, leading_number(build)),
+            None => (raw, None),
+        };
+        let version = version.split('-').next().unwrap_or(version);
+        let mut numbers = version.split('.').map(leading_number);
+        let feature = numbers.next().flatten()?;
+        let mut next = || numbers.next().flatten().unwrap_or(0);
+        let (interim, update, patch) = (next(), next(), next());
+
+        Some(JavaVersion { feature, interim, update, patch, build, vendor: None, raw: raw.to_string() })
+    }
+
+    fn with_vendor(mut self, vendor: Option<String>) -> Self {
+        self.vendor = vendor.filter(|v| !v.is_empty());
+        self
+    }
+
+    // Lire le fichier `release` d'un JAVA_HOME (JAVA_RUNTIME_VERSION, à défaut JAVA_VERSION)
+    pub fn from_release(fields: &[(String, String)]) -> Option<Self> {
+        let field = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone()).filter(|v| !v.is_empty());
+        let version = field("JAVA_RUNTIME_VERSION")
+            .and_then(|v| Self::parse(&v))
+            .or_else(|| Self::parse(&field("JAVA_VERSION")?))?;
+        Some(version.with_vendor(field("IMPLEMENTOR")))
+    }
+
+    // Interroger l'exécutable : propriétés système, puis première ligne de `-version` en dernier recours
+    pub fn probe(java_exe: &Path) -> Option<Self> {
+        let output = Command::new(java_exe).args(["-XshowSettings:properties", "-version"]).output().ok()?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        let property = |key: &str| {
+            stderr.lines().find_map(|line| {
+                let (name, value) = line.trim().split_once(" = ")?;
+                (name.trim() == key).then(|| value.trim().to_string())
+            })
+        };
+        let from_properties = property("java.runtime.version")
+            .and_then(|v| Self::parse(&v))
+            .or_else(|| Self::parse(&property("java.version")?));
+        if let Some(version) = from_properties {
+            return Some(version.with_vendor(property("java.vendor").or_else(|| property("java.vm.vendor"))));
+        }
+
+        // `java version "1.8.0_392"`, `openjdk version "21" 2023-09-19` ou `openjdk 21 2023-09-19`
+        let line = stderr.lines().find(|line| line.contains("version") || line.starts_with("openjdk"))?;
+        let quoted = line.split('"').nth(1);
+        let token = quoted.or_else(|| line.split_whitespace().find(|word| word.starts_with(|c: char| c.is_ascii_digit())))?;
+        Self::parse(token)
+    }
+}
+
+impl PartialOrd for JavaVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JavaVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.feature, self.interim, self.update, self.patch, self.build)
+            .cmp(&(other.feature, other.interim, other.update, other.patch, other.build))
+            .then_with(|| self.raw.cmp(&other.raw))
+            .then_with(|| self.vendor.cmp(&other.vendor))
+    }
+}
+
+impl std::fmt::Display for JavaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
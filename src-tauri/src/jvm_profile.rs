@@ -0,0 +1,404 @@
+// Profils JVM par serveur (mémoire en Mo, ramasse-miettes, options, propriétés système) :
+// enregistrés dans nether-config.json et appliqués à start.bat ou à user_jvm_args.txt (Forge/NeoForge)
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::server_config;
+
+const PROFILE_KEY: &str = "jvm_profile";
+const START_SCRIPT: &str = "start.bat";
+const USER_JVM_ARGS: &str = "user_jvm_args.txt";
+
+pub const MIN_HEAP_MB: u32 = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GarbageCollector {
+    #[default]
+    G1,
+    Zgc,
+    Shenandoah,
+}
+
+impl GarbageCollector {
+    pub const ALL: [GarbageCollector; 3] = [GarbageCollector::G1, GarbageCollector::Zgc, GarbageCollector::Shenandoah];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            GarbageCollector::G1 => "g1",
+            GarbageCollector::Zgc => "zgc",
+            GarbageCollector::Shenandoah => "shenandoah",
+        }
+    }
+
+    // Première version de Java où le ramasse-miettes est utilisable en production
+    pub fn minimum_java(&self) -> u32 {
+        match self {
+            GarbageCollector::G1 => 8,
+            GarbageCollector::Zgc => 15,
+            // Absent des builds Oracle, présent chez Temurin, Zulu, Corretto...
+            GarbageCollector::Shenandoah => 17,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JvmProfile {
+    // -Xmx
+    pub heap_mb: u32,
+    // -Xms ; None = égal à heap_mb, comme le recommandent les réglages d'Aikar
+    #[serde(default)]
+    pub min_heap_mb: Option<u32>,
+    #[serde(default)]
+    pub gc: GarbageCollector,
+    // Réglages optimisés du ramasse-miettes (flags d'Aikar pour G1)
+    #[serde(default = "default_true")]
+    pub tuned_flags: bool,
+    #[serde(default)]
+    pub extra_flags: Vec<String>,
+    // -Dclé=valeur
+    #[serde(default)]
+    pub system_properties: BTreeMap<String, String>,
+}
+
+impl JvmProfile {
+    pub fn recommended(ram_mb: u32, server_type: &str) -> Self {
+        let mut system_properties = BTreeMap::new();
+        // Laisser le temps aux gros modpacks d'échanger la liste des mods à la connexion
+        if server_type == "forge" || server_type == "neoforge" {
+            system_properties.insert("fml.readTimeout".to_string(), "180".to_string());
+        }
+        JvmProfile {
+            heap_mb: ram_mb.max(MIN_HEAP_MB),
+            min_heap_mb: None,
+            gc: GarbageCollector::G1,
+            tuned_flags: true,
+            extra_flags: Vec::new(),
+            system_properties,
+        }
+    }
+}
+
+// Disponibilité d'un ramasse-miettes pour la version de Java du serveur
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectorInfo {
+    pub id: String,
+    pub minimum_java: u32,
+    pub available: bool,
+}
+
+pub fn collectors(java_major: Option<u32>) -> Vec<CollectorInfo> {
+    GarbageCollector::ALL
+        .iter()
+        .map(|gc| CollectorInfo {
+            id: gc.id().to_string(),
+            minimum_java: gc.minimum_java(),
+            available: java_major.is_none_or(|major| major >= gc.minimum_java()),
+        })
+        .collect()
+}
+
+// Profil d'un serveur tel qu'affiché dans l'éditeur
+#[derive(Debug, Clone, Serialize)]
+pub struct JvmProfileState {
+    pub profile: JvmProfile,
+    // false : profil déduit du script actuel, pas encore enregistré
+    pub stored: bool,
+    pub java_major: Option<u32>,
+    // Arguments qui seront passés à Java
+    pub arguments: Vec<String>,
+    pub collectors: Vec<CollectorInfo>,
+}
+
+// Options déjà gérées par un champ dédié du profil
+fn reserved_flag(flag: &str) -> Option<&'static str> {
+    if flag.starts_with("-Xmx") || flag.starts_with("-Xms") {
+        Some("mémoire")
+    } else if flag.starts_with("-XX:+Use") && flag.ends_with("GC") {
+        Some("ramasse-miettes")
+    } else if flag == "-jar" || flag.starts_with('@') {
+        Some("lancement")
+    } else {
+        None
+    }
+}
+
+pub fn validate(profile: &JvmProfile, java_major: Option<u32>) -> Result<(), String> {
+    let mut errors = Vec::new();
+
+    if profile.heap_mb < MIN_HEAP_MB {
+        errors.push(format!("Mémoire maximale trop faible ({} Mo, minimum {} Mo)", profile.heap_mb, MIN_HEAP_MB));
+    }
+    if let Some(min) = profile.min_heap_mb {
+        if min > profile.heap_mb {
+            errors.push(format!("Mémoire initiale ({} Mo) supérieure à la mémoire maximale ({} Mo)", min, profile.heap_mb));
+        }
+    }
+    if let Some(major) = java_major.filter(|major| *major < profile.gc.minimum_java()) {
+        errors.push(format!("{} nécessite Java {} ou plus récent (Java {} détecté)", profile.gc.id(), profile.gc.minimum_java(), major));
+    }
+
+    for flag in &profile.extra_flags {
+        let flag = flag.trim();
+        if !flag.starts_with('-') || flag.contains(['"', '\n', '\r']) {
+            errors.push(format!("Option invalide: {}", flag));
+        } else if let Some(field) = reserved_flag(flag) {
+            errors.push(format!("{}: utilisez le réglage « {} » du profil", flag, field));
+        }
+    }
+    for (key, value) in &profile.system_properties {
+        if key.is_empty() || key.contains(|c: char| c.is_whitespace() || c == '=' || c == '"') {
+            errors.push(format!("Propriété système invalide: {}", key));
+        }
+        if value.contains(['"', '\n', '\r']) {
+            errors.push(format!("Valeur invalide pour {}", key));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Profil JVM invalide: {}", errors.join("; ")))
+    }
+}
+
+// Réglages G1 d'Aikar (https://docs.papermc.io/paper/aikars-flags), adaptés au-delà de 12 Go
+fn aikar_g1_flags(heap_mb: u32) -> Vec<String> {
+    let large = heap_mb >= 12 * 1024;
+    let (new_size, max_new_size, region, reserve, occupancy) = if large { (40, 50, "16M", 15, 20) } else { (30, 40, "8M", 20, 15) };
+    vec![
+        "-XX:+ParallelRefProcEnabled".to_string(),
+        "-XX:MaxGCPauseMillis=200".to_string(),
+        "-XX:+UnlockExperimentalVMOptions".to_string(),
+        "-XX:+DisableExplicitGC".to_string(),
+        "-XX:+AlwaysPreTouch".to_string(),
+        format!("-XX:G1NewSizePercent={}", new_size),
+        format!("-XX:G1MaxNewSizePercent={}", max_new_size),
+        format!("-XX:G1HeapRegionSize={}", region),
+        format!("-XX:G1ReservePercent={}", reserve),
+        "-XX:G1HeapWastePercent=5".to_string(),
+        "-XX:G1MixedGCCountTarget=4".to_string(),
+        format!("-XX:InitiatingHeapOccupancyPercent={}", occupancy),
+        "-XX:G1MixedGCLiveThresholdPercent=90".to_string(),
+        "-XX:G1RSetUpdatingPauseTimePercent=5".to_string(),
+        "-XX:SurvivorRatio=32".to_string(),
+        "-XX:+PerfDisableSharedMem".to_string(),
+        "-XX:MaxTenuringThreshold=1".to_string(),
+    ]
+}
+
+// Arguments JVM complets d'un profil (sans le -jar / fichier d'arguments du loader)
+pub fn arguments(profile: &JvmProfile, java_major: Option<u32>) -> Result<Vec<String>, String> {
+    validate(profile, java_major)?;
+
+    let mut args = vec![
+        format!("-Xms{}M", profile.min_heap_mb.unwrap_or(profile.heap_mb)),
+        format!("-Xmx{}M", profile.heap_mb),
+    ];
+
+    match profile.gc {
+        GarbageCollector::G1 => {
+            args.push("-XX:+UseG1GC".to_string());
+            if profile.tuned_flags {
+                args.extend(aikar_g1_flags(profile.heap_mb));
+            }
+        }
+        GarbageCollector::Zgc => {
+            args.push("-XX:+UseZGC".to_string());
+            // ZGC générationnel : option explicite en Java 21 et 22, mode par défaut ensuite
+            if java_major.is_some_and(|major| (21..23).contains(&major)) {
+                args.push("-XX:+ZGenerational".to_string());
+            }
+        }
+        GarbageCollector::Shenandoah => args.push("-XX:+UseShenandoahGC".to_string()),
+    }
+    if profile.tuned_flags && profile.gc != GarbageCollector::G1 {
+        args.extend(["-XX:+AlwaysPreTouch", "-XX:+DisableExplicitGC", "-XX:+PerfDisableSharedMem"].map(String::from));
+    }
+
+    args.extend(profile.extra_flags.iter().map(|flag| flag.trim().to_string()).filter(|flag| !flag.is_empty()));
+    args.extend(profile.system_properties.iter().map(|(key, value)| format!("-D{}={}", key, value)));
+    Ok(args)
+}
+
+// Arguments pour une ligne de start.bat (guillemets si espace, % doublé pour cmd)
+pub fn script_arguments(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            let arg = arg.replace('%', "%%");
+            if arg.contains(' ') { format!("\"{}\"", arg) } else { arg }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Écrire user_jvm_args.txt (lu par les scripts Forge/NeoForge via @user_jvm_args.txt)
+pub fn write_user_jvm_args(server_dir: &Path, args: &[String]) -> Result<(), String> {
+    let mut content = String::from(
+        "# Généré par Nether Client à partir du profil JVM du serveur.\n\
+         # Les modifications manuelles sont remplacées à chaque changement de profil.\n",
+    );
+    for arg in args {
+        if arg.contains(' ') {
+            content.push_str(&format!("\"{}\"\n", arg));
+        } else {
+            content.push_str(&format!("{}\n", arg));
+        }
+    }
+    fs::write(server_dir.join(USER_JVM_ARGS), content).map_err(|e| format!("Erreur écriture {}: {}", USER_JVM_ARGS, e))
+}
+
+pub fn load(server_dir: &Path) -> Option<JvmProfile> {
+    let content = fs::read_to_string(server_dir.join(server_config::CONFIG_FILE)).ok()?;
+    let config: serde_json::Value = serde_json::from_str(&content).ok()?;
+    serde_json::from_value(config.get(PROFILE_KEY)?.clone()).ok()
+}
+
+pub fn save(server_dir: &Path, profile: &JvmProfile) -> Result<(), String> {
+    let value = serde_json::to_value(profile).map_err(|e| format!("Erreur sérialisation: {}", e))?;
+    server_config::update(server_dir, |config| config[PROFILE_KEY] = value)
+}
+
+// Ligne de start.bat qui lance Java ("chemin\java.exe" ... -jar / @fichier d'arguments)
+fn is_launch_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with('"') && trimmed[1..].contains('"') && (trimmed.contains(" -jar ") || trimmed.contains(" @"))
+}
+
+// Exécutable Java utilisé par start.bat
+pub fn launch_java(server_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(server_dir.join(START_SCRIPT)).ok()?;
+    let line = content.lines().find(|line| is_launch_line(line))?.trim_start();
+    let end = line[1..].find('"')? + 1;
+    Some(line[1..end].to_string())
+}
+
+// Réécrire la première ligne de lancement de start.bat : `rewrite` reçoit l'exécutable Java (entre
+// guillemets) et la suite de la ligne, et renvoie la nouvelle ligne sans indentation
+fn rewrite_launch_line(content: &str, rewrite: impl FnOnce(&str, &str) -> String) -> Result<String, String> {
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+
+    let mut rewrite = Some(rewrite);
+    let lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            if rewrite.is_none() || !is_launch_line(line) {
+                return line.to_string();
+            }
+            let rewrite = rewrite.take().unwrap();
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let trimmed = line.trim_start();
+            let java_end = trimmed[1..].find('"').map_or(trimmed.len(), |i| i + 2);
+            format!("{}{}", indent, rewrite(&trimmed[..java_end], &trimmed[java_end..]))
+        })
+        .collect();
+
+    if rewrite.is_some() {
+        return Err(format!("Ligne de lancement Java introuvable dans {}", START_SCRIPT));
+    }
+
+    let mut updated = lines.join(newline);
+    if content.ends_with('\n') {
+        updated.push_str(newline);
+    }
+    Ok(updated)
+}
+
+// Ce qui suit les options JVM : -jar <jar> ou @<fichier d'arguments du loader>, puis nogui
+fn launch_target(rest: &str) -> String {
+    let tokens: Vec<&str> = rest.split_whitespace().collect();
+    let start = tokens
+        .iter()
+        .position(|token| *token == "-jar" || (token.starts_with('@') && *token != "@user_jvm_args.txt"))
+        .unwrap_or(tokens.len());
+    tokens[start..].join(" ")
+}
+
+fn read_start_script(server_dir: &Path) -> Result<String, String> {
+    fs::read_to_string(server_dir.join(START_SCRIPT)).map_err(|e| format!("Erreur lecture {}: {}", START_SCRIPT, e))
+}
+
+fn write_script(server_dir: &Path, name: &str, content: &str) -> Result<(), String> {
+    fs::write(server_dir.join(name), content).map_err(|e| format!("Erreur écriture {}: {}", name, e))
+}
+
+// Remplacer l'exécutable Java de la ligne de lancement de start.bat (arguments inchangés)
+pub fn set_launch_java(server_dir: &Path, java: &str) -> Result<(), String> {
+    let updated = rewrite_launch_line(&read_start_script(server_dir)?, |_, rest| format!("\"{}\"{}", java, rest))?;
+    write_script(server_dir, START_SCRIPT, &updated)
+}
+
+// Script de lancement ponctuel `name` : start.bat avec un autre Java et les arguments du profil pour sa
+// version sur la ligne de commande. start.bat et user_jvm_args.txt ne sont pas modifiés
+pub fn write_one_off_script(server_dir: &Path, name: &str, java: &str, java_major: Option<u32>) -> Result<(), String> {
+    let args = load(server_dir).map(|profile| arguments(&profile, java_major)).transpose()?;
+    let updated = rewrite_launch_line(&read_start_script(server_dir)?, |_, rest| match &args {
+        Some(args) => format!("\"{}\" {} {}", java, script_arguments(args), launch_target(rest)),
+        None => format!("\"{}\"{}", java, rest),
+    })?;
+    write_script(server_dir, name, &updated)
+}
+
+// Fichiers lus au lancement par start.bat : JAR passé à -jar et fichiers d'arguments (@...)
+pub fn launch_files(server_dir: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(server_dir.join(START_SCRIPT)) else { return Vec::new() };
+    let Some(line) = content.lines().find(|line| is_launch_line(line)) else { return Vec::new() };
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+
+    let mut files = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some(file) = token.strip_prefix('@') {
+            files.push(file.trim_matches('"').to_string());
+        } else if *token == "-jar" {
+            if let Some(jar) = tokens.get(i + 1) {
+                files.push(jar.trim_matches('"').to_string());
+            }
+        }
+    }
+    files
+}
+
+// Mémoire maximale actuelle (-Xmx de start.bat ou de user_jvm_args.txt), en Mo
+pub fn current_heap_mb(server_dir: &Path) -> Option<u32> {
+    [START_SCRIPT, USER_JVM_ARGS]
+        .iter()
+        .filter_map(|name| fs::read_to_string(server_dir.join(name)).ok())
+        .flat_map(|content| content.split_whitespace().filter_map(|arg| arg.strip_prefix("-Xmx").map(String::from)).collect::<Vec<_>>())
+        .filter_map(|value| {
+            let (number, unit) = value.split_at(value.find(|c: char| !c.is_ascii_digit())?);
+            let number: u32 = number.parse().ok()?;
+            match unit.to_uppercase().as_str() {
+                "G" => Some(number * 1024),
+                "M" => Some(number),
+                _ => None,
+            }
+        })
+        .next_back()
+}
+
+// Régénérer la ligne de lancement de start.bat (ou user_jvm_args.txt) à partir du profil
+pub fn apply(server_dir: &Path, profile: &JvmProfile, java_major: Option<u32>) -> Result<(), String> {
+    let args = arguments(profile, java_major)?;
+
+    let mut uses_args_file = false;
+    let updated = rewrite_launch_line(&read_start_script(server_dir)?, |java, rest| {
+        let target = launch_target(rest);
+        uses_args_file = target.starts_with('@');
+        if uses_args_file {
+            format!("{} @{} {}", java, USER_JVM_ARGS, target)
+        } else {
+            format!("{} {} {}", java, script_arguments(&args), target)
+        }
+    })?;
+
+    if uses_args_file {
+        write_user_jvm_args(server_dir, &args)?;
+    }
+    write_script(server_dir, START_SCRIPT, &updated)
+}
@@ -8,7 +8,37 @@ use std::io::{BufReader, BufRead, Write};
 use std::thread;
 use serde::{Deserialize, Serialize};
 
+mod app_paths;
+mod artifact_cache;
 mod automation;
+mod bukkit_config;
+mod downloader;
+mod endpoints;
+mod eula;
+mod file_tree;
+mod instance_import;
+mod java_discovery;
+mod java_pin;
+mod java_requirement;
+mod java_runtimes;
+mod java_version;
+mod jvm_profile;
+mod mod_configs;
+mod mohist;
+mod paper_family;
+mod preflight;
+mod properties_file;
+mod server_clone;
+mod server_config;
+mod server_import;
+mod server_properties;
+mod server_update;
+mod staging;
+mod templates;
+mod toml_file;
+mod updates;
+mod version_catalog;
+mod yaml_file;
 
 // Structure pour stocker un processus serveur avec son stdin
 struct ServerProcess {
@@ -26,6 +56,31 @@ lazy_static::lazy_static! {
     static ref AUTO_RESTART_ENABLED: Arc<Mutex<HashMap<String, bool>>> = Arc::new(Mutex::new(HashMap::new()));
     static ref SERVER_CRASH_COUNT: Arc<Mutex<HashMap<String, u32>>> = Arc::new(Mutex::new(HashMap::new()));
     static ref NEXT_AVAILABLE_PORT: Arc<Mutex<u16>> = Arc::new(Mutex::new(25565));
+    // Serveurs réservés par une opération en cours (démarrage, vérification de mise à jour)
+    static ref SERVER_BUSY: Arc<Mutex<HashMap<String, String>>> = Arc::new(Mutex::new(HashMap::new()));
+}
+
+// Réservation d'un serveur arrêté, libérée quand elle sort de portée
+struct ServerReservation(String);
+
+impl Drop for ServerReservation {
+    fn drop(&mut self) {
+        SERVER_BUSY.lock().unwrap().remove(&self.0);
+    }
+}
+
+// Réserver un serveur arrêté : refusé s'il tourne ou si une autre opération le réserve déjà
+fn reserve_server(server_name: &str, operation: &str) -> Result<ServerReservation, String> {
+    let processes = SERVER_PROCESSES.lock().unwrap();
+    if processes.contains_key(server_name) {
+        return Err(format!("Le serveur {} est en cours d'exécution, arrêtez-le avant {}", server_name, operation));
+    }
+    let mut busy = SERVER_BUSY.lock().unwrap();
+    if let Some(current) = busy.get(server_name) {
+        return Err(format!("Le serveur {} est occupé ({}), réessayez plus tard", server_name, current));
+    }
+    busy.insert(server_name.to_string(), operation.to_string());
+    Ok(ServerReservation(server_name.to_string()))
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -40,6 +95,12 @@ struct ServerConfig {
     gamemode: String,
     #[serde(default)]
     build: Option<u32>,
+    // Acceptation explicite de l'EULA Minecraft, jamais supposée
+    #[serde(default)]
+    accept_eula: bool,
+    // Auteur de l'acceptation (utilisateur du système par défaut)
+    #[serde(default)]
+    eula_accepted_by: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -63,6 +124,7 @@ async fn create_vanilla_server(config: ServerConfig) -> Result<String, String> {
     use std::io::Write;
     
     println!("Création du serveur Vanilla: {}", config.name);
+    let eula_accepted_by = eula::require(config.accept_eula, config.eula_accepted_by.as_deref())?;
     
     let server_id = format!("server_{}", chrono::Utc::now().timestamp());
     
@@ -79,119 +141,49 @@ async fn create_vanilla_server(config: ServerConfig) -> Result<String, String> {
     // Télécharger le JAR Vanilla depuis Mojang
     println!("Téléchargement du serveur Minecraft {}...", config.version);
     
-    // URL de l'API Mojang pour obtenir le lien de téléchargement
-    let manifest_url = "https://launchermeta.mojang.com/mc/game/version_manifest_v2.json";
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(300))
-        .build()
-        .map_err(|e| e.to_string())?;
+    // Récupérer les détails de la version depuis le catalogue Mojang
+    let version_details = version_catalog::version_details(&config.version).await?;
     
-    // Récupérer le manifest
-    let manifest: serde_json::Value = client
-        .get(manifest_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur manifest: {}", e))?
-        .json()
-        .await
-        .map_err(|e| format!("Erreur JSON manifest: {}", e))?;
-    
-    // Trouver la version
-    let versions = manifest["versions"].as_array()
-        .ok_or("Versions non trouvées")?;
-    
-    let version_data = versions.iter()
-        .find(|v| v["id"].as_str() == Some(&config.version))
-        .ok_or(format!("Version {} non trouvée", config.version))?;
-    
-    let version_url = version_data["url"].as_str()
-        .ok_or("URL de version non trouvée")?;
-    
-    // Récupérer les détails de la version
-    let version_details: serde_json::Value = client
-        .get(version_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur détails: {}", e))?
-        .json()
-        .await
-        .map_err(|e| format!("Erreur JSON détails: {}", e))?;
-    
-    let server_url = version_details["downloads"]["server"]["url"].as_str()
+    let server_url = version_details.server_url.as_deref()
         .ok_or("URL du serveur non trouvée")?;
     
     println!("Téléchargement depuis: {}", server_url);
     
-    // Télécharger le JAR
-    let jar_bytes = client
-        .get(server_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur téléchargement: {}", e))?
-        .bytes()
-        .await
-        .map_err(|e| format!("Erreur lecture: {}", e))?;
-    
-    // Sauvegarder le JAR
+    // Télécharger le JAR (vérifié avec le SHA-1 du manifest Mojang)
     let jar_path = server_path.join("server.jar");
-    let mut jar_file = fs::File::create(&jar_path)
-        .map_err(|e| format!("Erreur création JAR: {}", e))?;
-    jar_file.write_all(&jar_bytes)
-        .map_err(|e| format!("Erreur écriture JAR: {}", e))?;
+    let download = downloader::download(
+        &downloader::DownloadRequest::new(server_url, &jar_path)
+            .with_checksum(version_details.server_checksum())
+            .with_label(&format!("Minecraft {}", config.version))
+            .cached()
+    ).await?;
     
-    println!("JAR téléchargé: {} octets", jar_bytes.len());
+    println!("JAR téléchargé: {} octets", download.size);
     
-    // Créer eula.txt
-    let eula_path = server_path.join("eula.txt");
-    let mut eula_file = fs::File::create(&eula_path)
-        .map_err(|e| format!("Erreur création EULA: {}", e))?;
-    eula_file.write_all(b"eula=true\n")
-        .map_err(|e| format!("Erreur écriture EULA: {}", e))?;
+    // EULA acceptée explicitement à la création
+    eula::accept(&server_path, &eula_accepted_by)?;
     
     // Créer server.properties
-    let properties_content = format!(
-        "server-port={}\n\
-         max-players={}\n\
-         motd={}\n\
-         difficulty={}\n\
-         gamemode={}\n\
-         online-mode=true\n\
-         pvp=true\n\
-         spawn-protection=16\n\
-         view-distance=10\n",
-        config.port,
-        config.max_players,
-        config.motd,
-        config.difficulty,
-        config.gamemode
-    );
-    
-    let properties_path = server_path.join("server.properties");
-    let mut properties_file = fs::File::create(&properties_path)
-        .map_err(|e| format!("Erreur création properties: {}", e))?;
-    properties_file.write_all(properties_content.as_bytes())
-        .map_err(|e| format!("Erreur écriture properties: {}", e))?;
+    write_server_properties(&server_path, &config, Some(&config.version))?;
     
     // Obtenir le chemin Java correct pour cette version Minecraft
-    let java_path = get_java_executable_path(&config.version).await?;
+    let java_path = get_java_executable_path(&config.version, "vanilla").await?;
     println!("Utilisation de Java: {}", java_path);
     
     // Créer le script de lancement .bat avec le bon chemin Java
-    let ram_mb = config.ram;
-    let ram_gb = ram_mb / 1024;
+    let jvm_args = init_jvm_profile(&server_path, config.ram, "vanilla", &java_path)?;
     let bat_content = format!(
         "@echo off\n\
          title Nether Client - {}\n\
          echo Demarrage du serveur {}...\n\
          echo Utilisation de Java: {}\n\
-         \"{}\" -Xmx{}G -Xms{}G -jar server.jar nogui\n\
+         \"{}\" {} -jar server.jar nogui\n\
          pause\n",
         config.name,
         config.name,
         java_path,
         java_path,
-        ram_gb,
-        ram_gb / 2
+        jvm_profile::script_arguments(&jvm_args)
     );
     
     let bat_path = server_path.join("start.bat");
@@ -214,6 +206,7 @@ async fn create_forge_server(config: ServerConfig) -> Result<String, String> {
     use std::io::Write;
     
     println!("Création du serveur Forge: {}", config.name);
+    let eula_accepted_by = eula::require(config.accept_eula, config.eula_accepted_by.as_deref())?;
     
     let server_id = format!("server_{}", chrono::Utc::now().timestamp());
     
@@ -230,35 +223,24 @@ async fn create_forge_server(config: ServerConfig) -> Result<String, String> {
     // Télécharger l'installeur Forge
     println!("Téléchargement de Forge {}...", config.version);
     
-    let forge_url = format!(
-        "https://maven.minecraftforge.net/net/minecraftforge/forge/{}/forge-{}-installer.jar",
-        config.version, config.version
+    let forge_url = endpoints::url(
+        endpoints::Upstream::ForgeMaven,
+        &format!("/net/minecraftforge/forge/{}/forge-{}-installer.jar", config.version, config.version),
     );
     
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(300))
-        .build()
-        .map_err(|e| e.to_string())?;
-    
-    let installer_bytes = client
-        .get(&forge_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur téléchargement Forge: {}", e))?
-        .bytes()
-        .await
-        .map_err(|e| format!("Erreur lecture Forge: {}", e))?;
-    
     let installer_path = server_path.join("forge-installer.jar");
-    let mut installer_file = fs::File::create(&installer_path)
-        .map_err(|e| format!("Erreur création installer: {}", e))?;
-    installer_file.write_all(&installer_bytes)
-        .map_err(|e| format!("Erreur écriture installer: {}", e))?;
+    let checksum = downloader::fetch_maven_checksum(&forge_url).await;
+    let download = downloader::download(
+        &downloader::DownloadRequest::new(&forge_url, &installer_path)
+            .with_checksum(checksum)
+            .with_label(&format!("Forge {}", config.version))
+            .cached()
+    ).await?;
     
-    println!("Installeur Forge téléchargé: {} octets", installer_bytes.len());
+    println!("Installeur Forge téléchargé: {} octets", download.size);
     
     // Obtenir le chemin Java correct pour cette version Minecraft
-    let java_path = get_java_executable_path(&config.version).await?;
+    let java_path = get_java_executable_path(&config.version, "forge").await?;
     println!("Utilisation de Java: {}", java_path);
     
     // Vérifier que le fichier Java existe
@@ -282,39 +264,27 @@ async fn create_forge_server(config: ServerConfig) -> Result<String, String> {
     println!("Forge installé avec succès!");
     
     // Créer les fichiers de configuration
-    let eula_path = server_path.join("eula.txt");
-    let mut eula_file = fs::File::create(&eula_path)
-        .map_err(|e| format!("Erreur création EULA: {}", e))?;
-    eula_file.write_all(b"eula=true\n")
-        .map_err(|e| format!("Erreur écriture EULA: {}", e))?;
+    eula::accept(&server_path, &eula_accepted_by)?;
     
-    let properties_content = create_server_properties(&config);
-    
-    let properties_path = server_path.join("server.properties");
-    let mut properties_file = fs::File::create(&properties_path)
-        .map_err(|e| format!("Erreur création properties: {}", e))?;
-    properties_file.write_all(properties_content.as_bytes())
-        .map_err(|e| format!("Erreur écriture properties: {}", e))?;
+    write_server_properties(&server_path, &config, config.version.split('-').next())?;
     
     // Trouver le JAR Forge généré
     let _forge_jar = format!("forge-{}-shim.jar", config.version);
     
     // Créer le script de lancement avec le bon chemin Java
-    let ram_mb = config.ram;
-    let ram_gb = ram_mb / 1024;
+    let jvm_args = init_jvm_profile(&server_path, config.ram, "forge", &java_path)?;
+    jvm_profile::write_user_jvm_args(&server_path, &jvm_args)?;
     let bat_content = format!(
         "@echo off\n\
          title Nether Client - {}\n\
          echo Demarrage du serveur Forge {}...\n\
          echo Utilisation de Java: {}\n\
-         \"{}\" -Xmx{}G -Xms{}G @user_jvm_args.txt @libraries/net/minecraftforge/forge/{}/win_args.txt nogui\n\
+         \"{}\" @user_jvm_args.txt @libraries/net/minecraftforge/forge/{}/win_args.txt nogui\n\
          pause\n",
         config.name,
         config.name,
         java_path,
         java_path,
-        ram_gb,
-        ram_gb / 2,
         config.version
     );
     
@@ -329,6 +299,11 @@ async fn create_forge_server(config: ServerConfig) -> Result<String, String> {
     Ok(server_id)
 }
 
+// NeoForge 1.20.1 garde les coordonnées Forge : net/neoforged/forge/1.20.1-47.1.84/forge-1.20.1-47.1.84-installer.jar
+fn neoforge_artifact(version: &str) -> &'static str {
+    if version.starts_with("1.") { "forge" } else { "neoforge" }
+}
+
 // Commande pour créer un serveur NeoForge
 #[tauri::command]
 async fn create_neoforge_server(config: ServerConfig) -> Result<String, String> {
@@ -338,6 +313,7 @@ async fn create_neoforge_server(config: ServerConfig) -> Result<String, String>
     use std::io::Write;
     
     println!("Création du serveur NeoForge: {}", config.name);
+    let eula_accepted_by = eula::require(config.accept_eula, config.eula_accepted_by.as_deref())?;
     
     let server_id = format!("server_{}", chrono::Utc::now().timestamp());
     
@@ -354,35 +330,25 @@ async fn create_neoforge_server(config: ServerConfig) -> Result<String, String>
     // Télécharger l'installeur NeoForge
     println!("Téléchargement de NeoForge {}...", config.version);
     
-    let neoforge_url = format!(
-        "https://maven.neoforged.net/releases/net/neoforged/neoforge/{}/neoforge-{}-installer.jar",
-        config.version, config.version
+    let artifact = neoforge_artifact(&config.version);
+    let neoforge_url = endpoints::url(
+        endpoints::Upstream::NeoForgeMaven,
+        &format!("/releases/net/neoforged/{a}/{v}/{a}-{v}-installer.jar", a = artifact, v = config.version),
     );
     
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(300))
-        .build()
-        .map_err(|e| e.to_string())?;
-    
-    let installer_bytes = client
-        .get(&neoforge_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur téléchargement NeoForge: {}", e))?
-        .bytes()
-        .await
-        .map_err(|e| format!("Erreur lecture NeoForge: {}", e))?;
-    
     let installer_path = server_path.join("neoforge-installer.jar");
-    let mut installer_file = fs::File::create(&installer_path)
-        .map_err(|e| format!("Erreur création installer: {}", e))?;
-    installer_file.write_all(&installer_bytes)
-        .map_err(|e| format!("Erreur écriture installer: {}", e))?;
+    let checksum = downloader::fetch_maven_checksum(&neoforge_url).await;
+    let download = downloader::download(
+        &downloader::DownloadRequest::new(&neoforge_url, &installer_path)
+            .with_checksum(checksum)
+            .with_label(&format!("NeoForge {}", config.version))
+            .cached()
+    ).await?;
     
-    println!("Installeur NeoForge téléchargé: {} octets", installer_bytes.len());
+    println!("Installeur NeoForge téléchargé: {} octets", download.size);
     
     // Obtenir le chemin Java correct pour cette version Minecraft
-    let java_path = get_java_executable_path(&config.version).await?;
+    let java_path = get_java_executable_path(&config.version, "neoforge").await?;
     println!("Utilisation de Java: {}", java_path);
     
     // Vérifier que le fichier Java existe
@@ -406,36 +372,25 @@ async fn create_neoforge_server(config: ServerConfig) -> Result<String, String>
     println!("NeoForge installé avec succès!");
     
     // Créer les fichiers de configuration
-    let eula_path = server_path.join("eula.txt");
-    let mut eula_file = fs::File::create(&eula_path)
-        .map_err(|e| format!("Erreur création EULA: {}", e))?;
-    eula_file.write_all(b"eula=true\n")
-        .map_err(|e| format!("Erreur écriture EULA: {}", e))?;
-    
-    let properties_content = create_server_properties(&config);
+    eula::accept(&server_path, &eula_accepted_by)?;
     
-    let properties_path = server_path.join("server.properties");
-    let mut properties_file = fs::File::create(&properties_path)
-        .map_err(|e| format!("Erreur création properties: {}", e))?;
-    properties_file.write_all(properties_content.as_bytes())
-        .map_err(|e| format!("Erreur écriture properties: {}", e))?;
+    write_server_properties(&server_path, &config, updates::neoforge_minecraft_version(&config.version).as_deref())?;
     
     // Créer le script de lancement avec le bon chemin Java
-    let ram_mb = config.ram;
-    let ram_gb = ram_mb / 1024;
+    let jvm_args = init_jvm_profile(&server_path, config.ram, "neoforge", &java_path)?;
+    jvm_profile::write_user_jvm_args(&server_path, &jvm_args)?;
     let bat_content = format!(
         "@echo off\n\
          title Nether Client - {}\n\
          echo Demarrage du serveur NeoForge {}...\n\
          echo Utilisation de Java: {}\n\
-         \"{}\" -Xmx{}G -Xms{}G @user_jvm_args.txt @libraries/net/neoforged/neoforge/{}/win_args.txt nogui\n\
+         \"{}\" @user_jvm_args.txt @libraries/net/neoforged/{}/{}/win_args.txt nogui\n\
          pause\n",
         config.name,
         config.name,
         java_path,
         java_path,
-        ram_gb,
-        ram_gb / 2,
+        artifact,
         config.version
     );
     
@@ -452,6 +407,12 @@ async fn create_neoforge_server(config: ServerConfig) -> Result<String, String>
 
 // Fonction pour tester la connectivité réseau
 async fn test_network_connectivity() -> Result<(), String> {
+    // En mode hors ligne, tout est résolu depuis le cache d'artefacts
+    if endpoints::is_offline() {
+        println!("Mode hors ligne : test de connectivité ignoré");
+        return Ok(());
+    }
+    
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(10))
         .build()
@@ -479,48 +440,51 @@ async fn test_network_connectivity() -> Result<(), String> {
     Err("Aucune connectivité réseau détectée. Vérifiez votre connexion Internet.".to_string())
 }
 
-// Fonction utilitaire pour créer un server.properties correct
-fn create_server_properties(config: &ServerConfig) -> String {
-    format!(
-        "#Minecraft server properties
-#Generated by Nether Client
-server-port={}
-server-ip=0.0.0.0
-max-players={}
-motd={}
-difficulty={}
-gamemode={}
-online-mode=true
-white-list=false
-pvp=true
-allow-flight=false
-enable-command-block=true
-spawn-protection=16
-level-name=world
-level-type=DEFAULT
-hardcore=false
-enable-query=false
-enable-rcon=false
-resource-pack=
-resource-pack-sha1=
-max-world-size=29999984
-view-distance=10
-spawn-npcs=true
-spawn-animals=true
-generate-structures=true
-allow-nether=true
-broadcast-console-to-ops=true
-player-idle-timeout=0
-max-build-height=256
-level-seed=
-prevent-proxy-connections=false
-",
-        config.port,
-        config.max_players,
-        config.motd,
-        config.difficulty,
-        config.gamemode
-    )
+// Créer server.properties via le schéma : propriétés lues par la version, difficulty/gamemode
+// en index avant 1.14
+fn write_server_properties(server_path: &std::path::Path, config: &ServerConfig, minecraft_version: Option<&str>) -> Result<(), String> {
+    let initial = [
+        ("server-port", serde_json::json!(config.port)),
+        ("server-ip", serde_json::json!("0.0.0.0")),
+        ("max-players", serde_json::json!(config.max_players)),
+        ("motd", serde_json::json!(config.motd)),
+        ("difficulty", serde_json::json!(config.difficulty)),
+        ("gamemode", serde_json::json!(config.gamemode)),
+        ("online-mode", serde_json::json!(true)),
+        ("white-list", serde_json::json!(false)),
+        ("pvp", serde_json::json!(true)),
+        ("allow-flight", serde_json::json!(false)),
+        ("enable-command-block", serde_json::json!(true)),
+        ("spawn-protection", serde_json::json!(16)),
+        ("level-name", serde_json::json!("world")),
+        ("level-type", serde_json::json!("DEFAULT")),
+        ("hardcore", serde_json::json!(false)),
+        ("enable-query", serde_json::json!(false)),
+        ("enable-rcon", serde_json::json!(false)),
+        ("resource-pack", serde_json::json!("")),
+        ("resource-pack-sha1", serde_json::json!("")),
+        ("max-world-size", serde_json::json!(29999984)),
+        ("view-distance", serde_json::json!(10)),
+        ("spawn-npcs", serde_json::json!(true)),
+        ("spawn-animals", serde_json::json!(true)),
+        ("generate-structures", serde_json::json!(true)),
+        ("allow-nether", serde_json::json!(true)),
+        ("broadcast-console-to-ops", serde_json::json!(true)),
+        ("player-idle-timeout", serde_json::json!(0)),
+        ("max-build-height", serde_json::json!(256)),
+        ("level-seed", serde_json::json!("")),
+        ("prevent-proxy-connections", serde_json::json!(false)),
+    ];
+    
+    let mut document = properties_file::PropertiesFile::parse("#Minecraft server properties\n#Generated by Nether Client\n");
+    for (key, value) in initial {
+        let definition = server_properties::find(key)
+            .ok_or_else(|| format!("Propriété inconnue: {}", key))?;
+        if minecraft_version.map(|version| definition.available_in(version)).unwrap_or(true) {
+            document.set(key, &definition.normalize(&value, minecraft_version)?);
+        }
+    }
+    document.save(&server_path.join("server.properties"))
 }
 
 // Commande pour créer un serveur MohistMC avec fichier local optionnel
@@ -532,6 +496,7 @@ async fn create_mohist_server(config: ServerConfig, local_jar_path: Option<Strin
     use std::io::Write;
     
     println!("Création du serveur MohistMC: {}", config.name);
+    let eula_accepted_by = eula::require(config.accept_eula, config.eula_accepted_by.as_deref())?;
     
     // Tester la connectivité réseau avant de commencer
     test_network_connectivity().await.map_err(|e| {
@@ -550,10 +515,16 @@ async fn create_mohist_server(config: ServerConfig, local_jar_path: Option<Strin
     fs::create_dir_all(&server_path).map_err(|e| e.to_string())?;
     println!("Dossier créé: {}", server_path.display());
     
-    // Utiliser le fichier local s'il est fourni, sinon copier depuis les assets intégrés
-    let server_bytes = if let Some(local_path) = local_jar_path {
+    // Utiliser le fichier local s'il est fourni, sinon copier depuis les assets intégrés.
+    // Le nom du JAR garde le build ou le commit ("mohist-1.12.2-5af9344.jar") pour les vérifications de mise à jour
+    let (server_bytes, jar_name) = if let Some(local_path) = local_jar_path {
         println!("Utilisation du fichier local: {}", local_path);
-        fs::read(&local_path).map_err(|e| format!("Erreur lecture fichier local {}: {}", local_path, e))?
+        let bytes = fs::read(&local_path).map_err(|e| format!("Erreur lecture fichier local {}: {}", local_path, e))?;
+        let local_name = std::path::Path::new(&local_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .filter(|n| n.to_lowercase().starts_with(&format!("mohist-{}-", config.version)) && n.to_lowercase().ends_with(".jar"));
+        (bytes, local_name.unwrap_or_else(|| format!("mohist-{}-server.jar", config.version)))
     } else {
         // Copier depuis les assets intégrés de l'application
         println!("Copie de MohistMC {} depuis les assets intégrés...", config.version);
@@ -604,10 +575,11 @@ async fn create_mohist_server(config: ServerConfig, local_jar_path: Option<Strin
         println!("Chemin des assets trouvé: {}", assets_path.display());
         
         // Lire le fichier depuis les assets
-        fs::read(&assets_path).map_err(|e| format!("Erreur lecture fichier asset {}: {}", assets_path.display(), e))?
+        let bytes = fs::read(&assets_path).map_err(|e| format!("Erreur lecture fichier asset {}: {}", assets_path.display(), e))?;
+        (bytes, jar_filename.to_string())
     };
     
-    let server_jar_path = server_path.join(format!("mohist-{}-server.jar", config.version));
+    let server_jar_path = server_path.join(&jar_name);
     let mut server_file = fs::File::create(&server_jar_path)
         .map_err(|e| format!("Erreur création serveur: {}", e))?;
     server_file.write_all(&server_bytes)
@@ -616,27 +588,16 @@ async fn create_mohist_server(config: ServerConfig, local_jar_path: Option<Strin
     println!("Serveur MohistMC téléchargé: {} octets", server_bytes.len());
     
     // Obtenir le chemin Java correct pour cette version Minecraft
-    let java_path = get_java_executable_path(&config.version).await?;
+    let java_path = get_java_executable_path(&config.version, "mohist").await?;
     println!("Utilisation de Java: {}", java_path);
     
     // Créer les fichiers de configuration
-    let eula_path = server_path.join("eula.txt");
-    let mut eula_file = fs::File::create(&eula_path)
-        .map_err(|e| format!("Erreur création EULA: {}", e))?;
-    eula_file.write_all(b"eula=true\n")
-        .map_err(|e| format!("Erreur écriture EULA: {}", e))?;
+    eula::accept(&server_path, &eula_accepted_by)?;
     
-    let properties_content = create_server_properties(&config);
-    
-    let properties_path = server_path.join("server.properties");
-    let mut properties_file = fs::File::create(&properties_path)
-        .map_err(|e| format!("Erreur création properties: {}", e))?;
-    properties_file.write_all(properties_content.as_bytes())
-        .map_err(|e| format!("Erreur écriture properties: {}", e))?;
+    write_server_properties(&server_path, &config, Some(&config.version))?;
     
     // Créer le script de lancement avec le bon chemin Java
-    let ram_mb = config.ram;
-    let ram_gb = ram_mb / 1024;
+    let jvm_args = init_jvm_profile(&server_path, config.ram, "mohist", &java_path)?;
     let bat_content = format!(
         "@echo off\n\
          title Nether Client - {}\n\
@@ -644,7 +605,7 @@ async fn create_mohist_server(config: ServerConfig, local_jar_path: Option<Strin
          echo Utilisation de Java: {}\n\
          echo.\n\
          echo [INFO] Lancement du serveur...\n\
-         \"{}\" -Xmx{}G -Xms{}G -jar mohist-{}-server.jar nogui\n\
+         \"{}\" {} -jar {} nogui\n\
          if %ERRORLEVEL% neq 0 (\n\
              echo [ERROR] Erreur lors du demarrage du serveur (Code: %ERRORLEVEL%)\n\
          )\n\
@@ -655,9 +616,8 @@ async fn create_mohist_server(config: ServerConfig, local_jar_path: Option<Strin
         config.name,
         java_path,
         java_path,
-        ram_gb,
-        ram_gb / 2,
-        config.version
+        jvm_profile::script_arguments(&jvm_args),
+        jar_name
     );
     
     let bat_path = server_path.join("start.bat");
@@ -674,103 +634,90 @@ async fn create_mohist_server(config: ServerConfig, local_jar_path: Option<Strin
 // Commande pour récupérer les versions Paper depuis l'API
 #[tauri::command]
 async fn get_paper_versions() -> Result<Vec<serde_json::Value>, String> {
-    println!("Récupération des versions Paper depuis l'API...");
-    
-    let api_url = "https://api.papermc.io/v2/projects/paper";
-    
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Erreur création client HTTP: {}", e))?;
-    
-    let response = client
-        .get(api_url)
-        .header("Accept", "application/json")
-        .send()
-        .await
-        .map_err(|e| format!("Erreur requête API Paper: {}", e))?;
-    
-    if !response.status().is_success() {
-        let status = response.status();
-        return Err(format!("Erreur HTTP {}: {}", status, status.as_str()));
-    }
-    
-    let data: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Erreur parsing JSON: {}", e))?;
+    fetch_paper_family_versions(paper_family::PaperProject::Paper).await
+}
+
+// Commande pour récupérer les versions Folia depuis l'API PaperMC
+#[tauri::command]
+async fn get_folia_versions() -> Result<Vec<serde_json::Value>, String> {
+    fetch_paper_family_versions(paper_family::PaperProject::Folia).await
+}
+
+// Commande pour récupérer les versions Purpur depuis l'API Purpur
+#[tauri::command]
+async fn get_purpur_versions() -> Result<Vec<serde_json::Value>, String> {
+    fetch_paper_family_versions(paper_family::PaperProject::Purpur).await
+}
+
+// Récupérer les versions et builds d'un projet de la famille Paper
+async fn fetch_paper_family_versions(project: paper_family::PaperProject) -> Result<Vec<serde_json::Value>, String> {
+    println!("Récupération des versions {} depuis l'API...", project.display_name());
     
-    let version_list = data["versions"]
-        .as_array()
-        .ok_or("Format de réponse invalide: 'versions' n'est pas un tableau")?;
+    let version_list = paper_family::list_versions(project).await?;
     
     let mut versions_with_builds = Vec::new();
     
     // Limiter à 50 versions pour éviter trop de requêtes
-    for (index, version) in version_list.iter().take(50).enumerate() {
-        if let Some(version_str) = version.as_str() {
-            println!("Récupération des builds pour {} ({}/{})...", version_str, index + 1, version_list.len().min(50));
-            
-            let builds_url = format!("https://api.papermc.io/v2/projects/paper/versions/{}/builds", version_str);
-            
-            match client
-                .get(&builds_url)
-                .header("Accept", "application/json")
-                .send()
-                .await
-            {
-                Ok(builds_response) => {
-                    if builds_response.status().is_success() {
-                        if let Ok(builds_data) = builds_response.json::<serde_json::Value>().await {
-                            let builds = builds_data["builds"]
-                                .as_array()
-                                .map(|arr| {
-                                    arr.iter()
-                                        .filter_map(|b| b["build"].as_u64().map(|n| n as u32))
-                                        .collect::<Vec<u32>>()
-                                })
-                                .unwrap_or_default();
-                            
-                            let latest_build = builds.iter().max().copied().unwrap_or(1);
-                            
-                            versions_with_builds.push(serde_json::json!({
-                                "version": version_str,
-                                "builds": builds,
-                                "latestBuild": latest_build
-                            }));
-                        }
-                    }
-                }
-                Err(e) => {
-                    println!("⚠️ Erreur récupération builds pour {}: {}", version_str, e);
-                    // Ajouter quand même la version avec un build par défaut
-                    versions_with_builds.push(serde_json::json!({
-                        "version": version_str,
-                        "builds": [1],
-                        "latestBuild": 1
-                    }));
-                }
+    for (index, version_str) in version_list.iter().take(50).enumerate() {
+        println!("Récupération des builds pour {} ({}/{})...", version_str, index + 1, version_list.len().min(50));
+        
+        match paper_family::list_builds(project, version_str).await {
+            Ok(builds) => {
+                let latest_build = builds.iter().max().copied().unwrap_or(1);
+                
+                versions_with_builds.push(serde_json::json!({
+                    "version": version_str,
+                    "builds": builds,
+                    "latestBuild": latest_build
+                }));
+            }
+            Err(e) => {
+                println!("⚠️ Erreur récupération builds pour {}: {}", version_str, e);
+                // Ajouter quand même la version avec un build par défaut
+                versions_with_builds.push(serde_json::json!({
+                    "version": version_str,
+                    "builds": [1],
+                    "latestBuild": 1
+                }));
             }
         }
     }
     
     if versions_with_builds.is_empty() {
-        return Err("Aucune version Paper trouvée".to_string());
+        return Err(format!("Aucune version {} trouvée", project.display_name()));
     }
     
-    println!("✅ {} versions Paper récupérées", versions_with_builds.len());
+    println!("✅ {} versions {} récupérées", versions_with_builds.len(), project.display_name());
     Ok(versions_with_builds)
 }
 
 // Commande pour créer un serveur Paper
 #[tauri::command]
 async fn create_paper_server(config: ServerConfig) -> Result<String, String> {
+    create_paper_family_server(config, paper_family::PaperProject::Paper).await
+}
+
+// Commande pour créer un serveur Folia
+#[tauri::command]
+async fn create_folia_server(config: ServerConfig) -> Result<String, String> {
+    create_paper_family_server(config, paper_family::PaperProject::Folia).await
+}
+
+// Commande pour créer un serveur Purpur
+#[tauri::command]
+async fn create_purpur_server(config: ServerConfig) -> Result<String, String> {
+    create_paper_family_server(config, paper_family::PaperProject::Purpur).await
+}
+
+// Créer un serveur de la famille Paper (Paper, Folia, Purpur)
+async fn create_paper_family_server(config: ServerConfig, project: paper_family::PaperProject) -> Result<String, String> {
     use std::env;
     use std::path::PathBuf;
     use std::fs;
     use std::io::Write;
     
-    println!("Création du serveur Paper: {}", config.name);
+    println!("Création du serveur {}: {}", project.display_name(), config.name);
+    let eula_accepted_by = eula::require(config.accept_eula, config.eula_accepted_by.as_deref())?;
     
     // Tester la connectivité réseau avant de commencer
     test_network_connectivity().await.map_err(|e| {
@@ -789,54 +736,33 @@ async fn create_paper_server(config: ServerConfig) -> Result<String, String> {
     fs::create_dir_all(&server_path).map_err(|e| e.to_string())?;
     println!("Dossier créé: {}", server_path.display());
     
-    // Télécharger le JAR Paper depuis l'API PaperMC
-    let build = config.build.unwrap_or(1);
-    let paper_url = format!(
-        "https://api.papermc.io/v2/projects/paper/versions/{}/builds/{}/downloads/paper-{}-{}.jar",
-        config.version, build, config.version, build
-    );
+    // Télécharger le JAR depuis l'API du projet (dernier build si aucun n'est précisé)
+    let build = match config.build {
+        Some(build) => build,
+        None => paper_family::latest_build(project, &config.version).await?,
+    };
+    let (paper_url, checksum) = paper_family::build_download(project, &config.version, build).await
+        .map_err(|e| format!("Erreur téléchargement {}: {}. Vérifiez que la version et le build existent.", project.display_name(), e))?;
     
-    println!("Téléchargement de Paper {} build {}...", config.version, build);
+    println!("Téléchargement de {} {} build {}...", project.display_name(), config.version, build);
     println!("URL: {}", paper_url);
     
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(300))
-        .build()
-        .map_err(|e| e.to_string())?;
+    // Télécharger le JAR (vérifié avec le SHA-256 publié par PaperMC)
+    let jar_path = server_path.join(project.jar_name());
+    let download = downloader::download(
+        &downloader::DownloadRequest::new(&paper_url, &jar_path)
+            .with_checksum(checksum)
+            .with_label(&format!("{} {} build {}", project.display_name(), config.version, build))
+            .cached()
+    ).await?;
     
-    let jar_bytes = client
-        .get(&paper_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur téléchargement Paper: {}. Vérifiez que la version et le build existent.", e))?
-        .bytes()
-        .await
-        .map_err(|e| format!("Erreur lecture Paper: {}", e))?;
-    
-    // Sauvegarder le JAR
-    let jar_path = server_path.join("paper.jar");
-    let mut jar_file = fs::File::create(&jar_path)
-        .map_err(|e| format!("Erreur création JAR: {}", e))?;
-    jar_file.write_all(&jar_bytes)
-        .map_err(|e| format!("Erreur écriture JAR: {}", e))?;
-    
-    println!("JAR Paper téléchargé: {} octets", jar_bytes.len());
-    
-    // Créer eula.txt
-    let eula_path = server_path.join("eula.txt");
-    let mut eula_file = fs::File::create(&eula_path)
-        .map_err(|e| format!("Erreur création EULA: {}", e))?;
-    eula_file.write_all(b"eula=true\n")
-        .map_err(|e| format!("Erreur écriture EULA: {}", e))?;
+    println!("JAR {} téléchargé: {} octets", project.display_name(), download.size);
     
-    // Créer server.properties
-    let properties_content = create_server_properties(&config);
+    // EULA acceptée explicitement à la création
+    eula::accept(&server_path, &eula_accepted_by)?;
     
-    let properties_path = server_path.join("server.properties");
-    let mut properties_file = fs::File::create(&properties_path)
-        .map_err(|e| format!("Erreur création properties: {}", e))?;
-    properties_file.write_all(properties_content.as_bytes())
-        .map_err(|e| format!("Erreur écriture properties: {}", e))?;
+    // Créer server.properties
+    write_server_properties(&server_path, &config, Some(&config.version))?;
     
     // Créer bukkit.yml (configuration Bukkit)
     let bukkit_content = "# This is the main configuration file for Bukkit.
@@ -1058,20 +984,19 @@ stats:
         .map_err(|e| format!("Erreur création dossier plugins: {}", e))?;
     
     // Obtenir le chemin Java correct pour cette version Minecraft
-    let java_path = get_java_executable_path(&config.version).await?;
+    let java_path = get_java_executable_path(&config.version, project.id()).await?;
     println!("Utilisation de Java: {}", java_path);
     
     // Créer le script de lancement avec le bon chemin Java
-    let ram_mb = config.ram;
-    let ram_gb = ram_mb / 1024;
+    let jvm_args = init_jvm_profile(&server_path, config.ram, project.id(), &java_path)?;
     let bat_content = format!(
         "@echo off\n\
          title Nether Client - {}\n\
-         echo Demarrage du serveur Paper {}...\n\
+         echo Demarrage du serveur {} {}...\n\
          echo Utilisation de Java: {}\n\
          echo.\n\
          echo [INFO] Lancement du serveur...\n\
-         \"{}\" -Xmx{}G -Xms{}G -jar paper.jar nogui\n\
+         \"{}\" {} -jar {} nogui\n\
          if %ERRORLEVEL% neq 0 (\n\
              echo [ERROR] Erreur lors du demarrage du serveur (Code: %ERRORLEVEL%)\n\
          )\n\
@@ -1079,11 +1004,12 @@ stats:
          echo [INFO] Serveur arrete. Appuyez sur une touche pour fermer...\n\
          pause >nul\n",
         config.name,
+        project.display_name(),
         config.name,
         java_path,
         java_path,
-        ram_gb,
-        ram_gb / 2
+        jvm_profile::script_arguments(&jvm_args),
+        project.jar_name()
     );
     
     let bat_path = server_path.join("start.bat");
@@ -1092,7 +1018,7 @@ stats:
     bat_file.write_all(bat_content.as_bytes())
         .map_err(|e| format!("Erreur écriture BAT: {}", e))?;
     
-    println!("Serveur Paper créé avec succès!");
+    println!("Serveur {} créé avec succès!", project.display_name());
     
     Ok(server_id)
 }
@@ -1106,10 +1032,22 @@ async fn start_server(server_name: String, server_path: String) -> Result<(), St
     
     let path = PathBuf::from(&server_path);
     
-    // Vérifier si le script start.bat existe
-    let bat_path = path.join("start.bat");
-    if !bat_path.exists() {
-        return Err(format!("Script de démarrage non trouvé: {}", bat_path.display()));
+    // Pas de second processus pendant une vérification de mise à jour ou un autre démarrage
+    let _reservation = reserve_server(&server_name, "de le redémarrer")?;
+    
+    // Runtime Java épinglé résolu maintenant : start.bat suit les mises à jour et déplacements de Java
+    let launch_script = java_pin::prepare(&path).await.unwrap_or_else(|e| {
+        println!("⚠️ {}: {}", server_name, e);
+        "start.bat".to_string()
+    });
+    
+    // Vérifications avant démarrage (EULA, port, Java, mémoire, disque, fichiers de lancement)
+    let report = preflight::run(&path, server_java_major(&path)).await;
+    for issue in report.issues.iter().filter(|issue| issue.severity == "warning") {
+        println!("⚠️ {}: {} ({})", server_name, issue.message, issue.fix);
+    }
+    if !report.ready {
+        return Err(format!("Le serveur {} ne peut pas démarrer:\n{}", server_name, report.error_summary()));
     }
     
     // Initialiser les logs pour ce serveur
@@ -1120,7 +1058,7 @@ async fn start_server(server_name: String, server_path: String) -> Result<(), St
     
     // Démarrer le serveur avec le script .bat (fenêtre CMD stable)
     let mut child = Command::new("cmd")
-        .args(["/K", "start.bat"])
+        .args(["/K", &launch_script])
         .current_dir(&path)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
@@ -1186,6 +1124,28 @@ async fn start_server(server_name: String, server_path: String) -> Result<(), St
     Ok(())
 }
 
+// Vérifications avant démarrage, avec une correction proposée pour chaque problème
+#[tauri::command]
+async fn preflight_server(server_path: String) -> Result<preflight::PreflightReport, String> {
+    let path = std::path::Path::new(&server_path);
+    Ok(preflight::run(path, server_java_major(path)).await)
+}
+
+// État de l'EULA d'un serveur (acceptation et qui l'a enregistrée)
+#[tauri::command]
+async fn get_eula_status(server_path: String) -> Result<eula::EulaStatus, String> {
+    Ok(eula::status(std::path::Path::new(&server_path)))
+}
+
+// Accepter l'EULA d'un serveur existant (serveur importé), sur action explicite de l'utilisateur
+#[tauri::command]
+async fn accept_server_eula(server_path: String, accepted_by: Option<String>) -> Result<eula::EulaStatus, String> {
+    let path = std::path::Path::new(&server_path);
+    let accepted_by = eula::require(true, accepted_by.as_deref())?;
+    eula::accept(path, &accepted_by)?;
+    Ok(eula::status(path))
+}
+
 // Commande pour arrêter un serveur
 #[tauri::command]
 async fn stop_server(server_name: String) -> Result<(), String> {
@@ -1236,11 +1196,9 @@ async fn stop_server(server_name: String) -> Result<(), String> {
 
 // Commande pour mettre à jour les propriétés d'un serveur
 #[tauri::command]
-async fn update_server_properties(server_name: String, properties: serde_json::Value) -> Result<(), String> {
+async fn update_server_properties(server_name: String, properties: serde_json::Value) -> Result<Vec<String>, String> {
     use std::env;
     use std::path::PathBuf;
-    use std::fs;
-    use std::io::Write;
     
     println!("Mise à jour des propriétés du serveur: {}", server_name);
     
@@ -1257,59 +1215,109 @@ async fn update_server_properties(server_name: String, properties: serde_json::V
         return Err(format!("Fichier server.properties non trouvé: {}", properties_file.display()));
     }
     
-    // Lire le fichier existant
-    let mut existing_properties = std::collections::HashMap::new();
-    if let Ok(content) = fs::read_to_string(&properties_file) {
-        for line in content.lines() {
-            if let Some((key, value)) = line.split_once('=') {
-                existing_properties.insert(key.to_string(), value.to_string());
-            }
-        }
+    // Valider les nouvelles valeurs avec le schéma (toutes les erreurs sont renvoyées ensemble)
+    let changes = properties.as_object()
+        .ok_or("Les propriétés doivent être un objet clé/valeur")?;
+    // Difficulté et mode de jeu écrits sous forme d'index avant 1.14
+    let version = server_minecraft_version(&server_path);
+    let validated = server_properties::validate_changes(changes, version.as_deref())?;
+    for warning in &validated.warnings {
+        println!("⚠️ {}", warning);
     }
     
-    // Mettre à jour avec les nouvelles propriétés
-    if let Some(props) = properties.as_object() {
-        for (key, value) in props {
-            if let Some(val) = value.as_str() {
-                existing_properties.insert(key.clone(), val.to_string());
-            } else if let Some(val) = value.as_bool() {
-                existing_properties.insert(key.clone(), val.to_string());
-            } else if let Some(val) = value.as_i64() {
-                existing_properties.insert(key.clone(), val.to_string());
-            }
+    // Modifier uniquement les clés qui changent (commentaires, ordre et clés inconnues conservés)
+    let mut document = properties_file::PropertiesFile::load(&properties_file)?;
+    let mut modified = 0;
+    for (key, value) in &validated.changes {
+        if document.set(key, value) {
+            modified += 1;
         }
     }
     
-    // Écrire le fichier mis à jour
-    let mut file = fs::File::create(&properties_file)
-        .map_err(|e| format!("Erreur création fichier: {}", e))?;
-    
-    // Propriétés dans l'ordre standard Minecraft
-    let property_order = [
-        "server-port", "max-players", "motd", "difficulty", "gamemode",
-        "online-mode", "pvp", "spawn-protection", "view-distance", "simulation-distance",
-        "white-list", "allow-flight", "enable-command-block", "spawn-monsters",
-        "allow-nether", "force-gamemode", "resource-pack", "resource-pack-sha1"
-    ];
-    
-    // Écrire les propriétés dans l'ordre
-    for prop in &property_order {
-        if let Some(value) = existing_properties.get(*prop) {
-            writeln!(file, "{}={}", prop, value)
-                .map_err(|e| format!("Erreur écriture: {}", e))?;
-        }
+    if modified > 0 {
+        document.save(&properties_file)?;
     }
     
-    // Écrire les propriétés restantes
-    for (key, value) in &existing_properties {
-        if !property_order.contains(&key.as_str()) {
-            writeln!(file, "{}={}", key, value)
-                .map_err(|e| format!("Erreur écriture: {}", e))?;
-        }
-    }
+    println!("Propriétés mises à jour ({} modifiée(s)): {}", modified, properties_file.display());
+    Ok(validated.warnings)
+}
+
+// Version Minecraft d'un serveur (JAR/libraries, sinon détection par fichiers)
+fn server_minecraft_version(server_path: &std::path::Path) -> Option<String> {
+    server_import::fingerprint(server_path)
+        .minecraft_version
+        .or_else(|| Some(detect_version_internal(&server_path.to_path_buf())))
+        .filter(|version| !version.is_empty())
+}
+
+// Schéma des propriétés server.properties pour l'éditeur (disponibilité selon la version)
+#[tauri::command]
+async fn get_server_properties_schema(minecraft_version: Option<String>) -> Result<Vec<server_properties::PropertySchema>, String> {
+    Ok(server_properties::schema(minecraft_version.as_deref()))
+}
+
+// Signaler les propriétés inconnues, obsolètes ou invalides d'un serveur
+#[tauri::command]
+async fn audit_server_properties(server_path: String) -> Result<Vec<server_properties::PropertyIssue>, String> {
+    let path = std::path::PathBuf::from(&server_path);
+    let properties = properties_file::PropertiesFile::load(&path.join("server.properties"))?.entries();
     
-    println!("Propriétés mises à jour: {}", properties_file.display());
-    Ok(())
+    Ok(server_properties::audit(&properties, server_minecraft_version(&path).as_deref()))
+}
+
+// Réglages bukkit.yml / spigot.yml / config Paper d'un serveur, avec leur schéma
+#[tauri::command]
+async fn get_server_config_settings(server_path: String) -> Result<Vec<bukkit_config::SettingState>, String> {
+    Ok(bukkit_config::read(std::path::Path::new(&server_path)))
+}
+
+// Modifier des réglages YAML (les commentaires des fichiers sont conservés)
+#[tauri::command]
+async fn update_server_config_settings(server_path: String, changes: Vec<bukkit_config::SettingChange>) -> Result<usize, String> {
+    let modified = bukkit_config::update(std::path::Path::new(&server_path), &changes)?;
+    println!("Réglages YAML mis à jour ({} modifié(s)): {}", modified, server_path);
+    Ok(modified)
+}
+
+#[tauri::command]
+async fn list_config_presets() -> Result<Vec<bukkit_config::PresetInfo>, String> {
+    Ok(bukkit_config::presets())
+}
+
+// Comparer un préréglage d'optimisation aux valeurs actuelles avant de l'appliquer
+#[tauri::command]
+async fn preview_config_preset(server_path: String, preset_id: String) -> Result<Vec<bukkit_config::SettingDiff>, String> {
+    bukkit_config::preview(std::path::Path::new(&server_path), &preset_id)
+}
+
+#[tauri::command]
+async fn apply_config_preset(server_path: String, preset_id: String) -> Result<Vec<bukkit_config::SettingDiff>, String> {
+    let applied = bukkit_config::apply_preset(std::path::Path::new(&server_path), &preset_id)?;
+    println!("Préréglage {} appliqué ({} réglage(s)): {}", preset_id, applied.len(), server_path);
+    Ok(applied)
+}
+
+// Fichiers TOML des mods (config/, serverconfig/ des mondes, defaultconfigs/)
+#[tauri::command]
+async fn list_mod_configs(server_path: String) -> Result<Vec<mod_configs::ModConfigFile>, String> {
+    Ok(mod_configs::list(std::path::Path::new(&server_path)))
+}
+
+#[tauri::command]
+async fn read_mod_config(server_path: String, file: String) -> Result<mod_configs::ConfigSection, String> {
+    mod_configs::read(std::path::Path::new(&server_path), &file)
+}
+
+// Modifier un fichier TOML en conservant sa mise en forme ; propagate reporte un changement
+// de defaultconfigs/ dans les mondes existants
+#[tauri::command]
+async fn update_mod_config(
+    server_path: String,
+    file: String,
+    edits: Vec<mod_configs::ConfigEdit>,
+    propagate: Option<bool>,
+) -> Result<mod_configs::ConfigUpdateResult, String> {
+    mod_configs::update(std::path::Path::new(&server_path), &file, &edits, propagate.unwrap_or(false))
 }
 
 // Commande pour obtenir le statut d'un serveur
@@ -1345,6 +1353,7 @@ async fn delete_server_folder(server_name: String, server_path: String) -> Resul
         match fs::remove_dir_all(&path) {
             Ok(_) => {
                 println!("✅ Dossier supprimé avec succès: {}", path.display());
+                let _ = server_import::forget_adopted(&server_path);
                 return Ok(());
             }
             Err(e) => {
@@ -1401,29 +1410,53 @@ async fn get_server_status(server_name: String) -> Result<bool, String> {
 // Commande pour télécharger une version Minecraft
 #[tauri::command]
 async fn download_minecraft_version(version: String) -> Result<String, String> {
+    use std::env;
+    use std::path::PathBuf;
+    
     println!("Téléchargement de la version: {}", version);
     
-    // Simuler le téléchargement
-    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+    let details = version_catalog::version_details(&version).await?;
+    let server_url = details.server_url.as_deref()
+        .ok_or(format!("Aucun serveur publié pour la version {}", version))?;
     
-    Ok(format!("minecraft_server_{}.jar", version))
+    let app_data = env::var("APPDATA").map_err(|e| e.to_string())?;
+    let jar_path = PathBuf::from(&app_data)
+        .join("NetherClient")
+        .join("versions")
+        .join(&version)
+        .join("server.jar");
+    
+    downloader::download(
+        &downloader::DownloadRequest::new(server_url, &jar_path)
+            .with_checksum(details.server_checksum())
+            .with_label(&format!("Minecraft {}", version))
+            .cached()
+    ).await?;
+    
+    Ok(jar_path.to_string_lossy().to_string())
 }
 
-// Commande pour obtenir les versions disponibles
+// Commande pour obtenir les versions disponibles (releases par défaut)
 #[tauri::command]
-async fn get_minecraft_versions() -> Result<Vec<String>, String> {
+async fn get_minecraft_versions(kind: Option<String>) -> Result<Vec<String>, String> {
     println!("Récupération des versions Minecraft");
     
-    // Simuler la récupération des versions
-    let versions = vec![
-        "1.20.1".to_string(),
-        "1.20".to_string(),
-        "1.19.4".to_string(),
-        "1.19.3".to_string(),
-        "1.19.2".to_string(),
-    ];
-    
-    Ok(versions)
+    version_catalog::version_ids(kind.as_deref().unwrap_or("release")).await
+}
+
+// Catalogue complet des versions Minecraft avec filtres (types, limite, version Java)
+#[tauri::command]
+async fn get_version_catalog(
+    filter: Option<version_catalog::VersionFilter>,
+    force_refresh: Option<bool>,
+) -> Result<version_catalog::VersionCatalog, String> {
+    version_catalog::catalog(filter.unwrap_or_default(), force_refresh.unwrap_or(false)).await
+}
+
+// Détails d'une version Minecraft (Java requis, téléchargement serveur)
+#[tauri::command]
+async fn get_minecraft_version_details(version: String) -> Result<version_catalog::VersionDetails, String> {
+    version_catalog::version_details(&version).await
 }
 
 // Commande pour vérifier Java
@@ -1634,7 +1667,6 @@ async fn install_playit() -> Result<String, String> {
     use std::env;
     use std::path::PathBuf;
     use std::fs;
-    use std::io::Write;
     
     println!("Début de l'installation de Playit.gg...");
     
@@ -1647,40 +1679,18 @@ async fn install_playit() -> Result<String, String> {
     fs::create_dir_all(&playit_dir).map_err(|e| e.to_string())?;
     
     // URL de téléchargement de Playit.gg pour Windows
-    let download_url = "https://github.com/playit-cloud/playit-agent/releases/latest/download/playit-windows-x86_64.exe";
+    let download_url = endpoints::url(endpoints::Upstream::Playit, "/releases/latest/download/playit-windows-x86_64.exe");
     
     println!("Téléchargement depuis: {}", download_url);
     
-    // Télécharger le fichier
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(300))
-        .build()
-        .map_err(|e| e.to_string())?;
-    
-    let response = client
-        .get(download_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur lors du téléchargement: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(format!("Erreur HTTP: {}", response.status()));
-    }
-    
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Erreur lors de la lecture des données: {}", e))?;
-    
-    println!("Téléchargement terminé: {} octets", bytes.len());
-    
-    // Sauvegarder le fichier
+    // Télécharger le fichier (GitHub ne publie pas d'empreinte pour cet exécutable)
     let playit_exe = playit_dir.join("playit.exe");
-    let mut file = fs::File::create(&playit_exe)
-        .map_err(|e| format!("Erreur lors de la création du fichier: {}", e))?;
+    let download = downloader::download(
+        &downloader::DownloadRequest::new(&download_url, &playit_exe)
+            .with_label("Playit.gg")
+    ).await?;
     
-    file.write_all(&bytes)
-        .map_err(|e| format!("Erreur lors de l'écriture du fichier: {}", e))?;
+    println!("Téléchargement terminé: {} octets", download.size);
     
     println!("Installation terminée: {}", playit_exe.display());
     
@@ -1872,14 +1882,14 @@ async fn fix_server_start_script(server_name: String) -> Result<(), String> {
          echo Utilisation de Java: C:\\Program Files\\Common Files\\Oracle\\Java\\javapath\\bin\\java.exe\n\
          echo.\n\
          echo [INFO] Lancement du serveur...\n\
-         \"C:\\Program Files\\Common Files\\Oracle\\Java\\javapath\\bin\\java.exe\" -Xmx4G -Xms2G -jar mohist-1.12.2-server.jar nogui\n\
+         \"C:\\Program Files\\Common Files\\Oracle\\Java\\javapath\\bin\\java.exe\" -Xmx4G -Xms2G -jar {} nogui\n\
          if %ERRORLEVEL% neq 0 (\n\
              echo [ERROR] Erreur lors du demarrage du serveur (Code: %ERRORLEVEL%)\n\
          )\n\
          echo.\n\
          echo [INFO] Serveur arrete. Appuyez sur une touche pour fermer...\n\
          pause >nul\n",
-        server_name, server_name
+        server_name, server_name, mohist::server_jar(&server_path, "1.12.2")
     );
     
     // Écrire le nouveau script
@@ -1895,7 +1905,6 @@ async fn fix_server_start_script(server_name: String) -> Result<(), String> {
 async fn fix_server_network(server_name: String) -> Result<(), String> {
     use std::env;
     use std::path::PathBuf;
-    use std::fs;
     
     let app_data = env::var("APPDATA").map_err(|e| e.to_string())?;
     let server_path = PathBuf::from(&app_data)
@@ -1909,34 +1918,12 @@ async fn fix_server_network(server_name: String) -> Result<(), String> {
         return Err(format!("Fichier server.properties non trouvé pour le serveur {}", server_name));
     }
     
-    // Lire le fichier existant
-    let mut content = fs::read_to_string(&properties_path)
-        .map_err(|e| format!("Erreur lecture properties: {}", e))?;
-    
-    // Corriger l'IP du serveur
-    if content.contains("server-ip=") {
-        // Remplacer l'IP existante par 0.0.0.0
-        let lines: Vec<&str> = content.lines().collect();
-        let mut new_lines = Vec::new();
-        
-        for line in lines {
-            if line.starts_with("server-ip=") {
-                new_lines.push("server-ip=0.0.0.0");
-            } else {
-                new_lines.push(line);
-            }
-        }
-        
-        content = new_lines.join("\n");
-    } else {
-        // Ajouter la ligne si elle n'existe pas
-        content = format!("{}\nserver-ip=0.0.0.0", content);
+    // Corriger l'IP du serveur (0.0.0.0), sans toucher au reste du fichier
+    let mut document = properties_file::PropertiesFile::load(&properties_path)?;
+    if document.set("server-ip", "0.0.0.0") {
+        document.save(&properties_path)?;
     }
     
-    // Écrire le fichier corrigé
-    fs::write(&properties_path, content)
-        .map_err(|e| format!("Erreur écriture properties: {}", e))?;
-    
     println!("Configuration réseau corrigée pour le serveur: {}", server_name);
     Ok(())
 }
@@ -2628,277 +2615,117 @@ async fn download_java_runtime(version: u8) -> Result<String, String> {
     automation::download_java(version).await
 }
 
+// Versions majeures de Java disponibles au téléchargement (Adoptium)
+#[tauri::command]
+async fn list_available_java_releases() -> Result<java_runtimes::AvailableReleases, String> {
+    java_runtimes::available_releases().await
+}
+
+// Installer ou mettre à jour un runtime Java géré (n'importe quelle version majeure)
+#[tauri::command]
+async fn install_java_runtime(feature: u32) -> Result<java_discovery::JavaRuntime, String> {
+    java_runtimes::install(feature).await
+}
+
+// Runtimes Java installés par Nether Client
+#[tauri::command]
+async fn list_java_runtimes() -> Result<Vec<java_discovery::JavaRuntime>, String> {
+    Ok(java_runtimes::list())
+}
+
+// Supprimer un runtime Java géré (refusé s'il est utilisé par un serveur)
+#[tauri::command]
+async fn remove_java_runtime(path: String) -> Result<(), String> {
+    java_runtimes::remove(&path)
+}
+
 // Vérifier la version de Java installée
 #[tauri::command]
 async fn check_java_version() -> Result<Option<String>, String> {
     Ok(automation::check_java_version())
 }
 
-// Commande pour détecter toutes les versions Java installées
+// Commande pour détecter toutes les versions Java installées (Windows, Linux, macOS), plus récentes en premier
 #[tauri::command]
-async fn detect_java_versions() -> Result<Vec<serde_json::Value>, String> {
-    use std::process::Command;
-    use std::path::PathBuf;
-    use std::fs;
-    
-    let mut java_versions = Vec::new();
-    
-    // 1. Vérifier dans Program Files\Java
-    let program_files = std::env::var("PROGRAMFILES").unwrap_or_else(|_| "C:\\Program Files".to_string());
-    let java_path = PathBuf::from(&program_files).join("Java");
-    
-    if java_path.exists() {
-        if let Ok(entries) = fs::read_dir(&java_path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
-                        if dir_name.starts_with("jdk") || dir_name.starts_with("jre") {
-                            if let Some(version) = extract_java_version_from_path(&path) {
-                                java_versions.push(serde_json::json!({
-                                    "path": path.to_string_lossy().to_string(),
-                                    "version": version,
-                                    "type": if dir_name.starts_with("jdk") { "JDK" } else { "JRE" },
-                                    "source": "Program Files"
-                                }));
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    // 2. Vérifier dans Program Files (x86)\Java
-    let program_files_x86 = std::env::var("PROGRAMFILES(X86)").unwrap_or_else(|_| "C:\\Program Files (x86)".to_string());
-    let java_path_x86 = PathBuf::from(&program_files_x86).join("Java");
-    
-    if java_path_x86.exists() {
-        if let Ok(entries) = fs::read_dir(&java_path_x86) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
-                        if dir_name.starts_with("jdk") || dir_name.starts_with("jre") {
-                            if let Some(version) = extract_java_version_from_path(&path) {
-                                java_versions.push(serde_json::json!({
-                                    "path": path.to_string_lossy().to_string(),
-                                    "version": version,
-                                    "type": if dir_name.starts_with("jdk") { "JDK" } else { "JRE" },
-                                    "source": "Program Files (x86)"
-                                }));
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    // 3. Vérifier avec "where java" et JAVA_HOME
-    if let Ok(output) = Command::new("where").arg("java").output() {
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            for line in stdout.lines() {
-                let java_exe = line.trim();
-                if let Some(java_dir) = PathBuf::from(java_exe).parent() {
-                    if let Some(version) = get_java_version_from_exe(java_exe) {
-                        java_versions.push(serde_json::json!({
-                            "path": java_dir.to_string_lossy().to_string(),
-                            "version": version,
-                            "type": "System",
-                            "source": "PATH"
-                        }));
-                    }
-                }
-            }
-        }
-    }
-    
-    // 4. Vérifier JAVA_HOME
-    if let Ok(java_home) = std::env::var("JAVA_HOME") {
-        let java_home_path = PathBuf::from(&java_home);
-        if java_home_path.exists() {
-            if let Some(version) = get_java_version_from_exe(&java_home_path.join("bin").join("java.exe").to_string_lossy()) {
-                java_versions.push(serde_json::json!({
-                    "path": java_home,
-                    "version": version,
-                    "type": "JDK",
-                    "source": "JAVA_HOME"
-                }));
-            }
-        }
-    }
-    
-    // Trier par version (plus récente en premier)
-    java_versions.sort_by(|a, b| {
-        let version_a = a["version"].as_str().unwrap_or("0");
-        let version_b = b["version"].as_str().unwrap_or("0");
-        version_b.cmp(version_a)
-    });
-    
-    Ok(java_versions)
+async fn detect_java_versions() -> Result<Vec<java_discovery::JavaRuntime>, String> {
+    Ok(java_discovery::discover())
 }
 
-// Fonction pour extraire la version Java depuis un chemin
-fn extract_java_version_from_path(path: &std::path::Path) -> Option<String> {
-    if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
-        // Extraire la version depuis le nom du dossier (ex: jdk-21.0.1, jre1.8.0_291)
-        if let Some(version_start) = dir_name.find('-') {
-            let version_part = &dir_name[version_start + 1..];
-            if let Some(version_end) = version_part.find('-') {
-                return Some(version_part[..version_end].to_string());
-            } else {
-                return Some(version_part.to_string());
-            }
-        } else if dir_name.starts_with("jre") {
-            let version_part = &dir_name[3..];
-            return Some(version_part.replace('_', "."));
-        }
-    }
-    None
+// Version majeure de l'exécutable Java ("1.8.0_391" → 8)
+fn java_major_from_exe(java_exe: &str) -> Option<u32> {
+    java_version::JavaVersion::probe(std::path::Path::new(java_exe)).map(|version| version.feature)
 }
 
-// Fonction pour obtenir la version Java depuis un exécutable
-fn get_java_version_from_exe(java_exe: &str) -> Option<String> {
-    use std::process::Command;
-    
-    if let Ok(output) = Command::new(java_exe).arg("-version").output() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        if let Some(version_line) = stderr.lines().next() {
-            if let Some(version) = version_line.split_whitespace().nth(2) {
-                return Some(version.trim_matches('"').to_string());
-            }
-        }
-    }
-    None
+// Profil JVM recommandé d'un nouveau serveur et runtime Java épinglé (dernière mise à jour de la version
+// choisie), enregistrés dans nether-config.json ; renvoie les arguments JVM
+fn init_jvm_profile(server_path: &std::path::Path, ram_mb: u32, server_type: &str, java_path: &str) -> Result<Vec<String>, String> {
+    let profile = jvm_profile::JvmProfile::recommended(ram_mb, server_type);
+    jvm_profile::save(server_path, &profile)?;
+    let java_major = java_major_from_exe(java_path);
+    java_pin::save(server_path, &java_major.map_or(java_pin::JavaPin::Auto, |feature| java_pin::JavaPin::Latest { feature }))?;
+    jvm_profile::arguments(&profile, java_major)
 }
 
-// Commande pour obtenir la version Java recommandée pour une version Minecraft
+// Commande pour obtenir la version Java recommandée pour une version Minecraft (ou de loader)
 #[tauri::command]
-async fn get_recommended_java_version(minecraft_version: &str) -> Result<String, String> {
-    // Logique de recommandation basée sur la version Minecraft
-    let version_parts: Vec<&str> = minecraft_version.split('.').collect();
-    
-    if version_parts.len() >= 2 {
-        if let Ok(major) = version_parts[0].parse::<i32>() {
-            if let Ok(minor) = version_parts[1].parse::<i32>() {
-                // Minecraft 1.21+ nécessite Java 21
-                if major > 1 || (major == 1 && minor >= 21) {
-                    return Ok("21".to_string());
-                }
-                // Minecraft 1.17-1.20 nécessite Java 17+
-                else if major > 1 || (major == 1 && minor >= 17) {
-                    return Ok("17".to_string());
-                }
-                // Minecraft 1.16 et antérieur fonctionne avec Java 8+
-                else {
-                    return Ok("8".to_string());
-                }
-            }
-        }
-    }
-    
-    // Par défaut, recommander Java 21 pour les versions récentes
-    Ok("21".to_string())
+async fn get_recommended_java_version(minecraft_version: &str, server_type: Option<String>) -> Result<String, String> {
+    let requirement = java_requirement::for_version(minecraft_version, server_type.as_deref().unwrap_or("vanilla")).await;
+    Ok(requirement.recommended().to_string())
 }
 
 // Commande pour sélectionner automatiquement la meilleure version Java
 #[tauri::command]
-async fn select_best_java_version(minecraft_version: &str) -> Result<Option<serde_json::Value>, String> {
-    let recommended_version = get_recommended_java_version(minecraft_version).await?;
+async fn select_best_java_version(minecraft_version: &str, server_type: Option<String>) -> Result<Option<java_discovery::JavaRuntime>, String> {
+    let requirement = java_requirement::for_version(minecraft_version, server_type.as_deref().unwrap_or("vanilla")).await;
     let java_versions = detect_java_versions().await?;
     
-    // Chercher la version exacte recommandée
-    for java in &java_versions {
-        if let Some(version) = java["version"].as_str() {
-            if version.starts_with(&recommended_version) {
-                return Ok(Some(java.clone()));
-            }
-        }
-    }
-    
-    // Si pas trouvé, chercher une version supérieure
-    let recommended_major: i32 = recommended_version.parse().unwrap_or(21);
-    for java in &java_versions {
-        if let Some(version) = java["version"].as_str() {
-            if let Ok(version_major) = version.split('.').next().unwrap_or("0").parse::<i32>() {
-                if version_major >= recommended_major {
-                    return Ok(Some(java.clone()));
-                }
-            }
-        }
-    }
-    
-    // Si toujours pas trouvé, prendre la version la plus récente
-    if !java_versions.is_empty() {
-        return Ok(Some(java_versions[0].clone()));
-    }
-    
-    Ok(None)
+    Ok(java_discovery::best_match(&java_versions, &requirement).cloned())
 }
 
-// Fonction utilitaire pour obtenir le chemin Java correct pour une version Minecraft
-async fn get_java_executable_path(minecraft_version: &str) -> Result<String, String> {
-    use std::path::PathBuf;
-    
-    let recommended_version = get_recommended_java_version(minecraft_version).await?;
+// Exigence Java d'un serveur installé (minimum, maximum et leur origine)
+#[tauri::command]
+async fn get_server_java_requirement(server_path: String) -> Result<Option<java_requirement::JavaRequirement>, String> {
+    Ok(java_requirement::for_server(std::path::Path::new(&server_path)).await)
+}
+
+// Runtime Java épinglé d'un serveur et sa résolution actuelle
+#[tauri::command]
+async fn get_java_pin(server_path: String) -> Result<java_pin::PinStatus, String> {
+    Ok(java_pin::status(std::path::Path::new(&server_path)).await)
+}
+
+// Épingler un serveur sur une installation ou une version majeure ; start.bat est mis à jour aussitôt
+#[tauri::command]
+async fn set_java_pin(server_path: String, pin: java_pin::JavaPin) -> Result<java_pin::PinStatus, String> {
+    let path = std::path::Path::new(&server_path);
+    let _reservation = reserve_server(&server_import::server_name(path), "de changer son runtime Java")?;
+    java_pin::set(path, &pin).await
+}
+
+// Épinglage Java de tous les serveurs
+#[tauri::command]
+async fn list_java_pins() -> Result<Vec<java_pin::PinStatus>, String> {
+    Ok(java_pin::list().await)
+}
+
+// Ré-épingler plusieurs serveurs d'un coup (tous si aucune liste n'est donnée)
+#[tauri::command]
+async fn repin_servers(pin: java_pin::JavaPin, server_paths: Option<Vec<String>>) -> Result<Vec<java_pin::RepinResult>, String> {
+    Ok(java_pin::repin(&pin, server_paths, |name| reserve_server(name, "de changer son runtime Java")).await)
+}
+
+// Fonction utilitaire pour obtenir le chemin Java correct pour une version Minecraft et un loader
+async fn get_java_executable_path(minecraft_version: &str, server_type: &str) -> Result<String, String> {
+    let requirement = java_requirement::for_version(minecraft_version, server_type).await;
     let java_versions = detect_java_versions().await?;
     
-    // Fonction helper pour construire et vérifier le chemin Java
-    let build_and_check_path = |java_path_str: &str| -> Option<String> {
-        let java_path = PathBuf::from(java_path_str);
-        let java_exe = java_path.join("bin").join("java.exe");
-        
-        // Normaliser le chemin (convertir en String avec backslashes pour Windows)
-        let normalized_path = java_exe.to_string_lossy().replace('/', "\\");
-        
-        // Vérifier que le fichier existe
-        if std::path::Path::new(&normalized_path).exists() {
-            println!("✅ Chemin Java trouvé et vérifié: {}", normalized_path);
-            Some(normalized_path)
-        } else {
-            println!("❌ Chemin Java n'existe pas: {}", normalized_path);
-            None
-        }
-    };
-    
-    // Chercher la version exacte recommandée
-    for java in &java_versions {
-        if let Some(version) = java["version"].as_str() {
-            if version.starts_with(&recommended_version) {
-                if let Some(path) = java["path"].as_str() {
-                    if let Some(valid_path) = build_and_check_path(path) {
-                        return Ok(valid_path);
-                    }
-                }
-            }
-        }
-    }
-    
-    // Si pas trouvé, chercher une version supérieure
-    let recommended_major: i32 = recommended_version.parse().unwrap_or(21);
-    for java in &java_versions {
-        if let Some(version) = java["version"].as_str() {
-            if let Ok(version_major) = version.split('.').next().unwrap_or("0").parse::<i32>() {
-                if version_major >= recommended_major {
-                    if let Some(path) = java["path"].as_str() {
-                        if let Some(valid_path) = build_and_check_path(path) {
-                            return Ok(valid_path);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    // Si toujours pas trouvé, prendre la version la plus récente
-    for java in &java_versions {
-        if let Some(path) = java["path"].as_str() {
-            if let Some(valid_path) = build_and_check_path(path) {
-                return Ok(valid_path);
-            }
+    // Version recommandée, sinon la plus proche dans les limites, sinon la plus récente
+    if let Some(java) = java_discovery::best_match(&java_versions, &requirement) {
+        if !requirement.accepts(java.details.feature) {
+            println!("⚠️ Aucun Java compatible ({}) : {} utilisé faute de mieux", requirement.label(), java.version);
         }
+        println!("✅ Java {} trouvé: {} ({})", java.version, java.executable, java.source);
+        return Ok(java.executable.clone());
     }
     
     // Fallback sur java système (dans le PATH)
@@ -2918,10 +2745,47 @@ async fn cleanup_app_cache() -> Result<u64, String> {
     automation::cleanup_cache()
 }
 
+// Statistiques du cache d'artefacts partagé
+#[tauri::command]
+async fn get_artifact_cache_stats() -> Result<artifact_cache::CacheStats, String> {
+    artifact_cache::stats()
+}
+
+// Modifier la taille maximale du cache d'artefacts (en octets)
+#[tauri::command]
+async fn set_artifact_cache_limit(max_size: u64) -> Result<artifact_cache::CacheStats, String> {
+    artifact_cache::set_max_size(max_size)
+}
+
+// Sources de téléchargement configurées (URL officielle et éventuel miroir)
+#[tauri::command]
+async fn get_endpoints() -> Result<Vec<endpoints::EndpointInfo>, String> {
+    endpoints::list()
+}
+
+// Configuration brute des sources (miroirs et mode hors ligne)
+#[tauri::command]
+async fn get_endpoint_config() -> Result<endpoints::EndpointConfig, String> {
+    endpoints::config()
+}
+
+// Enregistrer les miroirs et le mode hors ligne
+#[tauri::command]
+async fn set_endpoint_config(config: endpoints::EndpointConfig) -> Result<Vec<endpoints::EndpointInfo>, String> {
+    endpoints::save_config(config)?;
+    endpoints::list()
+}
+
 // Vérifier les mises à jour disponibles pour un serveur
 #[tauri::command]
-async fn check_updates(version: String, server_type: String) -> Result<Option<String>, String> {
-    automation::check_server_updates(&version, &server_type).await
+async fn check_updates(version: String, server_type: String, server_path: Option<String>) -> Result<Option<String>, String> {
+    automation::check_server_updates(&version, &server_type, server_path.as_deref()).await
+}
+
+// Rapport détaillé des mises à jour (builds du loader et versions Minecraft plus récentes)
+#[tauri::command]
+async fn get_update_report(version: String, server_type: String, server_path: Option<String>) -> Result<updates::UpdateReport, String> {
+    updates::check(&version, &server_type, server_path.as_deref().map(std::path::Path::new)).await
 }
 
 // Obtenir les flags Java optimisés
@@ -2930,6 +2794,46 @@ async fn get_optimized_java_flags(ram_mb: u32, version: String, server_type: Str
     Ok(automation::get_optimized_java_flags(ram_mb, &version, &server_type))
 }
 
+// Version majeure du Java utilisé par le script de lancement d'un serveur
+fn server_java_major(server_dir: &std::path::Path) -> Option<u32> {
+    jvm_profile::launch_java(server_dir).and_then(|java| java_major_from_exe(&java))
+}
+
+// Profil JVM d'un serveur (enregistré, ou déduit de son script s'il n'en a pas encore)
+#[tauri::command]
+async fn get_jvm_profile(server_path: String) -> Result<jvm_profile::JvmProfileState, String> {
+    let server_dir = std::path::Path::new(&server_path);
+    let stored = jvm_profile::load(server_dir);
+    let java_major = server_java_major(server_dir);
+
+    let profile = match &stored {
+        Some(profile) => profile.clone(),
+        None => jvm_profile::JvmProfile::recommended(
+            jvm_profile::current_heap_mb(server_dir).unwrap_or(4096),
+            &server_import::fingerprint(server_dir).loader,
+        ),
+    };
+
+    Ok(jvm_profile::JvmProfileState {
+        arguments: jvm_profile::arguments(&profile, java_major).unwrap_or_default(),
+        collectors: jvm_profile::collectors(java_major),
+        stored: stored.is_some(),
+        java_major,
+        profile,
+    })
+}
+
+// Enregistrer un profil JVM et réécrire le script de lancement (pris en compte au prochain démarrage)
+#[tauri::command]
+async fn set_jvm_profile(server_path: String, profile: jvm_profile::JvmProfile) -> Result<jvm_profile::JvmProfileState, String> {
+    let server_dir = std::path::Path::new(&server_path);
+    jvm_profile::apply(server_dir, &profile, server_java_major(server_dir))?;
+    jvm_profile::save(server_dir, &profile)?;
+    println!("⚙️ Profil JVM appliqué ({} Mo, {}): {}", profile.heap_mb, profile.gc.id(), server_path);
+
+    get_jvm_profile(server_path).await
+}
+
 // Détecter si un serveur a crashé
 #[tauri::command]
 async fn detect_crash(server_name: String) -> Result<bool, String> {
@@ -3221,18 +3125,343 @@ async fn export_modpack(server_path: String, output_name: String) -> Result<Stri
     Ok(format!("Modpack exporté: {} ({} mods)", zip_path.display(), mod_count))
 }
 
+// ========== IMPORT DE SERVEURS EXISTANTS ==========
+
+// Identifier le loader et les versions d'un dossier ou d'une archive de serveur
+#[tauri::command]
+async fn inspect_server_folder(source: String) -> Result<server_import::ServerFingerprint, String> {
+    let mut fingerprint = server_import::inspect(&source)?;
+    if fingerprint.minecraft_version.is_none() && std::path::Path::new(&source).is_dir() {
+        let detected = detect_version_internal(&std::path::PathBuf::from(&source));
+        fingerprint.minecraft_version = (!detected.is_empty()).then_some(detected);
+    }
+    Ok(fingerprint)
+}
+
+// Importer un serveur existant : adoption sur place ("in_place") ou copie dans Serveurs ("copy")
+#[tauri::command]
+async fn import_server_folder(source: String, name: String, mode: String, ram: Option<u32>) -> Result<serde_json::Value, String> {
+    let name = server_clone::validate_name(&name)?.to_string();
+    
+    let server_dir = match mode.as_str() {
+        "in_place" => server_import::adopt_in_place(&source, &name)?,
+        "copy" => server_import::copy_into_servers(&source, &name)?,
+        _ => return Err(format!("Mode d'import inconnu: {}", mode)),
+    };
+    
+    let mut fingerprint = server_import::fingerprint(&server_dir);
+    if fingerprint.minecraft_version.is_none() {
+        let detected = detect_version_internal(&server_dir);
+        fingerprint.minecraft_version = (!detected.is_empty()).then_some(detected);
+    }
+    println!("📥 Serveur importé: {} ({} {})", name, fingerprint.loader, fingerprint.minecraft_version.as_deref().unwrap_or("?"));
+    
+    // Générer start.bat s'il n'existe pas encore (le script existant n'est jamais écrasé)
+    let start_script = server_dir.join("start.bat");
+    let mut script_written = false;
+    if !start_script.exists() {
+        let minecraft = fingerprint.minecraft_version.clone().unwrap_or_default();
+        let java_path = get_java_executable_path(&minecraft, &fingerprint.loader).await.unwrap_or_else(|_| "java".to_string());
+        let jvm_args = init_jvm_profile(&server_dir, ram.unwrap_or(4096), &fingerprint.loader, &java_path)?;
+        let script = server_import::launch_script(&name, &fingerprint, &java_path, &jvm_args)?;
+        if fingerprint.args_file.is_some() {
+            jvm_profile::write_user_jvm_args(&server_dir, &jvm_args)?;
+        }
+        std::fs::write(&start_script, script)
+            .map_err(|e| format!("Erreur écriture start.bat: {}", e))?;
+        script_written = true;
+    }
+    
+    // Version au format attendu par les mises à jour (build Paper, version complète du loader)
+    let config_version = match fingerprint.loader.as_str() {
+        "forge" | "neoforge" => fingerprint.loader_version.clone(),
+        "paper" | "folia" | "purpur" => match (&fingerprint.minecraft_version, &fingerprint.loader_version) {
+            (Some(minecraft), Some(build)) => Some(format!("{}-{}", minecraft, build)),
+            (minecraft, _) => minecraft.clone(),
+        },
+        _ => fingerprint.minecraft_version.clone(),
+    };
+    if let Some(version) = &config_version {
+        update_server_version_config(&server_dir, version)?;
+    }
+    
+    // L'EULA n'est jamais acceptée à la place de l'utilisateur : le serveur ne démarrera pas avant
+    let eula_accepted = eula::is_accepted(&server_dir);
+    if !eula_accepted {
+        println!("⚠️ EULA non acceptée pour le serveur importé {}", name);
+    }
+    
+    Ok(serde_json::json!({
+        "name": name,
+        "path": server_dir.to_string_lossy().to_string(),
+        "type": fingerprint.loader,
+        "version": fingerprint.minecraft_version.clone().unwrap_or_else(|| "Unknown".to_string()),
+        "port": fingerprint.port.unwrap_or(25565),
+        "script_written": script_written,
+        "eula_accepted": eula_accepted,
+        "fingerprint": fingerprint
+    }))
+}
+
+// Lire une instance client (Prism/MultiMC, CurseForge) et classer ses mods
+#[tauri::command]
+async fn inspect_client_instance(instance_path: String) -> Result<serde_json::Value, String> {
+    let instance = instance_import::read_instance(&instance_path)?;
+    let mods = instance_import::classify_mods(&instance);
+    let target = instance.server_target();
+    
+    Ok(serde_json::json!({
+        "instance": instance,
+        "mods": mods,
+        "server_type": target.as_ref().ok().map(|(server_type, _)| server_type.clone()),
+        "server_version": target.as_ref().ok().map(|(_, version)| version.clone()),
+        "error": target.err()
+    }))
+}
+
+// Créer un serveur à partir d'une instance client : loader et version de l'instance, puis mods et configs
+#[tauri::command]
+async fn import_client_instance(
+    instance_path: String,
+    name: Option<String>,
+    port: Option<u16>,
+    ram: Option<u32>,
+    accept_eula: Option<bool>,
+    eula_accepted_by: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let instance = instance_import::read_instance(&instance_path)?;
+    let (server_type, version) = instance.server_target()?;
+    // Le nom de l'instance (ou celui choisi) devient un dossier de Serveurs
+    let name = server_clone::validate_name(&name.unwrap_or_else(|| instance.name.clone()))?.to_string();
+    
+    let server_dir = app_paths::app_dir()?.join("Serveurs").join(&name);
+    if server_dir.exists() {
+        return Err(format!("Un serveur nommé {} existe déjà", name));
+    }
+    
+    let port = match port {
+        Some(port) => port,
+        None => get_available_port().await?,
+    };
+    
+    let config = ServerConfig {
+        name: name.clone(),
+        version: version.clone(),
+        port,
+        ram: ram.unwrap_or(6144),
+        motd: name.clone(),
+        max_players: 20,
+        difficulty: "normal".to_string(),
+        gamemode: "survival".to_string(),
+        build: None,
+        accept_eula: accept_eula.unwrap_or(false),
+        eula_accepted_by,
+    };
+    
+    println!("📥 Import de l'instance {} ({} {})", instance.name, server_type, version);
+    
+    let server_id = match server_type.as_str() {
+        "forge" => create_forge_server(config).await?,
+        "neoforge" => create_neoforge_server(config).await?,
+        _ => create_vanilla_server(config).await?,
+    };
+    // Un import interrompu ne laisse pas de serveur à moitié créé
+    let report = update_server_version_config(&server_dir, &version)
+        .and_then(|_| instance_import::copy_content(&instance, &server_dir))
+        .inspect_err(|_| {
+            let _ = std::fs::remove_dir_all(&server_dir);
+        })?;
+    
+    Ok(serde_json::json!({
+        "server_id": server_id,
+        "name": name,
+        "path": server_dir.to_string_lossy().to_string(),
+        "type": server_type,
+        "version": version,
+        "port": port,
+        "report": report
+    }))
+}
+
+// ========== DUPLICATION ET RENOMMAGE ==========
+
+// Dupliquer un serveur arrêté (nouveau port attribué automatiquement)
+#[tauri::command]
+async fn clone_server(server_name: String, server_path: String, new_name: String) -> Result<server_clone::ClonedServer, String> {
+    // Copie cohérente : ni démarrage ni mise à jour pendant la duplication
+    let _reservation = reserve_server(&server_name, "de le dupliquer")?;
+    
+    server_clone::clone_server(&server_name, &server_path, &new_name)
+}
+
+// Renommer un serveur arrêté (dossier, start.bat, config, sauvegardes et état en mémoire)
+#[tauri::command]
+async fn rename_server(server_name: String, server_path: String, new_name: String) -> Result<String, String> {
+    // Réservé pendant le renommage : aucun démarrage sous l'ancien nom
+    let _reservation = reserve_server(&server_name, "de le renommer")?;
+    
+    let new_path = server_clone::rename_server(&server_name, &server_path, &new_name)?;
+    let new_name = new_name.trim().to_string();
+    server_import::update_adopted(&server_path, &new_name, &new_path)?;
+    
+    // Les états en mémoire sont indexés par nom de serveur (un seul verrou par table)
+    {
+        let mut logs = SERVER_LOGS.lock().unwrap();
+        if let Some(server_logs) = logs.remove(&server_name) {
+            logs.insert(new_name.clone(), server_logs);
+        }
+    }
+    {
+        let mut auto_restart = AUTO_RESTART_ENABLED.lock().unwrap();
+        if let Some(enabled) = auto_restart.remove(&server_name) {
+            auto_restart.insert(new_name.clone(), enabled);
+        }
+    }
+    {
+        let mut crash_counts = SERVER_CRASH_COUNT.lock().unwrap();
+        if let Some(count) = crash_counts.remove(&server_name) {
+            crash_counts.insert(new_name, count);
+        }
+    }
+    
+    Ok(new_path)
+}
+
+// ========== MODÈLES DE SERVEUR ==========
+
+// Enregistrer un serveur existant comme modèle
+#[tauri::command]
+async fn save_server_as_template(server_path: String, server_type: String, options: templates::TemplateOptions) -> Result<templates::TemplateManifest, String> {
+    let server_dir = std::path::PathBuf::from(&server_path);
+    let version = std::fs::read_to_string(server_dir.join("nether-config.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|config| config["version"].as_str().map(|v| v.to_string()))
+        .unwrap_or_else(|| detect_version_internal(&server_dir));
+    
+    if version.is_empty() {
+        return Err("Impossible de déterminer la version du serveur".to_string());
+    }
+    
+    templates::save_from_server(&server_path, &server_type, &version, &options)
+}
+
+#[tauri::command]
+async fn list_templates() -> Result<Vec<templates::TemplateManifest>, String> {
+    Ok(templates::list())
+}
+
+#[tauri::command]
+async fn delete_template(template_id: String) -> Result<(), String> {
+    templates::delete(&template_id)
+}
+
+#[tauri::command]
+async fn export_template(template_id: String, output_path: String) -> Result<String, String> {
+    templates::export(&template_id, &output_path)
+}
+
+#[tauri::command]
+async fn import_template(archive_path: String) -> Result<templates::TemplateManifest, String> {
+    templates::import(&archive_path)
+}
+
+// Créer un nouveau serveur à partir d'un modèle (nom, port et RAM personnalisables)
+#[tauri::command]
+async fn create_server_from_template(template_id: String, overrides: templates::TemplateOverrides) -> Result<String, String> {
+    use std::path::PathBuf;
+    
+    let manifest = templates::load(&template_id)?;
+    
+    // Le nom devient un dossier de Serveurs : même validation que la duplication
+    let mut overrides = overrides;
+    overrides.name = server_clone::validate_name(&overrides.name)?.to_string();
+    
+    let app_data = std::env::var("APPDATA").map_err(|e| e.to_string())?;
+    let server_dir = PathBuf::from(&app_data)
+        .join("NetherClient")
+        .join("Serveurs")
+        .join(&overrides.name);
+    
+    if server_dir.exists() {
+        return Err(format!("Un serveur nommé {} existe déjà", overrides.name));
+    }
+    
+    let port = match overrides.port {
+        Some(port) => port,
+        None => get_available_port().await?,
+    };
+    
+    let property = |key: &str| manifest.properties.get(key).cloned();
+    let paper_project = paper_family::PaperProject::from_server_type(&manifest.server_type);
+    
+    // Les builds Paper/Mohist sont enregistrés sous la forme "1.20.4-496"
+    let (version, build) = if paper_project.is_some() || manifest.server_type == "mohist" {
+        updates::split_build(&manifest.version)
+    } else {
+        (manifest.version.clone(), None)
+    };
+    
+    let config = ServerConfig {
+        name: overrides.name.clone(),
+        version,
+        port,
+        ram: overrides.ram.or(manifest.ram_mb).unwrap_or(4096),
+        motd: overrides.motd.clone().or_else(|| property("motd")).unwrap_or_else(|| overrides.name.clone()),
+        max_players: overrides.max_players
+            .or_else(|| property("max-players").and_then(|v| v.parse().ok()))
+            .unwrap_or(20),
+        difficulty: property("difficulty").unwrap_or_else(|| "normal".to_string()),
+        gamemode: property("gamemode").unwrap_or_else(|| "survival".to_string()),
+        build,
+        accept_eula: overrides.accept_eula,
+        eula_accepted_by: overrides.eula_accepted_by.clone(),
+    };
+    
+    println!("📐 Création du serveur {} depuis le modèle \"{}\"", overrides.name, manifest.name);
+    
+    let server_id = match manifest.server_type.as_str() {
+        "vanilla" => create_vanilla_server(config).await?,
+        "forge" => create_forge_server(config).await?,
+        "neoforge" => create_neoforge_server(config).await?,
+        "mohist" => create_mohist_server(config, None).await?,
+        _ => match paper_project {
+            Some(project) => create_paper_family_server(config, project).await?,
+            None => return Err(format!("Type de serveur non supporté: {}", manifest.server_type)),
+        },
+    };
+    
+    templates::apply(&manifest, &server_dir, &overrides, port)?;
+
+    // Reprendre le profil JVM du modèle, avec la mémoire choisie pour ce serveur
+    if let Some(mut profile) = manifest.jvm_profile.clone() {
+        if let Some(ram) = overrides.ram {
+            profile.heap_mb = ram;
+            profile.min_heap_mb = profile.min_heap_mb.map(|min| min.min(ram));
+        }
+        jvm_profile::apply(&server_dir, &profile, server_java_major(&server_dir))?;
+        jvm_profile::save(&server_dir, &profile)?;
+    }
+    update_server_version_config(&server_dir, &manifest.version)?;
+    
+    println!("Serveur {} créé depuis le modèle", overrides.name);
+    Ok(server_id)
+}
+
 // ========== MISE À JOUR AUTOMATIQUE DES SERVEURS ==========
 
-// Mettre à jour un serveur vers une nouvelle version
+// Mettre à jour un serveur vers une nouvelle version (préparation à part, échange, vérification, rollback)
 #[tauri::command]
-async fn update_server(server_name: String, server_path: String, new_version: String, server_type: String) -> Result<String, String> {
+async fn update_server(server_name: String, server_path: String, new_version: String, server_type: String, verify: Option<bool>) -> Result<String, String> {
     use std::path::PathBuf;
-    use std::fs;
     
     println!("Mise à jour du serveur {} vers {}", server_name, new_version);
     
     let server_dir = PathBuf::from(&server_path);
     
+    // Le démarrage de vérification utilise le même port : le serveur reste réservé jusqu'à la fin
+    let _reservation = reserve_server(&server_name, "la mise à jour")?;
+    
     // 1. Créer une sauvegarde avant la mise à jour
     println!("Création d'une sauvegarde de sécurité...");
     let backup_result = create_backup_sync(&server_name, &server_path);
@@ -3240,142 +3469,329 @@ async fn update_server(server_name: String, server_path: String, new_version: St
         return Err(format!("Erreur création backup: {}", e));
     }
     
-    // 2. Télécharger la nouvelle version selon le type
+    let from_version = std::fs::read_to_string(server_dir.join("nether-config.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|config| config["version"].as_str().map(|v| v.to_string()));
+    let started_at = chrono::Utc::now().to_rfc3339();
+    
+    let mut transaction = server_update::UpdateTransaction::begin(&server_dir)?;
+    let mut history = server_update::UpdateRecord {
+        id: transaction.id().to_string(),
+        server_type: server_type.clone(),
+        from_version: from_version.clone(),
+        to_version: new_version.clone(),
+        started_at,
+        finished_at: String::new(),
+        status: "failed".to_string(),
+        verified: false,
+        error: None,
+        log_tail: Vec::new(),
+    };
+    
+    // 2. Préparer les nouveaux fichiers dans le dossier de staging
+    let staged = stage_server_update(&server_dir, &transaction.staging(), &new_version, &server_type).await;
+    let installed_version = match staged {
+        Ok(version) => version,
+        Err(e) => {
+            // Rien n'a encore été touché dans le dossier serveur
+            let _ = transaction.rollback();
+            history.error = Some(e.clone());
+            history.finished_at = chrono::Utc::now().to_rfc3339();
+            let _ = server_update::record(&server_dir, history);
+            return Err(e);
+        }
+    };
+    history.to_version = installed_version.clone();
+    
+    // 3. Échanger les fichiers puis vérifier que le serveur démarre
+    let outcome = match transaction.swap() {
+        Err(e) => Err((e, Vec::new())),
+        Ok(()) if verify.unwrap_or(true) => {
+            server_update::verify_boot(&server_dir, std::time::Duration::from_secs(300)).await
+        }
+        Ok(()) => Ok(Vec::new()),
+    };
+    
+    history.finished_at = chrono::Utc::now().to_rfc3339();
+    match outcome {
+        Ok(log) => {
+            transaction.finish()?;
+            history.status = "success".to_string();
+            history.verified = verify.unwrap_or(true);
+            history.log_tail = log;
+            let _ = server_update::record(&server_dir, history);
+            Ok(format!("Serveur {} mis à jour vers {} avec succès!", server_name, installed_version))
+        }
+        Err((e, log)) => {
+            // 4. Échec : retour exact aux fichiers précédents
+            println!("❌ Échec de la mise à jour: {}", e);
+            let rollback = transaction.rollback();
+            history.status = if rollback.is_ok() { "rolled_back" } else { "failed" }.to_string();
+            history.error = Some(match &rollback {
+                Ok(()) => e.clone(),
+                Err(rollback_error) => format!("{} (restauration impossible: {})", e, rollback_error),
+            });
+            history.log_tail = log;
+            let _ = server_update::record(&server_dir, history);
+            
+            match rollback {
+                Ok(()) => Err(format!("Mise à jour annulée, le serveur est revenu à la version précédente: {}", e)),
+                Err(rollback_error) => Err(format!("Mise à jour échouée et restauration impossible: {} / {}", e, rollback_error)),
+            }
+        }
+    }
+}
+
+// Préparer dans `staging` les fichiers qui remplaceront ceux du serveur. Renvoie la version installée.
+async fn stage_server_update(server_dir: &std::path::Path, staging: &std::path::PathBuf, new_version: &str, server_type: &str) -> Result<String, String> {
+    use std::fs;
+    
     println!("Téléchargement de la version {}...", new_version);
-    let jar_name = match server_type.as_str() {
+    let paper_project = paper_family::PaperProject::from_server_type(server_type);
+    
+    let installed_version = match server_type {
         "vanilla" => {
-            download_vanilla_jar(&new_version, &server_dir).await?;
-            "server.jar"
+            download_vanilla_jar(new_version, staging).await?;
+            new_version.to_string()
         },
-        "forge" => {
-            download_forge_installer(&new_version, &server_dir).await?;
-            "forge-installer.jar"
+        "forge" | "neoforge" => {
+            let (installer, minecraft) = if server_type == "forge" {
+                download_forge_installer(new_version, staging).await?;
+                ("forge-installer.jar", new_version.split('-').next().unwrap_or(new_version).to_string())
+            } else {
+                download_neoforge_installer(new_version, staging).await?;
+                ("neoforge-installer.jar", updates::neoforge_minecraft_version(new_version).unwrap_or_default())
+            };
+            
+            println!("Installation du loader...");
+            let java_path = get_java_executable_path(&minecraft, server_type).await.unwrap_or_else(|_| "java".to_string());
+            let output = Command::new(&java_path)
+                .args(["-jar", installer, "--installServer"])
+                .current_dir(staging)
+                .output()
+                .map_err(|e| format!("Erreur exécution installeur: {}", e))?;
+            
+            if !output.status.success() {
+                return Err(format!("Erreur installation: {}", String::from_utf8_lossy(&output.stderr)));
+            }
+            
+            // Ne pas installer l'installeur et son log dans le dossier serveur
+            let _ = fs::remove_file(staging.join(installer));
+            let _ = fs::remove_file(staging.join(format!("{}.log", installer)));
+            let _ = fs::remove_file(staging.join("installer.log"));
+            stage_loader_start_script(server_dir, staging, server_type, new_version)?;
+            
+            println!("Loader installé avec succès");
+            new_version.to_string()
+        },
+        "mohist" => {
+            // Le nom du JAR existant est conservé pour start.bat
+            let jar_name = mohist::server_jar(server_dir, &updates::split_build(new_version).0);
+            download_mohist_jar(new_version, &staging.join(jar_name)).await?
         },
-        "neoforge" => {
-            download_neoforge_installer(&new_version, &server_dir).await?;
-            "neoforge-installer.jar"
+        _ => match paper_project {
+            Some(project) => {
+                let jar_name = paper_family::server_jar(server_dir, project);
+                download_paper_family_jar(project, new_version, &staging.join(jar_name)).await?
+            },
+            None => return Err("Type de serveur non supporté".to_string()),
         },
-        _ => return Err("Type de serveur non supporté".to_string()),
     };
     
-    // 3. Sauvegarder l'ancien JAR
-    let old_jar = server_dir.join(jar_name);
-    if old_jar.exists() {
-        let backup_jar = server_dir.join(format!("{}.old", jar_name));
-        fs::rename(&old_jar, &backup_jar)
-            .map_err(|e| format!("Erreur sauvegarde ancien JAR: {}", e))?;
-        println!("Ancien JAR sauvegardé");
+    // La configuration mise à jour est échangée avec le reste pour pouvoir être restaurée
+    let config_file = server_dir.join("nether-config.json");
+    if config_file.exists() {
+        fs::copy(&config_file, staging.join("nether-config.json"))
+            .map_err(|e| format!("Erreur copie config: {}", e))?;
     }
+    update_server_version_config(staging, &installed_version)?;
     
-    // 4. Pour Forge/NeoForge, exécuter l'installeur
-    if server_type == "forge" || server_type == "neoforge" {
-        println!("Installation du loader...");
-        let _installer_path = server_dir.join(jar_name);
-        
-        let output = Command::new("java")
-            .args(["-jar", jar_name, "--installServer"])
-            .current_dir(&server_dir)
-            .output()
-            .map_err(|e| format!("Erreur exécution installeur: {}", e))?;
+    Ok(installed_version)
+}
+
+// Pointer start.bat vers le nouveau loader : arguments libraries/.../<version>/win_args.txt,
+// ou JAR forge-<version>.jar lancé directement (Forge avant 1.17)
+fn stage_loader_start_script(server_dir: &std::path::Path, staging: &std::path::Path, loader: &str, new_version: &str) -> Result<(), String> {
+    let Ok(content) = std::fs::read_to_string(server_dir.join("start.bat")) else { return Ok(()) };
+    
+    let group = if loader == "forge" { "minecraftforge" } else { "neoforged" };
+    let pattern = regex::Regex::new(&format!(r"(libraries/net/{}/{}/)[^/\\]+(/win_args\.txt)", group, loader))
+        .map_err(|e| e.to_string())?;
+    let mut updated = pattern
+        .replace_all(&content, |caps: &regex::Captures| {
+            format!("{}{}{}", &caps[1], new_version, &caps[2])
+        })
+        .into_owned();
+    
+    let forge_jar = std::fs::read_dir(staging)
+        .map_err(|e| format!("Erreur lecture staging: {}", e))?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .find(|name| loader == "forge" && name.starts_with("forge-") && name.ends_with(".jar") && !name.contains("installer"));
+    if let Some(new_jar) = forge_jar {
+        let jar_pattern = regex::Regex::new(r#"(-jar\s+"?)forge-[^"\s]+\.jar"#).map_err(|e| e.to_string())?;
+        updated = jar_pattern
+            .replace_all(&updated, |caps: &regex::Captures| format!("{}{}", &caps[1], new_jar))
+            .into_owned();
+    }
+    
+    if updated != content {
+        std::fs::write(staging.join("start.bat"), updated)
+            .map_err(|e| format!("Erreur écriture start.bat: {}", e))?;
+    }
+    
+    Ok(())
+}
+
+// Historique des mises à jour d'un serveur
+#[tauri::command]
+async fn get_update_history(server_path: String) -> Result<Vec<server_update::UpdateRecord>, String> {
+    Ok(server_update::history(std::path::Path::new(&server_path)))
+}
+
+// Créer un clone de test du serveur, y appliquer le changement proposé et le démarrer
+#[tauri::command]
+async fn create_staging_clone(server_name: String, server_path: String, server_type: String, options: staging::StagingOptions) -> Result<staging::StagingClone, String> {
+    // Copie cohérente : le serveur d'origine reste arrêté pendant la copie
+    let mut clone = {
+        let _reservation = reserve_server(&server_name, "de créer un clone de test")?;
+        staging::create_clone(&server_name, &server_path, &server_type, &options)?
+    };
+    
+    let prepared = prepare_staging_clone(&mut clone, &options).await;
+    if let Err(e) = prepared {
+        let _ = staging::discard(&clone.id);
+        return Err(e);
+    }
+    
+    staging::boot(&mut clone, options.boot_timeout_secs).await?;
+    Ok(clone)
+}
+
+async fn prepare_staging_clone(clone: &mut staging::StagingClone, options: &staging::StagingOptions) -> Result<(), String> {
+    if let Some(version) = &options.version {
+        let clone_dir = clone.dir();
+        let mut transaction = server_update::UpdateTransaction::begin(&clone_dir)?;
         
-        if !output.status.success() {
-            return Err(format!("Erreur installation: {}", String::from_utf8_lossy(&output.stderr)));
-        }
+        let staged = stage_server_update(&clone_dir, &transaction.staging(), version, &clone.server_type).await;
+        let installed_version = match staged.and_then(|v| transaction.swap().map(|_| v)) {
+            Ok(v) => v,
+            Err(e) => {
+                let _ = transaction.rollback();
+                return Err(e);
+            }
+        };
         
-        println!("Loader installé avec succès");
+        let installed = transaction.installed().to_vec();
+        transaction.finish()?;
+        staging::record_update(clone, &installed, &installed_version)?;
     }
     
-    // 5. Mettre à jour le fichier de configuration
-    update_server_version_config(&server_dir, &new_version)?;
+    staging::apply_mod_changes(clone, options)
+}
+
+// Lister les clones de test existants
+#[tauri::command]
+async fn list_staging_clones() -> Result<Vec<staging::StagingClone>, String> {
+    Ok(staging::list())
+}
+
+// Reporter le changement d'un clone de test sur le serveur d'origine
+#[tauri::command]
+async fn promote_staging_clone(id: String) -> Result<String, String> {
+    let clone = staging::load(&id)?;
+    let _reservation = reserve_server(&clone.server_name, "d'appliquer le changement")?;
+    
+    // Sauvegarde du serveur d'origine avant l'échange
+    create_backup_sync(&clone.server_name, &clone.source_path)
+        .map_err(|e| format!("Erreur création backup: {}", e))?;
     
-    Ok(format!("Serveur {} mis à jour vers {} avec succès!", server_name, new_version))
+    staging::promote(&id)
+}
+
+// Supprimer un clone de test
+#[tauri::command]
+async fn discard_staging_clone(id: String) -> Result<(), String> {
+    staging::discard(&id)
 }
 
 // Télécharger un JAR Vanilla
 async fn download_vanilla_jar(version: &str, server_dir: &std::path::PathBuf) -> Result<(), String> {
-    use std::fs;
-    
-    let manifest_url = "https://launchermeta.mojang.com/mc/game/version_manifest_v2.json";
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(300))
-        .build()
-        .map_err(|e| e.to_string())?;
-    
-    let manifest: serde_json::Value = client
-        .get(manifest_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur manifest: {}", e))?
-        .json()
-        .await
-        .map_err(|e| format!("Erreur JSON: {}", e))?;
-    
-    let versions = manifest["versions"].as_array()
-        .ok_or("Versions non trouvées")?;
-    
-    let version_info = versions.iter()
-        .find(|v| v["id"].as_str() == Some(version))
-        .ok_or(format!("Version {} non trouvée", version))?;
-    
-    let version_url = version_info["url"].as_str()
-        .ok_or("URL version non trouvée")?;
-    
-    let version_data: serde_json::Value = client
-        .get(version_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur données version: {}", e))?
-        .json()
-        .await
-        .map_err(|e| format!("Erreur JSON version: {}", e))?;
-    
-    let server_url = version_data["downloads"]["server"]["url"].as_str()
+    let version_details = version_catalog::version_details(version).await?;
+    let server_url = version_details.server_url.as_deref()
         .ok_or("URL serveur non trouvée")?;
     
-    let jar_bytes = client
-        .get(server_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur téléchargement: {}", e))?
-        .bytes()
-        .await
-        .map_err(|e| format!("Erreur lecture bytes: {}", e))?;
-    
     let jar_path = server_dir.join("server.jar");
-    fs::write(&jar_path, &jar_bytes)
-        .map_err(|e| format!("Erreur écriture JAR: {}", e))?;
+    downloader::download(
+        &downloader::DownloadRequest::new(server_url, &jar_path)
+            .with_checksum(version_details.server_checksum())
+            .with_label(&format!("Minecraft {}", version))
+            .cached()
+    ).await?;
     
     println!("JAR Vanilla téléchargé: {}", jar_path.display());
     Ok(())
 }
 
+// Télécharger un JAR Paper/Folia/Purpur ("1.20.1" = dernier build, "1.20.1-196" = build précis)
+async fn download_paper_family_jar(project: paper_family::PaperProject, version: &str, jar_path: &std::path::Path) -> Result<String, String> {
+    let (minecraft, build) = updates::split_build(version);
+    let build = match build {
+        Some(build) => build,
+        None => paper_family::latest_build(project, &minecraft).await?,
+    };
+    
+    let (jar_url, checksum) = paper_family::build_download(project, &minecraft, build).await?;
+    downloader::download(
+        &downloader::DownloadRequest::new(&jar_url, jar_path)
+            .with_checksum(checksum)
+            .with_label(&format!("{} {} build {}", project.display_name(), minecraft, build))
+            .cached()
+    ).await?;
+    
+    println!("JAR {} téléchargé: {}", project.display_name(), jar_path.display());
+    Ok(format!("{}-{}", minecraft, build))
+}
+
+// Télécharger un JAR MohistMC ("1.20.1" = dernier build, "1.20.1-512" = build précis)
+async fn download_mohist_jar(version: &str, jar_path: &std::path::Path) -> Result<String, String> {
+    let (minecraft, build) = updates::split_build(version);
+    let build = match build {
+        Some(build) => build,
+        None => mohist::list_builds(&minecraft)
+            .await?
+            .first()
+            .map(|b| b.number)
+            .ok_or(format!("Aucun build MohistMC trouvé pour {}", minecraft))?,
+    };
+    
+    downloader::download(
+        &downloader::DownloadRequest::new(&mohist::download_url(&minecraft, build), jar_path)
+            .with_label(&format!("MohistMC {} build {}", minecraft, build))
+            .cached()
+    ).await?;
+    
+    println!("JAR MohistMC téléchargé: {}", jar_path.display());
+    Ok(format!("{}-{}", minecraft, build))
+}
+
 // Télécharger un installeur Forge
 async fn download_forge_installer(version: &str, server_dir: &std::path::PathBuf) -> Result<(), String> {
-    use std::fs;
-    
     // Format: 1.20.1-47.2.0
-    let installer_url = format!(
-        "https://maven.minecraftforge.net/net/minecraftforge/forge/{}/forge-{}-installer.jar",
-        version, version
+    let installer_url = endpoints::url(
+        endpoints::Upstream::ForgeMaven,
+        &format!("/net/minecraftforge/forge/{}/forge-{}-installer.jar", version, version),
     );
     
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(300))
-        .build()
-        .map_err(|e| e.to_string())?;
-    
-    let jar_bytes = client
-        .get(&installer_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur téléchargement Forge: {}", e))?
-        .bytes()
-        .await
-        .map_err(|e| format!("Erreur lecture bytes: {}", e))?;
-    
     let jar_path = server_dir.join("forge-installer.jar");
-    fs::write(&jar_path, &jar_bytes)
-        .map_err(|e| format!("Erreur écriture installeur: {}", e))?;
+    let checksum = downloader::fetch_maven_checksum(&installer_url).await;
+    downloader::download(
+        &downloader::DownloadRequest::new(&installer_url, &jar_path)
+            .with_checksum(checksum)
+            .with_label(&format!("Forge {}", version))
+            .cached()
+    ).await?;
     
     println!("Installeur Forge téléchargé: {}", jar_path.display());
     Ok(())
@@ -3383,30 +3799,19 @@ async fn download_forge_installer(version: &str, server_dir: &std::path::PathBuf
 
 // Télécharger un installeur NeoForge
 async fn download_neoforge_installer(version: &str, server_dir: &std::path::PathBuf) -> Result<(), String> {
-    use std::fs;
-    
-    let installer_url = format!(
-        "https://maven.neoforged.net/releases/net/neoforged/neoforge/{}/neoforge-{}-installer.jar",
-        version, version
+    let installer_url = endpoints::url(
+        endpoints::Upstream::NeoForgeMaven,
+        &format!("/releases/net/neoforged/{a}/{v}/{a}-{v}-installer.jar", a = neoforge_artifact(version), v = version),
     );
     
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(300))
-        .build()
-        .map_err(|e| e.to_string())?;
-    
-    let jar_bytes = client
-        .get(&installer_url)
-        .send()
-        .await
-        .map_err(|e| format!("Erreur téléchargement NeoForge: {}", e))?
-        .bytes()
-        .await
-        .map_err(|e| format!("Erreur lecture bytes: {}", e))?;
-    
     let jar_path = server_dir.join("neoforge-installer.jar");
-    fs::write(&jar_path, &jar_bytes)
-        .map_err(|e| format!("Erreur écriture installeur: {}", e))?;
+    let checksum = downloader::fetch_maven_checksum(&installer_url).await;
+    downloader::download(
+        &downloader::DownloadRequest::new(&installer_url, &jar_path)
+            .with_checksum(checksum)
+            .with_label(&format!("NeoForge {}", version))
+            .cached()
+    ).await?;
     
     println!("Installeur NeoForge téléchargé: {}", jar_path.display());
     Ok(())
@@ -3414,28 +3819,10 @@ async fn download_neoforge_installer(version: &str, server_dir: &std::path::Path
 
 // Mettre à jour la version dans les fichiers de config
 fn update_server_version_config(server_dir: &std::path::PathBuf, new_version: &str) -> Result<(), String> {
-    use std::fs;
-    
-    let config_file = server_dir.join("nether-config.json");
-    
-    let mut config: serde_json::Value = if config_file.exists() {
-        let content = fs::read_to_string(&config_file)
-            .map_err(|e| format!("Erreur lecture config: {}", e))?;
-        serde_json::from_str(&content).unwrap_or(serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
-    
-    config["version"] = serde_json::json!(new_version);
-    config["last_updated"] = serde_json::json!(chrono::Utc::now().to_rfc3339());
-    
-    let config_str = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Erreur sérialisation: {}", e))?;
-    
-    fs::write(&config_file, config_str)
-        .map_err(|e| format!("Erreur écriture config: {}", e))?;
-    
-    Ok(())
+    server_config::update(server_dir, |config| {
+        config["version"] = serde_json::json!(new_version);
+        config["last_updated"] = serde_json::json!(chrono::Utc::now().to_rfc3339());
+    })
 }
 
 // ========== INTÉGRATION PLAYIT.GG ==========
@@ -3943,11 +4330,15 @@ struct ModpackInstallProgress {
 }
 
 // Télécharger et installer un modpack
+// `hashes` reprend l'objet publié par la source (ex: Modrinth {"sha1": ..., "sha512": ...})
 #[tauri::command]
 async fn install_modpack(
     server_name: String,
     modpack_name: String,
     download_url: String,
+    hashes: Option<HashMap<String, String>>,
+    accept_eula: Option<bool>,
+    eula_accepted_by: Option<String>,
 ) -> Result<String, String> {
     use std::env;
     use std::path::PathBuf;
@@ -3962,6 +4353,13 @@ async fn install_modpack(
         .join("Serveurs")
         .join(&server_name);
     
+    // Nouveau serveur (ou EULA jamais acceptée) : acceptation explicite requise avant le téléchargement
+    let eula_accepted_by = if eula::is_accepted(&server_path) {
+        None
+    } else {
+        Some(eula::require(accept_eula.unwrap_or(false), eula_accepted_by.as_deref())?)
+    };
+    
     fs::create_dir_all(&server_path)
         .map_err(|e| format!("Erreur création dossier serveur: {}", e))?;
     
@@ -3971,29 +4369,18 @@ async fn install_modpack(
     if !download_url.is_empty() {
         println!("Téléchargement du modpack depuis: {}", download_url);
         
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(600)) // 10 minutes
-            .build()
-            .map_err(|e| e.to_string())?;
-        
-        let response = client
-            .get(&download_url)
-            .send()
-            .await
-            .map_err(|e| format!("Erreur téléchargement: {}", e))?;
-        
-        let modpack_bytes = response
-            .bytes()
-            .await
-            .map_err(|e| format!("Erreur lecture bytes: {}", e))?;
-        
-        println!("Modpack téléchargé: {} octets", modpack_bytes.len());
-        
-        // Sauvegarder le fichier
+        let checksum = hashes.as_ref().and_then(downloader::Checksum::strongest);
         let modpack_file = server_path.join("modpack.zip");
-        fs::write(&modpack_file, &modpack_bytes)
-            .map_err(|e| format!("Erreur sauvegarde modpack: {}", e))?;
+        let mut request = downloader::DownloadRequest::new(&download_url, &modpack_file)
+            .with_checksum(checksum)
+            .with_label(&format!("Modpack {}", modpack_name));
+        // Sans empreinte, l'URL peut pointer vers un contenu qui change : pas de cache
+        if request.checksum.is_some() {
+            request = request.cached();
+        }
+        let download = downloader::download(&request).await?;
         
+        println!("Modpack téléchargé: {} octets", download.size);
         println!("Modpack sauvegardé: {}", modpack_file.display());
         
         // Extraire le modpack
@@ -4012,6 +4399,9 @@ async fn install_modpack(
     
     // Créer les fichiers de configuration de base
     create_modpack_config(&server_path, &modpack_name)?;
+    if let Some(accepted_by) = &eula_accepted_by {
+        eula::accept(&server_path, accepted_by)?;
+    }
     
     Ok(format!("Modpack {} installé avec succès dans {}", modpack_name, server_name))
 }
@@ -4057,16 +4447,6 @@ fn extract_modpack(zip_path: &std::path::PathBuf, output_path: &std::path::PathB
 // Créer les fichiers de configuration pour le modpack
 fn create_modpack_config(server_path: &std::path::PathBuf, modpack_name: &str) -> Result<(), String> {
     use std::fs;
-    use std::io::Write;
-    
-    // Créer eula.txt
-    let eula_path = server_path.join("eula.txt");
-    if !eula_path.exists() {
-        let mut eula_file = fs::File::create(&eula_path)
-            .map_err(|e| format!("Erreur création EULA: {}", e))?;
-        eula_file.write_all(b"eula=true\n")
-            .map_err(|e| format!("Erreur écriture EULA: {}", e))?;
-    }
     
     // Créer un fichier de configuration Nether Client
     let config = serde_json::json!({
@@ -4220,6 +4600,22 @@ fn detect_version_internal(path: &std::path::PathBuf) -> String {
                 }
             }
         }
+        // Purpur/Folia: purpur-1.20.1-2062.jar, folia-1.20.4-12.jar
+        else if jar_name_lower.starts_with("purpur-") || jar_name_lower.starts_with("folia-") {
+            if let Some((_, version_part)) = jar_name.split_once('-') {
+                let version_part = version_part.replace(".jar", "");
+                if let Some(dash_pos) = version_part.rfind('-') {
+                    let potential_version = &version_part[..dash_pos];
+                    if potential_version.matches('.').count() >= 1 {
+                        version = potential_version.to_string();
+                        break;
+                    }
+                } else if version_part.matches('.').count() >= 1 {
+                    version = version_part;
+                    break;
+                }
+            }
+        }
         // Spigot: spigot-1.20.1.jar, spigot.jar
         else if jar_name_lower.contains("spigot-") || jar_name_lower == "spigot.jar" {
             if jar_name_lower.contains("spigot-") {
@@ -4288,6 +4684,19 @@ fn detect_version_internal(path: &std::path::PathBuf) -> String {
         }
     }
     
+    // ========== MÉTHODE 1b: Détection depuis version_history.json (Paper, Folia, Purpur) ==========
+    if version.is_empty() {
+        if let Some(current) = paper_family::read_version_history(path) {
+            // Format: "git-Paper-196 (MC: 1.20.1)"
+            if let Some(mc_pos) = current.find("MC: ") {
+                let potential_version = current[mc_pos + 4..].trim_end_matches(')').trim();
+                if potential_version.matches('.').count() >= 1 {
+                    version = potential_version.to_string();
+                }
+            }
+        }
+    }
+    
     // ========== MÉTHODE 2: Détection depuis version.json ==========
     if version.is_empty() {
         let version_file = path.join("version.json");
@@ -4475,81 +4884,55 @@ async fn scan_servers_directory() -> Result<Vec<serde_json::Value>, String> {
         .join("NetherClient")
         .join("Serveurs");
     
-    if !servers_path.exists() {
-        return Ok(Vec::new());
-    }
-    
     let mut detected_servers = Vec::new();
     
-    for entry in fs::read_dir(&servers_path)
-        .map_err(|e| format!("Erreur lecture dossier serveurs: {}", e))? 
-    {
-        let entry = entry.map_err(|e| format!("Erreur entrée: {}", e))?;
-        let path = entry.path();
-        
+    // Dossiers de Serveurs/ puis serveurs adoptés sur place ailleurs sur le disque
+    let mut candidates: Vec<(String, PathBuf)> = Vec::new();
+    if servers_path.exists() {
+        for entry in fs::read_dir(&servers_path)
+            .map_err(|e| format!("Erreur lecture dossier serveurs: {}", e))? 
+        {
+            let entry = entry.map_err(|e| format!("Erreur entrée: {}", e))?;
+            candidates.push((entry.file_name().to_string_lossy().to_string(), entry.path()));
+        }
+    }
+    candidates.extend(
+        server_import::adopted()
+            .into_iter()
+            .map(|server| (server.name, PathBuf::from(server.path)))
+    );
+    
+    for (server_name, path) in candidates {
         if path.is_dir() {
-            let server_name = entry.file_name().to_string_lossy().to_string();
             let properties_file = path.join("server.properties");
             
             // Vérifier si c'est un serveur valide (a un server.properties)
             if properties_file.exists() {
                 // Lire les propriétés de base
                 let mut port = 25565;
-                let mut server_type = "vanilla".to_string();
                 
-                if let Ok(content) = fs::read_to_string(&properties_file) {
-                    for line in content.lines() {
-                        if line.starts_with("server-port=") {
-                            if let Some(port_str) = line.split('=').nth(1) {
-                                if let Ok(p) = port_str.trim().parse::<u16>() {
-                                    port = p;
-                                }
-                            }
-                        }
+                if let Ok(properties) = properties_file::PropertiesFile::load(&properties_file) {
+                    if let Some(p) = properties.get("server-port").and_then(|p| p.trim().parse::<u16>().ok()) {
+                        port = p;
                     }
                 }
                 
-                // Détecter le type de serveur
-                // Vérifier d'abord Paper/Spigot (détection par fichiers de configuration)
-                if path.join("paper.jar").exists() || path.join("spigot.jar").exists() || 
-                   path.join("bukkit.yml").exists() || path.join("spigot.yml").exists() ||
-                   (path.join("plugins").exists() && !path.join("mods").exists()) {
-                    server_type = "paper".to_string();
-                }
-                // Vérifier ensuite les mods
-                else if path.join("mods").exists() {
-                    let mut found_neoforge = false;
-                    let mut found_forge = false;
-                    let mut found_mohist = false;
-                    
-                    if let Ok(entries) = fs::read_dir(path.join("mods")) {
-                        for entry in entries.filter_map(|e| e.ok()) {
-                            let name = entry.file_name().to_string_lossy().to_lowercase();
-                            if name.contains("neoforge") {
-                                found_neoforge = true;
-                                break;
-                            } else if name.contains("mohist") {
-                                found_mohist = true;
-                                break;
-                            } else if name.contains("forge") {
-                                found_forge = true;
-                            }
-                        }
-                    }
-                    
-                    if found_neoforge {
-                        server_type = "neoforge".to_string();
-                    } else if found_mohist {
-                        server_type = "mohist".to_string();
-                    } else if found_forge {
-                        server_type = "forge".to_string();
-                    } else {
-                        server_type = "forge".to_string(); // Par défaut si mods présents
-                    }
+                // Détecter le type de serveur depuis libraries/ et les manifests des JAR
+                let fingerprint = server_import::fingerprint(&path);
+                let server_type = if fingerprint.loader != "unknown" {
+                    fingerprint.loader.clone()
                 }
+                // Serveurs Bukkit/Spigot sans JAR reconnu (détection par fichiers de configuration)
+                else if path.join("bukkit.yml").exists() || path.join("spigot.yml").exists() ||
+                   (path.join("plugins").exists() && !path.join("mods").exists()) {
+                    "paper".to_string()
+                } else {
+                    "vanilla".to_string()
+                };
                 
-                // Utiliser la fonction robuste de détection pour tous les serveurs
-                let detected_version = detect_version_internal(&path);
+                // Version embarquée dans le JAR/libraries, sinon détection robuste par fichiers
+                let detected_version = fingerprint.minecraft_version
+                    .unwrap_or_else(|| detect_version_internal(&path));
                 let final_version = if detected_version.is_empty() { "Unknown".to_string() } else { detected_version };
                 
                 detected_servers.push(serde_json::json!({
@@ -4557,7 +4940,8 @@ async fn scan_servers_directory() -> Result<Vec<serde_json::Value>, String> {
                     "path": path.to_string_lossy().to_string(),
                     "port": port,
                     "version": final_version,
-                    "type": server_type
+                    "type": server_type,
+                    "eula_accepted": eula::is_accepted(&path)
                 }));
             }
         }
@@ -5021,6 +5405,12 @@ fn main() {
     
     tauri::Builder::default()
         .setup(|app| {
+            // Relayer la progression des téléchargements vers l'interface
+            let handle = app.handle();
+            downloader::set_progress_listener(move |progress| {
+                let _ = handle.emit_all("download-progress", progress);
+            });
+            
             // Configuration spéciale pour Windows avec transparence
             #[cfg(target_os = "windows")]
             {
@@ -5085,14 +5475,34 @@ fn main() {
             create_neoforge_server,
             create_mohist_server,
             create_paper_server,
+            create_folia_server,
+            create_purpur_server,
             start_server,
+            preflight_server,
+            get_eula_status,
+            accept_server_eula,
             stop_server,
             get_server_status,
             update_server_properties,
+            get_server_properties_schema,
+            audit_server_properties,
+            get_server_config_settings,
+            update_server_config_settings,
+            list_config_presets,
+            preview_config_preset,
+            apply_config_preset,
+            list_mod_configs,
+            read_mod_config,
+            update_mod_config,
             check_java_version,
             detect_java_versions,
             get_recommended_java_version,
             select_best_java_version,
+            get_server_java_requirement,
+            get_java_pin,
+            set_java_pin,
+            list_java_pins,
+            repin_servers,
             delete_server_folder,
             get_server_logs,
             clear_server_logs,
@@ -5112,11 +5522,40 @@ fn main() {
             enable_auto_restart,
             get_available_port,
             download_java_runtime,
+            list_available_java_releases,
+            install_java_runtime,
+            list_java_runtimes,
+            remove_java_runtime,
             check_java_version,
             cleanup_server_logs,
             cleanup_app_cache,
+            get_artifact_cache_stats,
+            set_artifact_cache_limit,
+            get_endpoints,
+            get_endpoint_config,
+            set_endpoint_config,
             check_updates,
+            get_update_report,
+            get_update_history,
+            create_staging_clone,
+            list_staging_clones,
+            promote_staging_clone,
+            discard_staging_clone,
+            inspect_server_folder,
+            import_server_folder,
+            inspect_client_instance,
+            import_client_instance,
+            clone_server,
+            rename_server,
+            save_server_as_template,
+            list_templates,
+            delete_template,
+            export_template,
+            import_template,
+            create_server_from_template,
             get_optimized_java_flags,
+            get_jvm_profile,
+            set_jvm_profile,
             detect_crash,
             add_player_to_list,
             remove_player_from_list,
@@ -5125,7 +5564,11 @@ fn main() {
             update_server,
             download_minecraft_version,
             get_minecraft_versions,
+            get_version_catalog,
+            get_minecraft_version_details,
             get_paper_versions,
+            get_folia_versions,
+            get_purpur_versions,
             check_java_installation,
             open_folder,
             send_notification,
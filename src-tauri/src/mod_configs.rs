@@ -0,0 +1,373 @@
+// Configurations TOML des mods Forge/NeoForge : config/, <monde>/serverconfig/ et defaultconfigs/.
+// Arborescence avec les commentaires comme descriptions, édition en place et report dans les mondes existants
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::toml_file::{self, TomlEntry, TomlFile};
+
+const CONFIG_DIR: &str = "config";
+const DEFAULT_CONFIGS_DIR: &str = "defaultconfigs";
+const SERVER_CONFIG_DIR: &str = "serverconfig";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModConfigFile {
+    // Chemin relatif au dossier du serveur, séparé par des '/'
+    pub path: String,
+    // "config", "serverconfig" (propre à un monde) ou "defaultconfigs" (copié dans les nouveaux mondes)
+    pub scope: String,
+    pub world: Option<String>,
+    // Fichiers *-client.toml : sans effet sur un serveur
+    pub client_only: bool,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigSetting {
+    pub key: String,
+    pub path: String,
+    // "boolean", "integer", "float", "string", "array" ou "other" (non modifiable)
+    pub kind: String,
+    pub value: serde_json::Value,
+    pub description: String,
+    // Contraintes indiquées par Forge dans les commentaires ("Range: 1 ~ 64", "Allowed Values: ...")
+    pub range: Option<String>,
+    pub allowed_values: Vec<String>,
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigSection {
+    pub name: String,
+    pub path: String,
+    pub description: String,
+    pub settings: Vec<ConfigSetting>,
+    pub sections: Vec<ConfigSection>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigEdit {
+    pub path: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigUpdateResult {
+    pub modified: usize,
+    // Copies du fichier dans les mondes (serverconfig/) mises à jour
+    pub propagated: Vec<String>,
+}
+
+fn relative(server_dir: &Path, path: &Path) -> String {
+    path.strip_prefix(server_dir)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn collect_toml(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() && depth > 0 {
+            collect_toml(&path, depth - 1, out);
+        } else if path.extension().is_some_and(|ext| ext == "toml") {
+            out.push(path);
+        }
+    }
+}
+
+// Dossiers de monde qui ont un serverconfig/ (world, ou le level-name choisi)
+fn worlds(server_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(server_dir) else { return Vec::new() };
+    let mut worlds: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join(SERVER_CONFIG_DIR).is_dir())
+        .collect();
+    worlds.sort();
+    worlds
+}
+
+pub fn list(server_dir: &Path) -> Vec<ModConfigFile> {
+    let mut files = Vec::new();
+    let mut add = |paths: Vec<PathBuf>, scope: &str, world: Option<String>| {
+        for path in paths {
+            let name = path.file_stem().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+            files.push(ModConfigFile {
+                path: relative(server_dir, &path),
+                scope: scope.to_string(),
+                world: world.clone(),
+                client_only: name.ends_with("-client") || name.ends_with("_client"),
+                size: fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            });
+        }
+    };
+
+    let mut config = Vec::new();
+    collect_toml(&server_dir.join(CONFIG_DIR), 3, &mut config);
+    add(config, "config", None);
+
+    for world in worlds(server_dir) {
+        let mut server_configs = Vec::new();
+        collect_toml(&world.join(SERVER_CONFIG_DIR), 0, &mut server_configs);
+        let name = world.file_name().map(|n| n.to_string_lossy().to_string());
+        add(server_configs, "serverconfig", name);
+    }
+
+    let mut defaults = Vec::new();
+    collect_toml(&server_dir.join(DEFAULT_CONFIGS_DIR), 3, &mut defaults);
+    add(defaults, "defaultconfigs", None);
+
+    files.sort_by(|a, b| a.scope.cmp(&b.scope).then_with(|| a.path.to_lowercase().cmp(&b.path.to_lowercase())));
+    files
+}
+
+// Chemin relatif reçu de l'interface : uniquement un .toml sous config/, defaultconfigs/ ou <monde>/serverconfig/
+fn resolve(server_dir: &Path, file: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(file);
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| match c {
+            Component::Normal(part) => Ok(part.to_string_lossy().to_string()),
+            _ => Err(format!("Chemin de configuration invalide: {}", file)),
+        })
+        .collect::<Result<_, _>>()?;
+
+    let allowed = match parts.first().map(|p| p.as_str()) {
+        Some(CONFIG_DIR) | Some(DEFAULT_CONFIGS_DIR) => parts.len() >= 2,
+        _ => parts.len() == 3 && parts[1] == SERVER_CONFIG_DIR,
+    };
+    if !allowed || relative.extension().is_none_or(|ext| ext != "toml") {
+        return Err(format!("Chemin de configuration invalide: {}", file));
+    }
+
+    let path = server_dir.join(relative);
+    if !path.is_file() {
+        return Err(format!("Fichier de configuration introuvable: {}", file));
+    }
+    Ok(path)
+}
+
+// Séparer la description des lignes de contraintes ajoutées par Forge/NeoForge
+fn describe(comments: &[String]) -> (String, Option<String>, Vec<String>, Option<String>) {
+    let mut description = Vec::new();
+    let mut range = None;
+    let mut allowed = Vec::new();
+    let mut default = None;
+
+    for line in comments {
+        if let Some(value) = line.strip_prefix("Range:") {
+            range = Some(value.trim().to_string());
+        } else if let Some(values) = line.strip_prefix("Allowed Values:") {
+            allowed = values.split(',').map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect();
+        } else if let Some(value) = line.strip_prefix("Default:") {
+            default = Some(value.trim().to_string());
+        } else if !line.is_empty() {
+            description.push(line.clone());
+        }
+    }
+
+    (description.join("\n"), range, allowed, default)
+}
+
+fn kind(entry: &TomlEntry) -> &'static str {
+    match &entry.value {
+        Some(serde_json::Value::Bool(_)) => "boolean",
+        Some(serde_json::Value::Number(n)) if n.is_i64() => "integer",
+        Some(serde_json::Value::Number(_)) => "float",
+        Some(serde_json::Value::String(_)) => "string",
+        Some(serde_json::Value::Array(_)) => "array",
+        _ => "other",
+    }
+}
+
+fn section_mut<'a>(section: &'a mut ConfigSection, path: &[String]) -> &'a mut ConfigSection {
+    let Some((name, rest)) = path.split_first() else { return section };
+    let index = match section.sections.iter().position(|s| &s.name == name) {
+        Some(index) => index,
+        None => {
+            let path = if section.path.is_empty() { name.clone() } else { format!("{}.{}", section.path, name) };
+            section.sections.push(ConfigSection {
+                name: name.clone(),
+                path,
+                description: String::new(),
+                settings: Vec::new(),
+                sections: Vec::new(),
+            });
+            section.sections.len() - 1
+        }
+    };
+    section_mut(&mut section.sections[index], rest)
+}
+
+// Arborescence d'un fichier : tables imbriquées, valeurs et descriptions
+pub fn read(server_dir: &Path, file: &str) -> Result<ConfigSection, String> {
+    let path = resolve(server_dir, file)?;
+    let outline = TomlFile::load(&path)?.outline();
+
+    let mut root = ConfigSection {
+        name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        path: String::new(),
+        description: String::new(),
+        settings: Vec::new(),
+        sections: Vec::new(),
+    };
+
+    for table in &outline.tables {
+        let section = section_mut(&mut root, &table.path);
+        section.description = describe(&table.comments).0;
+    }
+
+    for entry in &outline.entries {
+        let (description, range, allowed_values, default) = describe(&entry.comments);
+        let section = section_mut(&mut root, &entry.table);
+        section.settings.push(ConfigSetting {
+            key: entry.key.join("."),
+            path: entry.path(),
+            kind: kind(entry).to_string(),
+            value: entry.value.clone().unwrap_or_else(|| serde_json::Value::String(entry.raw.clone())),
+            description,
+            range,
+            allowed_values,
+            default,
+        });
+    }
+
+    Ok(root)
+}
+
+// Bornes d'une contrainte Forge ("1 ~ 64", "> 0", "< 100"), incluses
+fn range_bounds(range: &str) -> (Option<f64>, Option<f64>) {
+    if let Some((min, max)) = range.split_once('~') {
+        return (min.trim().parse().ok(), max.trim().parse().ok());
+    }
+    if let Some(min) = range.strip_prefix('>') {
+        return (min.trim().parse().ok(), None);
+    }
+    if let Some(max) = range.strip_prefix('<') {
+        return (None, max.trim().parse().ok());
+    }
+    (None, None)
+}
+
+// Convertir une valeur de l'interface en texte TOML du même type que la valeur actuelle
+fn to_toml(entry: &TomlEntry, value: &serde_json::Value) -> Result<String, String> {
+    let path = entry.path();
+    let (_, range, allowed, _) = describe(&entry.comments);
+    let text = value.as_str().map(|s| s.trim());
+
+    let formatted = match kind(entry) {
+        "boolean" => match (value, text) {
+            (serde_json::Value::Bool(b), _) => Some(b.to_string()),
+            (_, Some("true")) | (_, Some("false")) => text.map(|t| t.to_string()),
+            _ => None,
+        },
+        "integer" => value.as_i64().or_else(|| text.and_then(|t| t.parse().ok())).map(|n| n.to_string()),
+        "float" => value
+            .as_f64()
+            .or_else(|| text.and_then(|t| t.parse().ok()))
+            .filter(|n| n.is_finite())
+            .and_then(|n| toml_file::format_value(&serde_json::json!(n), true)),
+        "string" => {
+            let text = match value {
+                serde_json::Value::String(s) => Some(s.clone()),
+                serde_json::Value::Number(n) => Some(n.to_string()),
+                serde_json::Value::Bool(b) => Some(b.to_string()),
+                _ => None,
+            };
+            if let Some(t) = &text {
+                if !allowed.is_empty() && !allowed.iter().any(|a| a.eq_ignore_ascii_case(t)) {
+                    return Err(format!("{}: valeur parmi {} attendue", path, allowed.join(", ")));
+                }
+            }
+            // Reprendre la casse exacte de la valeur autorisée
+            let text = text.map(|t| allowed.iter().find(|a| a.eq_ignore_ascii_case(&t)).cloned().unwrap_or(t));
+            text.and_then(|t| toml_file::format_value(&serde_json::Value::String(t), false))
+        }
+        "array" => match value {
+            serde_json::Value::Array(_) => toml_file::format_value(value, false),
+            _ => None,
+        },
+        _ => return Err(format!("{}: type de valeur non modifiable ici, éditez le fichier à la main", path)),
+    };
+    let formatted = formatted.ok_or_else(|| format!("{}: valeur de type {} attendue", path, kind(entry)))?;
+
+    if let (Some(range), Ok(number)) = (range, formatted.parse::<f64>()) {
+        let (min, max) = range_bounds(&range);
+        if min.is_some_and(|m| number < m) || max.is_some_and(|m| number > m) {
+            return Err(format!("{}: {} hors limites ({})", path, formatted, range));
+        }
+    }
+
+    Ok(formatted)
+}
+
+// Appliquer des valeurs TOML déjà validées à un fichier ; renvoie le nombre de valeurs modifiées
+fn write(path: &Path, values: &[(String, String)]) -> Result<usize, String> {
+    let mut document = TomlFile::load(path)?;
+    let mut modified = 0;
+    for (key, value) in values {
+        if document.get(key).is_some() && document.set(key, value)? {
+            modified += 1;
+        }
+    }
+    if modified > 0 {
+        document.save(path)?;
+    }
+    Ok(modified)
+}
+
+// Modifier un fichier ; pour un fichier de defaultconfigs/, reporter si demandé les mêmes valeurs
+// dans les serverconfig/ des mondes existants (Forge ne les copie qu'à la création d'un monde)
+pub fn update(server_dir: &Path, file: &str, edits: &[ConfigEdit], propagate: bool) -> Result<ConfigUpdateResult, String> {
+    let path = resolve(server_dir, file)?;
+    let outline = TomlFile::load(&path)?.outline();
+    let default_name = match file.strip_prefix(&format!("{}/", DEFAULT_CONFIGS_DIR)) {
+        Some(name) => Some(name),
+        None if propagate => return Err("Seuls les fichiers de defaultconfigs/ peuvent être reportés dans les mondes".to_string()),
+        None => None,
+    };
+
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    for edit in edits {
+        if edit.value.is_null() {
+            continue;
+        }
+        let Some(entry) = outline.entries.iter().find(|e| e.path() == edit.path) else {
+            errors.push(format!("Clé introuvable: {}", edit.path));
+            continue;
+        };
+        match to_toml(entry, &edit.value) {
+            Ok(text) => values.push((edit.path.clone(), text)),
+            Err(e) => errors.push(e),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(format!("Valeurs invalides: {}", errors.join("; ")));
+    }
+
+    let modified = write(&path, &values)?;
+    println!("✏️ {}: {} valeur(s) modifiée(s)", file, modified);
+
+    let mut propagated = Vec::new();
+    if let Some(name) = default_name.filter(|_| propagate) {
+        for world in worlds(server_dir) {
+            let target = world.join(SERVER_CONFIG_DIR).join(name);
+            // Absent : Forge le copiera depuis defaultconfigs/ au prochain démarrage
+            if !target.is_file() {
+                continue;
+            }
+            let changed = write(&target, &values)?;
+            if changed > 0 {
+                println!("↪️ {}: {} valeur(s) reportée(s)", relative(server_dir, &target), changed);
+                propagated.push(relative(server_dir, &target));
+            }
+        }
+    }
+
+    Ok(ConfigUpdateResult { modified, propagated })
+}
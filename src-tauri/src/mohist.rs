@@ -0,0 +1,97 @@
+// Module pour les builds MohistMC publiés sur l'API mohistmc.com
+use std::fs;
+use std::path::Path;
+
+use crate::endpoints::{self, Upstream};
+
+// Un build Mohist (numéro croissant, commit et version Forge embarquée)
+#[derive(Debug, Clone)]
+pub struct MohistBuild {
+    pub number: u32,
+    pub git_sha: Option<String>,
+    pub forge_version: Option<String>,
+    pub created_at: Option<String>,
+}
+
+fn api_url(path: &str) -> String {
+    endpoints::url(Upstream::Mohist, &format!("/api/v2/projects/mohist{}", path))
+}
+
+// Lister les builds d'une version Minecraft, du plus récent au plus ancien
+pub async fn list_builds(version: &str) -> Result<Vec<MohistBuild>, String> {
+    let data = endpoints::fetch_json(&api_url(&format!("/{}/builds", version))).await?;
+
+    let mut builds: Vec<MohistBuild> = data["builds"]
+        .as_array()
+        .ok_or("Format de réponse Mohist invalide: 'builds' n'est pas un tableau")?
+        .iter()
+        .filter_map(|b| {
+            Some(MohistBuild {
+                number: b["number"].as_u64()? as u32,
+                git_sha: b["gitSha"].as_str().map(|s| s.to_string()),
+                forge_version: b["forgeVersion"].as_str().map(|s| s.to_string()),
+                created_at: b["createdAt"]
+                    .as_i64()
+                    .and_then(chrono::DateTime::from_timestamp_millis)
+                    .map(|t| t.to_rfc3339()),
+            })
+        })
+        .collect();
+
+    builds.sort_by_key(|b| std::cmp::Reverse(b.number));
+    Ok(builds)
+}
+
+pub async fn list_versions() -> Result<Vec<String>, String> {
+    let data = endpoints::fetch_json(&api_url("")).await?;
+    Ok(data["versions"]
+        .as_array()
+        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+        .unwrap_or_default())
+}
+
+pub fn download_url(version: &str, build: u32) -> String {
+    api_url(&format!("/{}/builds/{}/download", version, build))
+}
+
+// Build installé : numéro dans le nom du JAR ("mohist-1.20.1-512.jar")
+// ou commit court des JAR intégrés ("mohist-1.12.2-5af9344.jar")
+pub fn installed_build(path: &Path, version: &str, builds: &[MohistBuild]) -> Option<u32> {
+    let entries = fs::read_dir(path).ok()?;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        let Some(suffix) = name
+            .strip_prefix(&format!("mohist-{}-", version))
+            .and_then(|rest| rest.strip_suffix(".jar"))
+        else {
+            continue;
+        };
+
+        if let Ok(number) = suffix.parse::<u32>() {
+            return Some(number);
+        }
+        if let Some(build) = builds
+            .iter()
+            .find(|b| b.git_sha.as_deref().map(|sha| sha.starts_with(suffix)).unwrap_or(false))
+        {
+            return Some(build.number);
+        }
+    }
+
+    None
+}
+
+// JAR actuellement utilisé par le serveur (start.bat y fait référence)
+pub fn server_jar(path: &Path, version: &str) -> String {
+    fs::read_dir(path)
+        .ok()
+        .and_then(|entries| {
+            entries.flatten().find_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let lower = name.to_lowercase();
+                (lower.starts_with("mohist") && lower.ends_with(".jar")).then_some(name)
+            })
+        })
+        .unwrap_or_else(|| format!("mohist-{}-server.jar", version))
+}
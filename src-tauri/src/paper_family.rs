@@ -0,0 +1,347 @@
+// Module pour les serveurs de la famille Paper (Paper, Folia, Purpur)
+use std::fs;
+use std::path::Path;
+
+use crate::downloader::Checksum;
+use crate::endpoints::{self, Upstream};
+
+// Projets supportés : Paper et Folia passent par l'API PaperMC, Purpur a sa propre API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaperProject {
+    Paper,
+    Folia,
+    Purpur,
+}
+
+impl PaperProject {
+    pub fn from_server_type(server_type: &str) -> Option<Self> {
+        match server_type.to_lowercase().as_str() {
+            "paper" => Some(PaperProject::Paper),
+            "folia" => Some(PaperProject::Folia),
+            "purpur" => Some(PaperProject::Purpur),
+            _ => None,
+        }
+    }
+
+    // Identifiant utilisé comme type de serveur et comme nom de projet dans les API
+    pub fn id(&self) -> &'static str {
+        match self {
+            PaperProject::Paper => "paper",
+            PaperProject::Folia => "folia",
+            PaperProject::Purpur => "purpur",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PaperProject::Paper => "Paper",
+            PaperProject::Folia => "Folia",
+            PaperProject::Purpur => "Purpur",
+        }
+    }
+
+    // Nom du JAR placé dans le dossier du serveur
+    pub fn jar_name(&self) -> &'static str {
+        match self {
+            PaperProject::Paper => "paper.jar",
+            PaperProject::Folia => "folia.jar",
+            PaperProject::Purpur => "purpur.jar",
+        }
+    }
+}
+
+// URL de l'API pour un chemin relatif au projet ("" pour la racine du projet)
+fn api_url(project: PaperProject, path: &str) -> String {
+    match project {
+        PaperProject::Purpur => endpoints::url(Upstream::Purpur, &format!("/v2/purpur{}", path)),
+        _ => endpoints::url(Upstream::PaperMc, &format!("/v2/projects/{}{}", project.id(), path)),
+    }
+}
+
+// Lister les versions Minecraft disponibles pour un projet (ordre de l'API, plus ancienne en premier)
+pub async fn list_versions(project: PaperProject) -> Result<Vec<String>, String> {
+    let data = endpoints::fetch_json(&api_url(project, "")).await?;
+    let versions = data["versions"]
+        .as_array()
+        .ok_or("Format de réponse invalide: 'versions' n'est pas un tableau")?
+        .iter()
+        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+        .collect::<Vec<String>>();
+
+    Ok(versions)
+}
+
+// Lister les numéros de build disponibles pour une version Minecraft
+pub async fn list_builds(project: PaperProject, version: &str) -> Result<Vec<u32>, String> {
+    let builds = match project {
+        PaperProject::Purpur => {
+            let data = endpoints::fetch_json(&api_url(project, &format!("/{}", version))).await?;
+            data["builds"]["all"]
+                .as_array()
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|b| b.as_str().and_then(|s| s.parse::<u32>().ok()))
+                        .collect::<Vec<u32>>()
+                })
+                .unwrap_or_default()
+        }
+        _ => {
+            let data = endpoints::fetch_json(&api_url(project, &format!("/versions/{}/builds", version))).await?;
+            data["builds"]
+                .as_array()
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|b| b["build"].as_u64().map(|n| n as u32))
+                        .collect::<Vec<u32>>()
+                })
+                .unwrap_or_default()
+        }
+    };
+
+    Ok(builds)
+}
+
+// Dernier build disponible pour une version
+pub async fn latest_build(project: PaperProject, version: &str) -> Result<u32, String> {
+    list_builds(project, version)
+        .await?
+        .into_iter()
+        .max()
+        .ok_or_else(|| format!("Aucun build {} trouvé pour {}", project.display_name(), version))
+}
+
+// URL de téléchargement du JAR pour une version et un build donnés
+pub fn download_url(project: PaperProject, version: &str, build: u32) -> String {
+    match project {
+        PaperProject::Purpur => api_url(project, &format!("/{}/{}/download", version, build)),
+        _ => api_url(
+            project,
+            &format!("/versions/{}/builds/{}/downloads/{}-{}-{}.jar", version, build, project.id(), version, build),
+        ),
+    }
+}
+
+// Informations de téléchargement d'un build : URL et empreinte publiée par l'API
+pub async fn build_download(project: PaperProject, version: &str, build: u32) -> Result<(String, Option<Checksum>), String> {
+    // Purpur ne publie qu'un MD5, aucune empreinte exploitable
+    if project == PaperProject::Purpur {
+        return Ok((download_url(project, version, build), None));
+    }
+
+    let build_path = format!("/versions/{}/builds/{}", version, build);
+    let data = endpoints::fetch_json(&api_url(project, &build_path)).await?;
+    let application = &data["downloads"]["application"];
+
+    let jar_url = match application["name"].as_str() {
+        Some(name) => api_url(project, &format!("{}/downloads/{}", build_path, name)),
+        None => download_url(project, version, build),
+    };
+    let checksum = application["sha256"]
+        .as_str()
+        .and_then(|sha256| Checksum::from_algorithm("sha256", sha256));
+
+    Ok((jar_url, checksum))
+}
+
+// Noms des entrées à la racine du dossier du serveur
+fn root_names(path: &Path) -> Vec<String> {
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| entry.file_name().to_string_lossy().to_string()).collect())
+        .unwrap_or_default()
+}
+
+// Détecter le projet à partir de version_history.json, des noms à la racine et d'un test d'existence
+// (dossier sur le disque ou index d'une archive)
+pub fn detect_project_from(version_history: Option<&str>, root_names: &[String], exists: impl Fn(&str) -> bool) -> Option<PaperProject> {
+    // 1. version_history.json écrit par Paper et ses forks ("git-Purpur-2062 (MC: 1.20.1)")
+    if let Some(project) = version_history.and_then(project_from_version_string) {
+        return Some(project);
+    }
+
+    // 2. Nom des JAR présents à la racine
+    for name in root_names.iter().map(|name| name.to_lowercase()) {
+        if !name.ends_with(".jar") {
+            continue;
+        }
+        if name.starts_with("purpur") {
+            return Some(PaperProject::Purpur);
+        } else if name.starts_with("folia") {
+            return Some(PaperProject::Folia);
+        }
+    }
+
+    // 3. Fichiers de configuration propres à Purpur
+    if exists("purpur.yml") {
+        return Some(PaperProject::Purpur);
+    }
+
+    // 4. Configuration Paper (dossier config/ moderne ou paper.yml historique)
+    if exists("paper.jar") || exists("paper.yml") || exists("config/paper-global.yml") {
+        return Some(PaperProject::Paper);
+    }
+
+    None
+}
+
+// Lire la version courante depuis version_history.json
+pub fn read_version_history(path: &Path) -> Option<String> {
+    parse_version_history(&fs::read_to_string(path.join("version_history.json")).ok()?)
+}
+
+// Version courante ("currentVersion") d'un contenu de version_history.json
+pub fn parse_version_history(content: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    json["currentVersion"].as_str().map(|s| s.to_string())
+}
+
+fn project_from_version_string(version: &str) -> Option<PaperProject> {
+    let lower = version.to_lowercase();
+    if lower.contains("purpur") {
+        Some(PaperProject::Purpur)
+    } else if lower.contains("folia") {
+        Some(PaperProject::Folia)
+    } else if lower.contains("paper") {
+        Some(PaperProject::Paper)
+    } else {
+        None
+    }
+}
+
+// Détails d'un build : canal et messages de commit
+#[derive(Debug, Clone)]
+pub struct BuildInfo {
+    pub build: u32,
+    // "default" ou "experimental" (Purpur n'a qu'un canal)
+    pub channel: String,
+    pub time: Option<String>,
+    pub changes: Vec<String>,
+}
+
+// Builds plus récents que `after` pour une version, du plus récent au plus ancien
+pub async fn builds_after(project: PaperProject, version: &str, after: u32, limit: usize) -> Result<Vec<BuildInfo>, String> {
+    let newer = |build: u32| build > after;
+
+    let mut builds = match project {
+        PaperProject::Purpur => {
+            // L'API Purpur ne liste que les numéros : on récupère le détail des plus récents
+            let mut numbers: Vec<u32> = list_builds(project, version).await?.into_iter().filter(|b| newer(*b)).collect();
+            numbers.sort_unstable_by(|a, b| b.cmp(a));
+            numbers.truncate(limit);
+
+            let mut builds = Vec::new();
+            for number in numbers {
+                // Un build illisible ou en échec est ignoré sans interrompre la vérification
+                let data = match endpoints::fetch_json(&api_url(project, &format!("/{}/{}", version, number))).await {
+                    Ok(data) => data,
+                    Err(e) => {
+                        println!("⚠️ Build Purpur {} #{} ignoré: {}", version, number, e);
+                        continue;
+                    }
+                };
+                if data["result"].as_str().map(|r| r != "SUCCESS").unwrap_or(false) {
+                    println!("⚠️ Build Purpur {} #{} ignoré: {}", version, number, data["result"].as_str().unwrap_or("?"));
+                    continue;
+                }
+                builds.push(BuildInfo {
+                    build: number,
+                    channel: "default".to_string(),
+                    time: data["timestamp"]
+                        .as_i64()
+                        .and_then(chrono::DateTime::from_timestamp_millis)
+                        .map(|t| t.to_rfc3339()),
+                    changes: data["commits"]
+                        .as_array()
+                        .map(|commits| {
+                            commits
+                                .iter()
+                                .filter_map(|c| c["description"].as_str().map(|d| d.trim().to_string()))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                });
+            }
+            builds
+        }
+        _ => {
+            let data = endpoints::fetch_json(&api_url(project, &format!("/versions/{}/builds", version))).await?;
+            data["builds"]
+                .as_array()
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|b| {
+                            let build = b["build"].as_u64()? as u32;
+                            Some(BuildInfo {
+                                build,
+                                channel: b["channel"].as_str().unwrap_or("default").to_lowercase(),
+                                time: b["time"].as_str().map(|t| t.to_string()),
+                                changes: b["changes"]
+                                    .as_array()
+                                    .map(|changes| {
+                                        changes
+                                            .iter()
+                                            .filter_map(|c| c["summary"].as_str().map(|m| m.trim().to_string()))
+                                            .collect()
+                                    })
+                                    .unwrap_or_default(),
+                            })
+                        })
+                        .filter(|b| newer(b.build))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        }
+    };
+
+    builds.sort_by_key(|b| std::cmp::Reverse(b.build));
+    builds.truncate(limit);
+    Ok(builds)
+}
+
+// Version Minecraft et build installés, lus depuis version_history.json ou le nom du JAR
+pub fn installed_build(path: &Path, project: PaperProject) -> Option<(String, u32)> {
+    installed_build_from(read_version_history(path).as_deref(), &root_names(path), project)
+}
+
+pub fn installed_build_from(version_history: Option<&str>, root_names: &[String], project: PaperProject) -> Option<(String, u32)> {
+    if let Some(current) = version_history {
+        // Ancien format : "git-Paper-196 (MC: 1.20.1)"
+        let legacy = regex::Regex::new(r"-(\d+) \(MC: ([^)]+)\)").ok()?;
+        if let Some(caps) = legacy.captures(current) {
+            if let Ok(build) = caps[1].parse::<u32>() {
+                return Some((caps[2].to_string(), build));
+            }
+        }
+        // Nouveau format : "1.21.4-15-main@abc1234 (2025-01-01T00:00:00Z)"
+        let modern = regex::Regex::new(r"^(\d+\.\d+(?:\.\d+)?)-(\d+)-").ok()?;
+        if let Some(caps) = modern.captures(current.trim()) {
+            if let Ok(build) = caps[2].parse::<u32>() {
+                return Some((caps[1].to_string(), build));
+            }
+        }
+    }
+
+    // JAR téléchargé manuellement : "paper-1.20.1-196.jar"
+    let pattern = regex::Regex::new(&format!(r"^{}-(\d+\.\d+(?:\.\d+)?)-(\d+)\.jar$", project.id())).ok()?;
+    root_names.iter().find_map(|name| {
+        let name = name.to_lowercase();
+        let caps = pattern.captures(&name)?;
+        Some((caps[1].to_string(), caps[2].parse::<u32>().ok()?))
+    })
+}
+
+// JAR actuellement utilisé par le serveur (conservé tel quel pour ne pas casser start.bat)
+pub fn server_jar(path: &Path, project: PaperProject) -> String {
+    server_jar_from(&root_names(path), project)
+}
+
+pub fn server_jar_from(root_names: &[String], project: PaperProject) -> String {
+    let prefix = project.id();
+    root_names
+        .iter()
+        .find(|name| {
+            let lower = name.to_lowercase();
+            lower.starts_with(prefix) && lower.ends_with(".jar")
+        })
+        .cloned()
+        .unwrap_or_else(|| project.jar_name().to_string())
+}
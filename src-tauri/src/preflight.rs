@@ -0,0 +1,302 @@
+// Vérifications avant démarrage : un lancement voué à l'échec est signalé avec sa cause
+// et une correction proposée, au lieu d'une console qui se ferme
+use serde::Serialize;
+use std::fs;
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, TcpListener};
+use std::path::{Path, PathBuf};
+
+use crate::eula;
+use crate::java_pin;
+use crate::java_requirement;
+use crate::jvm_profile;
+use crate::properties_file::PropertiesFile;
+use crate::server_clone;
+
+const DEFAULT_PORT: u16 = 25565;
+// Espace disque libre minimal (Mo) : en dessous le monde ne peut plus être sauvegardé
+const DISK_ERROR_MB: u64 = 512;
+const DISK_WARNING_MB: u64 = 2048;
+// Mémoire utilisée par la JVM en plus du tas (métaspace, threads, code compilé)
+const JVM_OVERHEAD_MB: u64 = 512;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PreflightIssue {
+    // "eula", "port", "java", "java_pin", "memory", "disk", "launch" ou "port_conflict"
+    pub check: String,
+    // "error" (le serveur ne démarrera pas) ou "warning"
+    pub severity: String,
+    pub message: String,
+    pub fix: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PreflightReport {
+    // Aucune erreur bloquante
+    pub ready: bool,
+    pub issues: Vec<PreflightIssue>,
+}
+
+impl PreflightReport {
+    // Message unique regroupant les erreurs bloquantes
+    pub fn error_summary(&self) -> String {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == "error")
+            .map(|issue| format!("{} ({})", issue.message, issue.fix))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn issue(check: &str, severity: &str, message: String, fix: String) -> PreflightIssue {
+    PreflightIssue { check: check.to_string(), severity: severity.to_string(), message, fix }
+}
+
+// Jamais acceptée implicitement : le démarrage est refusé tant que l'utilisateur ne l'a pas fait
+fn check_eula(server_dir: &Path, issues: &mut Vec<PreflightIssue>) {
+    if !eula::is_accepted(server_dir) {
+        issues.push(issue(
+            "eula",
+            "error",
+            "L'EULA Minecraft n'a pas été acceptée (eula.txt)".to_string(),
+            format!("Lire et accepter l'EULA Minecraft ({})", eula::EULA_URL),
+        ));
+    }
+}
+
+fn check_port(server_dir: &Path, properties: &PropertiesFile, port: u16, issues: &mut Vec<PreflightIssue>) {
+    let server_ip = properties.get("server-ip").map(|ip| ip.trim().to_string()).unwrap_or_default();
+    let address = if server_ip.is_empty() {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    } else {
+        match server_ip.parse::<IpAddr>() {
+            Ok(address) => address,
+            Err(_) => {
+                issues.push(issue(
+                    "port",
+                    "error",
+                    format!("server-ip \"{}\" n'est pas une adresse IP valide", server_ip),
+                    "Vider server-ip pour écouter sur toutes les interfaces".to_string(),
+                ));
+                return;
+            }
+        }
+    };
+
+    match TcpListener::bind((address, port)) {
+        Ok(_) => {}
+        Err(e) if e.kind() == ErrorKind::AddrNotAvailable => issues.push(issue(
+            "port",
+            "error",
+            format!("server-ip {} n'est pas une adresse de cette machine", server_ip),
+            "Vider server-ip ou indiquer l'adresse d'une carte réseau de cette machine".to_string(),
+        )),
+        Err(_) => {
+            let suggestion = server_dir
+                .parent()
+                .and_then(|root| server_clone::free_port(root).ok())
+                .map(|free| format!("Arrêter le programme qui l'utilise ou passer au port {}", free))
+                .unwrap_or_else(|| "Arrêter le programme qui l'utilise ou changer server-port".to_string());
+            issues.push(issue("port", "error", format!("Le port {} est déjà utilisé", port), suggestion));
+        }
+    }
+}
+
+// Autres serveurs configurés sur le même port (impossible de les lancer en même temps)
+fn check_port_conflicts(server_dir: &Path, port: u16, issues: &mut Vec<PreflightIssue>) {
+    let Some(root) = server_dir.parent() else { return };
+    let Ok(entries) = fs::read_dir(root) else { return };
+    let own = fs::canonicalize(server_dir).unwrap_or_else(|_| server_dir.to_path_buf());
+
+    let mut others: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| fs::canonicalize(path).unwrap_or_else(|_| path.clone()) != own)
+        .filter(|path| {
+            PropertiesFile::load(&path.join("server.properties"))
+                .ok()
+                .and_then(|properties| properties.get("server-port")?.trim().parse::<u16>().ok())
+                == Some(port)
+        })
+        .filter_map(|path| Some(path.file_name()?.to_string_lossy().to_string()))
+        .collect();
+    others.sort();
+
+    if !others.is_empty() {
+        let fix = server_clone::free_port(root)
+            .map(|free| format!("Attribuer un autre port à l'un des serveurs (port libre: {})", free))
+            .unwrap_or_else(|_| "Attribuer un autre port à l'un des serveurs".to_string());
+        issues.push(issue(
+            "port_conflict",
+            "warning",
+            format!("Le port {} est aussi attribué à: {}", port, others.join(", ")),
+            fix,
+        ));
+    }
+}
+
+// Runtime épinglé disparu (déplacé, désinstallé) : le serveur démarre avec un remplacement s'il y en a un
+async fn check_java_pin(server_dir: &Path, issues: &mut Vec<PreflightIssue>) {
+    let status = java_pin::status(server_dir).await;
+    let Some(warning) = status.warning else { return };
+    issues.push(issue(
+        "java_pin",
+        if status.fallback.is_some() { "warning" } else { "error" },
+        warning,
+        "Épingler ce serveur sur un runtime Java installé".to_string(),
+    ));
+}
+
+async fn check_java(server_dir: &Path, java_major: Option<u32>, issues: &mut Vec<PreflightIssue>) {
+    let Some(java) = jvm_profile::launch_java(server_dir) else { return };
+    let java_path = PathBuf::from(&java);
+    if java_path.is_absolute() && !java_path.exists() {
+        issues.push(issue(
+            "java",
+            "error",
+            format!("Java introuvable: {}", java),
+            "Installer Java ou choisir une autre installation pour ce serveur".to_string(),
+        ));
+        return;
+    }
+
+    let Some(major) = java_major else {
+        issues.push(issue(
+            "java",
+            "warning",
+            format!("Impossible de déterminer la version de {}", java),
+            "Vérifier que cette installation Java fonctionne".to_string(),
+        ));
+        return;
+    };
+
+    // Minimum et maximum : un Java trop récent casse les anciens Forge autant qu'un Java trop ancien
+    let Some(requirement) = java_requirement::for_server(server_dir).await else { return };
+    if let Err(message) = requirement.check(major) {
+        issues.push(issue(
+            "java",
+            "error",
+            message,
+            format!("Choisir une installation {} pour ce serveur (Java {} recommandé)", requirement.label(), requirement.recommended()),
+        ));
+    }
+}
+
+fn check_memory(server_dir: &Path, issues: &mut Vec<PreflightIssue>) {
+    let heap_mb = jvm_profile::load(server_dir)
+        .map(|profile| profile.heap_mb)
+        .or_else(|| jvm_profile::current_heap_mb(server_dir));
+    let Some(heap_mb) = heap_mb.map(u64::from) else { return };
+
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+    let total_mb = sys.total_memory() / 1024 / 1024;
+    let available_mb = sys.available_memory() / 1024 / 1024;
+
+    if heap_mb >= total_mb {
+        issues.push(issue(
+            "memory",
+            "error",
+            format!("{} Mo de mémoire demandés, la machine n'en a que {} Mo", heap_mb, total_mb),
+            format!("Réduire la mémoire du serveur sous {} Mo", total_mb.saturating_sub(JVM_OVERHEAD_MB * 2)),
+        ));
+    } else if heap_mb + JVM_OVERHEAD_MB > available_mb {
+        issues.push(issue(
+            "memory",
+            "warning",
+            format!("{} Mo de mémoire demandés pour {} Mo libres", heap_mb, available_mb),
+            "Fermer des applications ou arrêter un autre serveur, ou réduire la mémoire allouée".to_string(),
+        ));
+    }
+}
+
+// Sans le préfixe \\?\ ajouté par canonicalize sous Windows, pour comparer aux points de montage
+fn plain_path(path: &Path) -> PathBuf {
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let text = canonical.to_string_lossy();
+    PathBuf::from(text.strip_prefix(r"\\?\").unwrap_or(&text))
+}
+
+fn check_disk(server_dir: &Path, issues: &mut Vec<PreflightIssue>) {
+    let path = plain_path(server_dir);
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    // Disque dont le point de montage est le plus long préfixe du dossier serveur
+    let Some(disk) = disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+    else {
+        return;
+    };
+
+    let free_mb = disk.available_space() / 1024 / 1024;
+    let mount = disk.mount_point().display();
+    if free_mb < DISK_ERROR_MB {
+        issues.push(issue(
+            "disk",
+            "error",
+            format!("Seulement {} Mo libres sur {}", free_mb, mount),
+            "Libérer de l'espace disque (anciennes sauvegardes, logs)".to_string(),
+        ));
+    } else if free_mb < DISK_WARNING_MB {
+        issues.push(issue(
+            "disk",
+            "warning",
+            format!("Espace disque faible sur {} ({} Mo libres)", mount, free_mb),
+            "Libérer de l'espace disque (anciennes sauvegardes, logs)".to_string(),
+        ));
+    }
+}
+
+// start.bat et les fichiers qu'il lance (JAR serveur ou win_args.txt de Forge/NeoForge)
+fn check_launch_files(server_dir: &Path, issues: &mut Vec<PreflightIssue>) {
+    if !server_dir.join("start.bat").exists() {
+        issues.push(issue(
+            "launch",
+            "error",
+            "Script de démarrage start.bat introuvable".to_string(),
+            "Réimporter le dossier du serveur pour régénérer start.bat".to_string(),
+        ));
+        return;
+    }
+
+    for file in jvm_profile::launch_files(server_dir) {
+        if server_dir.join(&file).exists() {
+            continue;
+        }
+        let fix = if file.ends_with("_args.txt") {
+            "Réinstaller le loader (mise à jour vers la même version) pour régénérer ses bibliothèques"
+        } else {
+            "Retélécharger le JAR serveur (mise à jour vers la même version)"
+        };
+        issues.push(issue("launch", "error", format!("Fichier de lancement introuvable: {}", file), fix.to_string()));
+    }
+}
+
+// Toutes les vérifications ; java_major est la version du Java lancé par start.bat
+pub async fn run(server_dir: &Path, java_major: Option<u32>) -> PreflightReport {
+    let mut issues = Vec::new();
+
+    check_launch_files(server_dir, &mut issues);
+    check_eula(server_dir, &mut issues);
+
+    let properties = PropertiesFile::load_or_default(&server_dir.join("server.properties"));
+    let port = properties
+        .get("server-port")
+        .and_then(|port| port.trim().parse().ok())
+        .unwrap_or(DEFAULT_PORT);
+    check_port(server_dir, &properties, port, &mut issues);
+    check_port_conflicts(server_dir, port, &mut issues);
+
+    check_java_pin(server_dir, &mut issues).await;
+    check_java(server_dir, java_major, &mut issues).await;
+    check_memory(server_dir, &mut issues);
+    check_disk(server_dir, &mut issues);
+
+    PreflightReport {
+        ready: issues.iter().all(|issue| issue.severity != "error"),
+        issues,
+    }
+}
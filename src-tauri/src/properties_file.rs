@@ -0,0 +1,294 @@
+// Lecture/écriture sans perte des fichiers .properties Java (server.properties) :
+// commentaires, lignes vides, ordre, séparateurs et continuations sont conservés tels quels,
+// seules les lignes des clés modifiées sont réécrites
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+enum Line {
+    // Commentaire, ligne vide ou ligne non reconnue : réécrite à l'identique
+    Raw(String),
+    Entry {
+        // Texte d'origine (plusieurs lignes physiques en cas de continuation)
+        raw: String,
+        // Clé telle qu'écrite, séparateur et espaces compris ("motd=", "level-name : ")
+        prefix: String,
+        key: String,
+        value: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct PropertiesFile {
+    lines: Vec<Line>,
+    newline: &'static str,
+    trailing_newline: bool,
+    // Fichier lu en ISO-8859-1 : réécrit dans le même encodage
+    latin1: bool,
+}
+
+impl Default for PropertiesFile {
+    fn default() -> Self {
+        PropertiesFile {
+            lines: Vec::new(),
+            newline: "\n",
+            trailing_newline: true,
+            latin1: false,
+        }
+    }
+}
+
+// Nombre de barres obliques inverses en fin de ligne impair : la ligne continue
+fn continues(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+fn is_separator_space(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\x0c'
+}
+
+// Décoder les séquences d'échappement (\t, \n, \uXXXX, \: ...)
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    let mut pending_high: Option<u16> = None;
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('f') => out.push('\x0c'),
+            Some('u') => {
+                let hex: String = chars.clone().take(4).collect();
+                match u16::from_str_radix(&hex, 16) {
+                    Ok(unit) if hex.len() == 4 => {
+                        for _ in 0..4 {
+                            chars.next();
+                        }
+                        // Paires de substitution UTF-16
+                        if (0xD800..0xDC00).contains(&unit) {
+                            pending_high = Some(unit);
+                            continue;
+                        }
+                        if let Some(high) = pending_high.take() {
+                            if let Some(Ok(decoded)) = char::decode_utf16([high, unit]).next() {
+                                out.push(decoded);
+                                continue;
+                            }
+                        }
+                        out.extend(char::from_u32(unit as u32));
+                    }
+                    _ => out.push('u'),
+                }
+            }
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+
+    out
+}
+
+fn escape(text: &str, is_key: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (i, c) in text.chars().enumerate() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\x0c' => out.push_str("\\f"),
+            // Espace : toujours dans une clé, seulement en tête dans une valeur
+            ' ' if is_key || i == 0 => out.push_str("\\ "),
+            '=' | ':' if is_key => {
+                out.push('\\');
+                out.push(c);
+            }
+            '#' | '!' if i == 0 => {
+                out.push('\\');
+                out.push(c);
+            }
+            // Hors ASCII : \uXXXX, lisible quel que soit l'encodage utilisé par le serveur
+            c if !c.is_ascii() || c.is_ascii_control() => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    out.push_str(&format!("\\u{:04X}", unit));
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn escape_key(key: &str) -> String {
+    escape(key, true)
+}
+
+pub fn escape_value(value: &str) -> String {
+    escape(value, false)
+}
+
+// Découper une ligne logique en (préfixe brut, clé, valeur)
+fn split_entry(logical: &str) -> (String, String, String) {
+    let trimmed_start = logical.len() - logical.trim_start_matches(is_separator_space).len();
+    let body = &logical[trimmed_start..];
+
+    let mut key_end = body.len();
+    let mut escaped = false;
+    for (i, c) in body.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        if c == '\\' {
+            escaped = true;
+        } else if c == '=' || c == ':' || is_separator_space(c) {
+            key_end = i;
+            break;
+        }
+    }
+
+    let raw_key = &body[..key_end];
+    let mut rest = body[key_end..].trim_start_matches(is_separator_space);
+    if rest.starts_with('=') || rest.starts_with(':') {
+        rest = rest[1..].trim_start_matches(is_separator_space);
+    }
+    let prefix_len = trimmed_start + (body.len() - rest.len());
+
+    (logical[..prefix_len].to_string(), unescape(raw_key), unescape(rest))
+}
+
+impl PropertiesFile {
+    pub fn parse(content: &str) -> Self {
+        let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+        let trailing_newline = content.is_empty() || content.ends_with('\n');
+
+        let mut lines = Vec::new();
+        let mut physical = content.lines();
+
+        while let Some(first) = physical.next() {
+            let trimmed = first.trim_start_matches(is_separator_space);
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('!') {
+                lines.push(Line::Raw(first.to_string()));
+                continue;
+            }
+
+            // Regrouper les lignes de continuation
+            let mut raw = first.to_string();
+            let mut logical = first.to_string();
+            while continues(&logical) {
+                logical.pop();
+                let Some(next) = physical.next() else { break };
+                raw.push_str(newline);
+                raw.push_str(next);
+                logical.push_str(next.trim_start_matches(is_separator_space));
+            }
+
+            let (prefix, key, value) = split_entry(&logical);
+            // Le préfixe n'est réutilisable que si la clé tient sur la première ligne
+            let prefix = if first.starts_with(&prefix) { prefix } else { format!("{}=", escape_key(&key)) };
+            lines.push(Line::Entry { raw, prefix, key, value });
+        }
+
+        PropertiesFile { lines, newline, trailing_newline, latin1: false }
+    }
+
+    // Lire un fichier (UTF-8, sinon ISO-8859-1 comme java.util.Properties)
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let bytes = fs::read(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+        match String::from_utf8(bytes) {
+            Ok(text) => Ok(Self::parse(&text)),
+            Err(e) => {
+                let text: String = e.into_bytes().iter().map(|b| *b as char).collect();
+                Ok(PropertiesFile { latin1: true, ..Self::parse(&text) })
+            }
+        }
+    }
+
+    // Fichier existant, ou document vide s'il n'existe pas encore
+    pub fn load_or_default(path: &Path) -> Self {
+        Self::load(path).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = self.to_string();
+        // Les valeurs modifiées sont échappées en \uXXXX : seules les lignes d'origine contiennent de l'ISO-8859-1
+        let bytes = if self.latin1 {
+            text.chars().map(|c| u8::try_from(c).unwrap_or(b'?')).collect()
+        } else {
+            text.into_bytes()
+        };
+        fs::write(path, bytes).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        // La dernière occurrence l'emporte, comme pour java.util.Properties
+        self.lines.iter().rev().find_map(|line| match line {
+            Line::Entry { key: k, value, .. } if k == key => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    // Modifier une valeur ; la ligne n'est réécrite que si la valeur change. Renvoie true si modifié.
+    pub fn set(&mut self, key: &str, value: &str) -> bool {
+        let existing = self.lines.iter_mut().rev().find_map(|line| match line {
+            Line::Entry { key: k, .. } if k == key => Some(line),
+            _ => None,
+        });
+
+        match existing {
+            Some(Line::Entry { raw, prefix, value: current, .. }) => {
+                if current == value {
+                    return false;
+                }
+                *raw = format!("{}{}", prefix, escape_value(value));
+                *current = value.to_string();
+                true
+            }
+            _ => {
+                let prefix = format!("{}=", escape_key(key));
+                self.lines.push(Line::Entry {
+                    raw: format!("{}{}", prefix, escape_value(value)),
+                    prefix,
+                    key: key.to_string(),
+                    value: value.to_string(),
+                });
+                true
+            }
+        }
+    }
+
+    // Paires clé/valeur dans l'ordre du fichier
+    pub fn entries(&self) -> Vec<(String, String)> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                Line::Entry { key, value, .. } => Some((key.clone(), value.clone())),
+                Line::Raw(_) => None,
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for PropertiesFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text: Vec<&str> = self
+            .lines
+            .iter()
+            .map(|line| match line {
+                Line::Raw(raw) | Line::Entry { raw, .. } => raw.as_str(),
+            })
+            .collect();
+        write!(f, "{}", text.join(self.newline))?;
+        if self.trailing_newline && !text.is_empty() {
+            write!(f, "{}", self.newline)?;
+        }
+        Ok(())
+    }
+}
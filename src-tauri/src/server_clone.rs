@@ -0,0 +1,222 @@
+// Dupliquer et renommer un serveur en mettant à jour tout ce qui fait référence à son nom
+// (start.bat, nether-config.json, sauvegardes)
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::app_paths;
+use crate::automation;
+use crate::file_tree;
+use crate::properties_file::PropertiesFile;
+use crate::server_config;
+
+// Entrées non copiées lors d'une duplication
+const SKIPPED: [&str; 5] = ["logs", "crash-reports", "session.lock", ".nether-update", "nether-update-history.json"];
+// Caractères interdits dans un nom de dossier Windows
+const INVALID_CHARS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+// Format de l'horodatage des sauvegardes ("<serveur>_2024-05-01_12-30-00.zip")
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+const DEFAULT_PORT: u16 = 25565;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClonedServer {
+    pub name: String,
+    pub path: String,
+    pub port: u16,
+}
+
+// Vérifier un nom de serveur utilisé comme nom de dossier (ni vide, ni "..", ni séparateur)
+pub fn validate_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() || name == "." || name == ".." {
+        return Err("Le nom du serveur est requis".to_string());
+    }
+    if name.contains(INVALID_CHARS) {
+        return Err(format!("Le nom {} contient des caractères interdits", name));
+    }
+    Ok(name)
+}
+
+// Vérifier le nouveau nom et renvoyer le dossier cible (à côté du dossier d'origine)
+fn target_dir(server_path: &Path, new_name: &str) -> Result<PathBuf, String> {
+    let name = validate_name(new_name)?;
+    if !server_path.is_dir() {
+        return Err(format!("Dossier serveur introuvable: {}", server_path.display()));
+    }
+
+    let target = server_path
+        .parent()
+        .ok_or("Dossier parent du serveur introuvable")?
+        .join(name);
+    if target.exists() {
+        return Err(format!("Un serveur nommé {} existe déjà", name));
+    }
+    Ok(target)
+}
+
+// Remplacer le nom dans le titre et le message de démarrage de start.bat
+fn rewrite_start_script(server_dir: &Path, old_name: &str, new_name: &str) -> Result<(), String> {
+    let path = server_dir.join("start.bat");
+    let Ok(content) = fs::read_to_string(&path) else { return Ok(()) };
+
+    // "echo Demarrage du serveur <nom>..." ou "echo Demarrage du serveur Forge <nom>..."
+    let updated: String = content
+        .lines()
+        .map(|line| {
+            if line.starts_with("title Nether Client - ") {
+                line.replace(&format!("- {}", old_name), &format!("- {}", new_name))
+            } else if line.starts_with("echo Demarrage du serveur") {
+                line.replace(&format!(" {}...", old_name), &format!(" {}...", new_name))
+            } else {
+                line.to_string()
+            }
+        })
+        .map(|line| line + "\n")
+        .collect();
+
+    if updated != content {
+        fs::write(&path, updated).map_err(|e| format!("Erreur écriture start.bat: {}", e))?;
+    }
+    Ok(())
+}
+
+// Mettre à jour le nom enregistré dans nether-config.json
+fn rewrite_config(server_dir: &Path, new_name: &str) -> Result<(), String> {
+    if !server_dir.join(server_config::CONFIG_FILE).exists() {
+        return Ok(());
+    }
+    server_config::update(server_dir, |config| {
+        if config.get("name").is_some() {
+            config["name"] = serde_json::json!(new_name);
+        }
+    })
+}
+
+// Ports déjà attribués aux serveurs existants (qu'ils soient démarrés ou non)
+fn used_ports(servers_root: &Path) -> Vec<u16> {
+    let Ok(entries) = fs::read_dir(servers_root) else { return Vec::new() };
+    entries
+        .flatten()
+        .filter_map(|entry| PropertiesFile::load(&entry.path().join("server.properties")).ok())
+        .filter_map(|properties| properties.get("server-port")?.trim().parse().ok())
+        .collect()
+}
+
+pub fn free_port(servers_root: &Path) -> Result<u16, String> {
+    let used = used_ports(servers_root);
+    let mut port = DEFAULT_PORT;
+    loop {
+        port = automation::find_available_port(port)?;
+        if !used.contains(&port) {
+            return Ok(port);
+        }
+        port += 1;
+    }
+}
+
+// Écrire le nouveau port (et le port query qui le suit par défaut)
+fn set_port(server_dir: &Path, port: u16) -> Result<(), String> {
+    let path = server_dir.join("server.properties");
+    let mut properties = PropertiesFile::load_or_default(&path);
+
+    properties.set("server-port", &port.to_string());
+    if properties.get("query.port").is_some() {
+        properties.set("query.port", &port.to_string());
+    }
+
+    properties.save(&path)
+}
+
+// Dupliquer un serveur sous un nouveau nom, avec un port libre
+pub fn clone_server(server_name: &str, server_path: &str, new_name: &str) -> Result<ClonedServer, String> {
+    let source = Path::new(server_path);
+    let target = target_dir(source, new_name)?;
+    let new_name = new_name.trim();
+
+    println!("Duplication du serveur {} vers {}...", server_name, new_name);
+    fs::create_dir_all(&target).map_err(|e| format!("Erreur création dossier: {}", e))?;
+
+    let copied = (|| {
+        for entry in fs::read_dir(source).map_err(|e| format!("Erreur lecture dossier serveur: {}", e))?.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if SKIPPED.contains(&name.as_str()) {
+                continue;
+            }
+            file_tree::copy_tree(&entry.path(), &target.join(&name))?;
+        }
+
+        let servers_root = target.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        let port = free_port(&servers_root)?;
+        set_port(&target, port)?;
+        rewrite_start_script(&target, server_name, new_name)?;
+        rewrite_config(&target, new_name)?;
+        Ok::<u16, String>(port)
+    })();
+
+    match copied {
+        Ok(port) => {
+            println!("Serveur {} dupliqué (port {})", new_name, port);
+            Ok(ClonedServer {
+                name: new_name.to_string(),
+                path: target.to_string_lossy().to_string(),
+                port,
+            })
+        }
+        Err(e) => {
+            let _ = fs::remove_dir_all(&target);
+            Err(e)
+        }
+    }
+}
+
+// Renommer les sauvegardes "<ancien>_<horodatage>.zip" en "<nouveau>_<horodatage>.zip"
+fn rename_backups(old_name: &str, new_name: &str) -> Result<usize, String> {
+    let backups_dir = app_paths::app_dir()?.join("backups");
+    let Ok(entries) = fs::read_dir(&backups_dir) else { return Ok(0) };
+
+    let prefix = format!("{}_", old_name);
+    let mut renamed = 0;
+
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(stamp) = file_name
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(".zip"))
+        else {
+            continue;
+        };
+        // Ignorer les sauvegardes d'un autre serveur dont le nom commence pareil ("Survie_2")
+        if chrono::NaiveDateTime::parse_from_str(stamp, BACKUP_TIMESTAMP_FORMAT).is_err() {
+            continue;
+        }
+
+        fs::rename(entry.path(), backups_dir.join(format!("{}_{}.zip", new_name, stamp)))
+            .map_err(|e| format!("Erreur renommage de la sauvegarde {}: {}", file_name, e))?;
+        renamed += 1;
+    }
+
+    Ok(renamed)
+}
+
+// Renommer le dossier du serveur et mettre à jour ses références. Renvoie le nouveau chemin.
+pub fn rename_server(server_name: &str, server_path: &str, new_name: &str) -> Result<String, String> {
+    let source = Path::new(server_path);
+    let target = target_dir(source, new_name)?;
+    let new_name = new_name.trim();
+
+    fs::rename(source, &target)
+        .map_err(|e| format!("Erreur renommage du dossier (fichiers encore ouverts ?): {}", e))?;
+
+    let rewritten = rewrite_start_script(&target, server_name, new_name)
+        .and_then(|_| rewrite_config(&target, new_name));
+    if let Err(e) = rewritten {
+        // Revenir à l'ancien nom pour ne pas laisser un serveur à moitié renommé
+        let _ = fs::rename(&target, source);
+        return Err(e);
+    }
+
+    let backups = rename_backups(server_name, new_name)?;
+    println!("Serveur {} renommé en {} ({} sauvegardes associées)", server_name, new_name, backups);
+
+    Ok(target.to_string_lossy().to_string())
+}
@@ -0,0 +1,40 @@
+// nether-config.json : réglages Nether Client propres à un serveur (nom, version, EULA, profil JVM, Java...)
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+pub const CONFIG_FILE: &str = "nether-config.json";
+
+lazy_static::lazy_static! {
+    // Verrou commun aux écritures : plusieurs commandes modifient des clés différentes du même fichier
+    static ref CONFIG_LOCK: Arc<Mutex<()>> = Arc::new(Mutex::new(()));
+}
+
+// Modifier nether-config.json sous verrou (lecture, modification, écriture atomique).
+// Un fichier absent part d'un objet vide ; un fichier invalide n'est jamais écrasé.
+pub fn update<F>(server_dir: &Path, change: F) -> Result<(), String>
+where
+    F: FnOnce(&mut serde_json::Value),
+{
+    let _guard = CONFIG_LOCK.lock().unwrap();
+    let path = server_dir.join(CONFIG_FILE);
+
+    let mut config = match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str::<serde_json::Value>(&content)
+            .ok()
+            .filter(|value| value.is_object())
+            .ok_or_else(|| format!("{} invalide: {}", CONFIG_FILE, path.display()))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::json!({}),
+        Err(e) => return Err(format!("Erreur lecture {}: {}", CONFIG_FILE, e)),
+    };
+
+    change(&mut config);
+
+    let json = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Erreur sérialisation: {}", e))?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, json)
+        .map_err(|e| format!("Erreur écriture {}: {}", CONFIG_FILE, e))?;
+    fs::rename(&tmp_path, &path)
+        .map_err(|e| format!("Erreur écriture {}: {}", CONFIG_FILE, e))
+}
@@ -0,0 +1,564 @@
+// Import de serveurs existants (dossier n'importe où sur le disque ou archive ZIP) :
+// identification du loader et des versions, adoption sur place ou copie, script de lancement
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{Cursor, Read, Seek};
+use std::path::{Path, PathBuf};
+
+use crate::app_paths;
+use crate::file_tree;
+use crate::jvm_profile;
+use crate::paper_family;
+use crate::properties_file::PropertiesFile;
+use crate::updates;
+
+const ADOPTED_FILE: &str = "adopted-servers.json";
+
+// Résultat de l'analyse d'un dossier serveur
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ServerFingerprint {
+    pub path: String,
+    // "vanilla", "forge", "neoforge", "paper", "folia", "purpur", "mohist", "fabric", "quilt" ou "unknown"
+    pub loader: String,
+    pub minecraft_version: Option<String>,
+    pub loader_version: Option<String>,
+    // JAR lancé avec -jar (absent pour Forge/NeoForge modernes lancés via win_args.txt)
+    pub server_jar: Option<String>,
+    // Fichier d'arguments Forge/NeoForge (libraries/.../win_args.txt)
+    pub args_file: Option<String>,
+    pub port: Option<u16>,
+    pub has_start_script: bool,
+    // Indices ayant mené à la détection
+    pub evidence: Vec<String>,
+}
+
+// Serveur adopté sur place (hors du dossier Serveurs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdoptedServer {
+    pub name: String,
+    pub path: String,
+    pub adopted_at: String,
+}
+
+// Sous-dossiers de `dir` (noms triés)
+fn subdirs(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort_by(|a, b| updates::compare_versions(a, b));
+    names
+}
+
+trait ReadSeek: Read + Seek {}
+impl<T: Read + Seek> ReadSeek for T {}
+
+type Jar = zip::ZipArchive<Box<dyn ReadSeek>>;
+
+// Contenu d'un serveur à analyser (chemins relatifs séparés par "/") : dossier sur le disque,
+// ou index d'une archive ZIP lu sans l'extraire
+trait ServerFiles {
+    fn exists(&self, relative: &str) -> bool;
+    fn read(&self, relative: &str) -> Option<Vec<u8>>;
+    // Entrées directes d'un dossier ("" pour la racine) : (sous-dossiers, fichiers)
+    fn entries(&self, relative: &str) -> (Vec<String>, Vec<String>);
+
+    fn read_string(&self, relative: &str) -> Option<String> {
+        self.read(relative).map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+    }
+
+    // Un JAR d'une archive est lu en mémoire : ses entrées compressées ne permettent pas d'accès direct
+    fn jar(&self, relative: &str) -> Option<Jar> {
+        let reader: Box<dyn ReadSeek> = Box::new(Cursor::new(self.read(relative)?));
+        zip::ZipArchive::new(reader).ok()
+    }
+}
+
+struct DirFiles<'a>(&'a Path);
+
+impl ServerFiles for DirFiles<'_> {
+    fn exists(&self, relative: &str) -> bool {
+        self.0.join(relative).exists()
+    }
+
+    fn read(&self, relative: &str) -> Option<Vec<u8>> {
+        fs::read(self.0.join(relative)).ok()
+    }
+
+    fn entries(&self, relative: &str) -> (Vec<String>, Vec<String>) {
+        let (mut dirs, mut files) = (Vec::new(), Vec::new());
+        for entry in fs::read_dir(self.0.join(relative)).into_iter().flatten().flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() { dirs.push(name) } else { files.push(name) }
+        }
+        (dirs, files)
+    }
+
+    fn jar(&self, relative: &str) -> Option<Jar> {
+        let reader: Box<dyn ReadSeek> = Box::new(fs::File::open(self.0.join(relative)).ok()?);
+        zip::ZipArchive::new(reader).ok()
+    }
+}
+
+struct ArchiveFiles {
+    archive: RefCell<zip::ZipArchive<fs::File>>,
+    // Fichier (chemin relatif, dossier racine unique retiré) -> index dans l'archive
+    files: BTreeMap<String, usize>,
+    dirs: BTreeSet<String>,
+}
+
+impl ArchiveFiles {
+    fn open(archive_path: &Path) -> Result<Self, String> {
+        let file = fs::File::open(archive_path)
+            .map_err(|e| format!("Erreur ouverture archive: {}", e))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| format!("Erreur lecture archive: {}", e))?;
+
+        let names = archive_names(&mut archive);
+        let root = single_root(&names);
+        let mut files = BTreeMap::new();
+        let mut dirs = BTreeSet::new();
+        for i in 0..archive.len() {
+            let Ok(entry) = archive.by_index(i) else { continue };
+            let Some(relative) = entry.enclosed_name().map(|p| p.to_path_buf()) else { continue };
+            let relative = match &root {
+                Some(root) => relative.strip_prefix(root).map(|p| p.to_path_buf()).unwrap_or(relative),
+                None => relative,
+            };
+            let relative: Vec<String> = relative.components().map(|c| c.as_os_str().to_string_lossy().to_string()).collect();
+            if relative.is_empty() {
+                continue;
+            }
+            // Les archives n'ont pas toujours d'entrée pour chaque dossier
+            let parents = if entry.is_dir() { relative.len() } else { relative.len() - 1 };
+            for depth in 1..=parents {
+                dirs.insert(relative[..depth].join("/"));
+            }
+            if !entry.is_dir() {
+                files.insert(relative.join("/"), i);
+            }
+        }
+
+        Ok(ArchiveFiles { archive: RefCell::new(archive), files, dirs })
+    }
+}
+
+impl ServerFiles for ArchiveFiles {
+    fn exists(&self, relative: &str) -> bool {
+        self.files.contains_key(relative) || self.dirs.contains(relative)
+    }
+
+    fn read(&self, relative: &str) -> Option<Vec<u8>> {
+        let index = *self.files.get(relative)?;
+        let mut archive = self.archive.borrow_mut();
+        let mut entry = archive.by_index(index).ok()?;
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes).ok()?;
+        Some(bytes)
+    }
+
+    fn entries(&self, relative: &str) -> (Vec<String>, Vec<String>) {
+        let prefix = if relative.is_empty() { String::new() } else { format!("{}/", relative) };
+        let children = |paths: &mut dyn Iterator<Item = &String>| -> Vec<String> {
+            paths
+                .filter_map(|path| path.strip_prefix(&prefix))
+                .filter(|name| !name.is_empty() && !name.contains('/'))
+                .map(|name| name.to_string())
+                .collect()
+        };
+        (children(&mut self.dirs.iter()), children(&mut self.files.keys()))
+    }
+}
+
+// Version la plus récente installée dans libraries/<groupe>/<artefact>/
+fn library_version(files: &dyn ServerFiles, group_path: &str) -> Option<String> {
+    let (mut versions, _) = files.entries(&format!("libraries/{}", group_path));
+    versions.sort_by(|a, b| updates::compare_versions(a, b));
+    versions.pop()
+}
+
+fn jar_entry(jar: &mut zip::ZipArchive<impl Read + Seek>, entry: &str) -> Option<String> {
+    let mut content = String::new();
+    jar.by_name(entry).ok()?.read_to_string(&mut content).ok()?;
+    Some(content)
+}
+
+// Lire un fichier d'un JAR (META-INF/MANIFEST.MF, version.json...)
+pub fn read_jar_entry(jar: &Path, entry: &str) -> Option<String> {
+    let file = fs::File::open(jar).ok()?;
+    jar_entry(&mut zip::ZipArchive::new(file).ok()?, entry)
+}
+
+pub fn manifest_attribute(manifest: &str, key: &str) -> Option<String> {
+    manifest.lines().find_map(|line| {
+        line.strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .map(|value| value.trim().to_string())
+    })
+}
+
+// Version Minecraft embarquée : version.json (vanilla, bundler) ou META-INF/versions.list (Paperclip)
+fn embedded_minecraft_version(jar: &mut Jar) -> Option<String> {
+    if let Some(json) = jar_entry(jar, "version.json") {
+        if let Ok(data) = serde_json::from_str::<serde_json::Value>(&json) {
+            if let Some(id) = data["id"].as_str().or(data["name"].as_str()) {
+                return Some(id.to_string());
+            }
+        }
+    }
+
+    // "<sha256>\t<id>\t<chemin>" ; l'identifiant se termine par la version Minecraft
+    jar_entry(jar, "META-INF/versions.list")?
+        .lines()
+        .filter_map(|line| line.split('\t').nth(1))
+        .find_map(|id| id.rsplit('-').find(|part| part.contains('.')).map(|v| v.to_string()))
+}
+
+// Loader déclaré par le Main-Class du manifest d'un JAR serveur
+fn loader_from_main_class(main_class: &str) -> Option<&'static str> {
+    match main_class {
+        "net.minecraft.server.Main" | "net.minecraft.bundler.Main" | "net.minecraft.server.MinecraftServer" => Some("vanilla"),
+        "io.papermc.paperclip.Main" | "io.papermc.paperclip.Paperclip" | "com.destroystokyo.paperclip.Paperclip" => Some("paper"),
+        "com.mohistmc.MohistMCStart" | "com.mohistmc.MohistMC" | "red.mohist.Mohist" => Some("mohist"),
+        "net.fabricmc.installer.ServerLauncher" | "net.fabricmc.loader.launch.server.FabricServerLauncher"
+        | "net.fabricmc.loader.impl.launch.server.FabricServerLauncher" => Some("fabric"),
+        "org.quiltmc.loader.impl.launch.server.QuiltServerLauncher" => Some("quilt"),
+        "net.minecraftforge.fml.relauncher.ServerLaunchWrapper" | "cpw.mods.bootstraplauncher.BootstrapLauncher" => Some("forge"),
+        _ => None,
+    }
+}
+
+// Analyser un dossier serveur : libraries/ d'abord, puis manifests des JAR à la racine
+pub fn fingerprint(server_dir: &Path) -> ServerFingerprint {
+    fingerprint_files(&DirFiles(server_dir), server_dir.to_string_lossy().to_string())
+}
+
+fn fingerprint_files(files: &dyn ServerFiles, path: String) -> ServerFingerprint {
+    let mut fp = ServerFingerprint {
+        path,
+        loader: "unknown".to_string(),
+        has_start_script: files.exists("start.bat") || files.exists("run.bat"),
+        port: files
+            .read_string("server.properties")
+            .and_then(|content| PropertiesFile::parse(&content).get("server-port")?.trim().parse().ok()),
+        ..Default::default()
+    };
+
+    // ---- libraries/ (installations Forge, NeoForge, Fabric, Quilt) ----
+    if let Some(version) = library_version(files, "net/neoforged/neoforge") {
+        fp.evidence.push(format!("libraries/net/neoforged/neoforge/{}", version));
+        fp.loader = "neoforge".to_string();
+        fp.minecraft_version = updates::neoforge_minecraft_version(&version);
+        fp.args_file = Some(format!("libraries/net/neoforged/neoforge/{}/win_args.txt", version));
+        fp.loader_version = Some(version);
+    } else if let Some(version) = library_version(files, "net/neoforged/forge") {
+        // NeoForge 1.20.1 garde le groupe et le format de version Forge : "1.20.1-47.1.84"
+        fp.evidence.push(format!("libraries/net/neoforged/forge/{}", version));
+        fp.loader = "neoforge".to_string();
+        fp.minecraft_version = updates::neoforge_minecraft_version(&version);
+        fp.args_file = Some(format!("libraries/net/neoforged/forge/{}/win_args.txt", version));
+        fp.loader_version = Some(version);
+    } else if let Some(version) = library_version(files, "net/minecraftforge/forge") {
+        // Dossier "1.20.1-47.2.0" : version Minecraft puis version Forge
+        fp.evidence.push(format!("libraries/net/minecraftforge/forge/{}", version));
+        fp.loader = "forge".to_string();
+        fp.minecraft_version = version.split('-').next().map(|v| v.to_string());
+        let args_file = format!("libraries/net/minecraftforge/forge/{}/win_args.txt", version);
+        if files.exists(&args_file) {
+            fp.args_file = Some(args_file);
+        }
+        fp.loader_version = Some(version);
+    } else if let Some(version) = library_version(files, "net/fabricmc/fabric-loader") {
+        fp.evidence.push(format!("libraries/net/fabricmc/fabric-loader/{}", version));
+        fp.loader = "fabric".to_string();
+        fp.loader_version = Some(version);
+    } else if let Some(version) = library_version(files, "org/quiltmc/quilt-loader") {
+        fp.evidence.push(format!("libraries/org/quiltmc/quilt-loader/{}", version));
+        fp.loader = "quilt".to_string();
+        fp.loader_version = Some(version);
+    }
+
+    // ---- Manifests des JAR à la racine ----
+    let (_, root_files) = files.entries("");
+    let mut jars: Vec<String> = root_files
+        .iter()
+        .filter(|name| name.to_lowercase().ends_with(".jar"))
+        .cloned()
+        .collect();
+    jars.sort();
+
+    let candidates: Vec<(String, &'static str, String)> = jars
+        .iter()
+        .filter_map(|jar_name| {
+            let manifest = jar_entry(&mut files.jar(jar_name)?, "META-INF/MANIFEST.MF")?;
+            let main_class = manifest_attribute(&manifest, "Main-Class")?;
+            let loader = loader_from_main_class(&main_class)?;
+            fp.evidence.push(format!("{}: Main-Class {}", jar_name, main_class));
+            Some((jar_name.clone(), loader, manifest))
+        })
+        .collect();
+
+    // Un JAR hybride (Mohist) l'emporte sur les bibliothèques Forge qu'il installe ;
+    // sinon le loader de libraries/ prime et le JAR vanilla n'est retenu qu'en dernier
+    let chosen = candidates
+        .iter()
+        .find(|(_, loader, _)| *loader == "mohist")
+        .or_else(|| candidates.iter().find(|(_, loader, _)| *loader == fp.loader))
+        .or_else(|| {
+            if fp.loader != "unknown" {
+                return None;
+            }
+            candidates
+                .iter()
+                .find(|(_, loader, _)| *loader != "vanilla")
+                .or_else(|| candidates.first())
+        });
+
+    if let Some((jar_name, loader, manifest)) = chosen {
+        if *loader == "mohist" {
+            fp.args_file = None;
+            fp.loader_version = None;
+        }
+        fp.loader = loader.to_string();
+        if fp.args_file.is_none() {
+            fp.server_jar = Some(jar_name.clone());
+        }
+        if fp.loader_version.is_none() && *loader != "vanilla" {
+            fp.loader_version = manifest_attribute(manifest, "Implementation-Version");
+        }
+    }
+    // Fabric/Quilt : le lanceur a besoin du JAR vanilla à côté, et la version Minecraft vient de lui
+    if fp.minecraft_version.is_none() {
+        fp.minecraft_version = jars.iter().find_map(|jar_name| embedded_minecraft_version(&mut files.jar(jar_name)?));
+    }
+
+    // ---- Famille Paper : projet et build exacts ----
+    if fp.loader == "paper" || fp.loader == "unknown" {
+        let version_history = files
+            .read_string("version_history.json")
+            .and_then(|content| paper_family::parse_version_history(&content));
+        let (root_dirs, _) = files.entries("");
+        let root_names: Vec<String> = root_dirs.into_iter().chain(root_files.iter().cloned()).collect();
+        if let Some(project) = paper_family::detect_project_from(version_history.as_deref(), &root_names, |relative| files.exists(relative)) {
+            fp.evidence.push(format!("famille Paper: {}", project.id()));
+            fp.loader = project.id().to_string();
+            if let Some((minecraft, build)) = paper_family::installed_build_from(version_history.as_deref(), &root_names, project) {
+                fp.minecraft_version = Some(minecraft);
+                fp.loader_version = Some(build.to_string());
+            }
+            fp.server_jar.get_or_insert_with(|| paper_family::server_jar_from(&root_names, project));
+        }
+    }
+
+    fp
+}
+
+// Script de lancement pour un serveur importé. Avec un fichier d'arguments Forge/NeoForge,
+// les arguments JVM sont lus dans user_jvm_args.txt (écrit par l'appelant), jamais répétés sur la ligne
+pub fn launch_script(name: &str, fp: &ServerFingerprint, java_path: &str, jvm_args: &[String]) -> Result<String, String> {
+    let launch = match (&fp.args_file, &fp.server_jar) {
+        (Some(args_file), _) => format!("@user_jvm_args.txt @{}", args_file),
+        (None, Some(jar)) => format!("{} -jar \"{}\"", jvm_profile::script_arguments(jvm_args), jar),
+        (None, None) => return Err("Aucun JAR serveur ni fichier d'arguments trouvé".to_string()),
+    };
+
+    Ok(format!(
+        "@echo off\n\
+         title Nether Client - {}\n\
+         echo Demarrage du serveur {}...\n\
+         echo Utilisation de Java: {}\n\
+         \"{}\" {} nogui\n\
+         pause\n",
+        name,
+        name,
+        java_path,
+        java_path,
+        launch
+    ))
+}
+
+// ========== SOURCES D'IMPORT ==========
+
+pub fn is_archive(source: &Path) -> bool {
+    source.is_file() && source.extension().map(|ext| ext.eq_ignore_ascii_case("zip")).unwrap_or(false)
+}
+
+fn archive_names(archive: &mut zip::ZipArchive<fs::File>) -> Vec<PathBuf> {
+    (0..archive.len())
+        .filter_map(|i| archive.by_index(i).ok().and_then(|e| e.enclosed_name().map(|p| p.to_path_buf())))
+        .collect()
+}
+
+// Dossier racine commun à toutes les entrées, s'il y en a un seul
+fn single_root(names: &[PathBuf]) -> Option<PathBuf> {
+    let first_components: Vec<_> = names.iter().filter_map(|p| p.components().next()).collect();
+    match first_components.first() {
+        Some(first) if first_components.iter().all(|c| c == first) && names.iter().any(|p| p.components().count() > 1) => {
+            Some(PathBuf::from(first.as_os_str()))
+        }
+        _ => None,
+    }
+}
+
+// Extraire une archive ; si tout est dans un seul dossier racine, son contenu est remonté
+pub fn extract_archive(archive_path: &Path, target: &Path) -> Result<(), String> {
+    let file = fs::File::open(archive_path)
+        .map_err(|e| format!("Erreur ouverture archive: {}", e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Erreur lecture archive: {}", e))?;
+
+    let single_root = single_root(&archive_names(&mut archive));
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)
+            .map_err(|e| format!("Erreur extraction: {}", e))?;
+        // Refuser les chemins qui sortent du dossier cible
+        let Some(relative) = entry.enclosed_name().map(|p| p.to_path_buf()) else { continue };
+        let relative = match &single_root {
+            Some(root) => relative.strip_prefix(root).map(|p| p.to_path_buf()).unwrap_or(relative),
+            None => relative,
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+
+        let out = target.join(&relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&out).map_err(|e| format!("Erreur création dossier: {}", e))?;
+            continue;
+        }
+        if let Some(parent) = out.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Erreur création dossier: {}", e))?;
+        }
+        let mut file = fs::File::create(&out)
+            .map_err(|e| format!("Erreur création fichier: {}", e))?;
+        std::io::copy(&mut entry, &mut file)
+            .map_err(|e| format!("Erreur extraction fichier: {}", e))?;
+    }
+
+    Ok(())
+}
+
+// Analyser une source d'import sans la modifier (une archive est lue depuis son index, sans extraction)
+pub fn inspect(source: &str) -> Result<ServerFingerprint, String> {
+    let source_path = Path::new(source);
+    if source_path.is_dir() {
+        return Ok(fingerprint(source_path));
+    }
+    if !is_archive(source_path) {
+        return Err(format!("Source introuvable ou non supportée: {}", source));
+    }
+
+    Ok(fingerprint_files(&ArchiveFiles::open(source_path)?, source.to_string()))
+}
+
+// Copier (ou extraire) la source dans Serveurs/<nom>
+pub fn copy_into_servers(source: &str, name: &str) -> Result<PathBuf, String> {
+    let target = app_paths::app_dir()?.join("Serveurs").join(name);
+    if target.exists() {
+        return Err(format!("Un serveur nommé {} existe déjà", name));
+    }
+
+    let source_path = Path::new(source);
+    let result = if is_archive(source_path) {
+        extract_archive(source_path, &target)
+    } else if source_path.is_dir() {
+        file_tree::copy_tree(source_path, &target)
+    } else {
+        Err(format!("Source introuvable ou non supportée: {}", source))
+    };
+
+    match result {
+        Ok(()) => Ok(target),
+        Err(e) => {
+            let _ = fs::remove_dir_all(&target);
+            Err(e)
+        }
+    }
+}
+
+// ========== SERVEURS ADOPTÉS SUR PLACE ==========
+
+pub fn adopted() -> Vec<AdoptedServer> {
+    app_paths::app_dir()
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(ADOPTED_FILE)).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+// Nom d'un serveur : nom d'adoption pour un serveur adopté sur place, sinon nom du dossier
+pub fn server_name(server_dir: &Path) -> String {
+    adopted()
+        .into_iter()
+        .find(|server| Path::new(&server.path) == server_dir)
+        .map(|server| server.name)
+        .or_else(|| server_dir.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_default()
+}
+
+// Dossiers de tous les serveurs connus : ceux de Serveurs/ puis les serveurs adoptés sur place
+pub fn server_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = app_paths::app_dir()
+        .map(|dir| dir.join("Serveurs"))
+        .map(|root| subdirs(&root).into_iter().map(|name| root.join(name)).collect())
+        .unwrap_or_default();
+    dirs.extend(adopted().into_iter().map(|server| PathBuf::from(server.path)));
+    dirs
+}
+
+fn save_adopted(servers: &[AdoptedServer]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(servers)
+        .map_err(|e| format!("Erreur sérialisation: {}", e))?;
+    fs::write(app_paths::app_dir()?.join(ADOPTED_FILE), json)
+        .map_err(|e| format!("Erreur écriture {}: {}", ADOPTED_FILE, e))
+}
+
+// Enregistrer un dossier existant comme serveur géré, sans le déplacer
+pub fn adopt_in_place(source: &str, name: &str) -> Result<PathBuf, String> {
+    let path = Path::new(source);
+    if !path.is_dir() {
+        return Err("Seul un dossier peut être adopté sur place (les archives doivent être copiées)".to_string());
+    }
+
+    let mut servers = adopted();
+    if servers.iter().any(|s| s.name == name) || app_paths::app_dir()?.join("Serveurs").join(name).exists() {
+        return Err(format!("Un serveur nommé {} existe déjà", name));
+    }
+    if servers.iter().any(|s| Path::new(&s.path) == path) {
+        return Err(format!("Le dossier {} est déjà adopté", source));
+    }
+
+    servers.push(AdoptedServer {
+        name: name.to_string(),
+        path: source.to_string(),
+        adopted_at: chrono::Utc::now().to_rfc3339(),
+    });
+    save_adopted(&servers)?;
+    Ok(path.to_path_buf())
+}
+
+// Suivre un renommage/déplacement d'un serveur adopté
+pub fn update_adopted(old_path: &str, new_name: &str, new_path: &str) -> Result<(), String> {
+    let mut servers = adopted();
+    let Some(server) = servers.iter_mut().find(|s| Path::new(&s.path) == Path::new(old_path)) else {
+        return Ok(());
+    };
+    server.name = new_name.to_string();
+    server.path = new_path.to_string();
+    save_adopted(&servers)
+}
+
+// Ne plus gérer un serveur adopté (le dossier n'est pas supprimé)
+pub fn forget_adopted(path: &str) -> Result<(), String> {
+    let mut servers = adopted();
+    servers.retain(|s| Path::new(&s.path) != Path::new(path));
+    save_adopted(&servers)
+}
@@ -0,0 +1,325 @@
+// Schéma typé de server.properties (vanilla) : type, bornes, valeurs possibles, valeur par défaut
+// et versions Minecraft d'apparition/suppression de chaque propriété
+use serde::Serialize;
+use std::cmp::Ordering;
+
+use crate::updates::compare_versions;
+
+#[derive(Debug, Clone, Copy)]
+pub enum PropertyKind {
+    Bool,
+    Int { min: i64, max: i64 },
+    Text,
+    Enum(&'static [&'static str]),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PropertyDef {
+    pub key: &'static str,
+    pub kind: PropertyKind,
+    pub default: &'static str,
+    // Première version Minecraft qui lit la propriété
+    pub introduced: &'static str,
+    // Première version qui ne la lit plus
+    pub removed: Option<&'static str>,
+    pub description: &'static str,
+}
+
+const PORT: PropertyKind = PropertyKind::Int { min: 1, max: 65535 };
+const POSITIVE: PropertyKind = PropertyKind::Int { min: 0, max: i32::MAX as i64 };
+// Avant 1.14, difficulty et gamemode ne sont lus que sous forme d'index (difficulty=2, gamemode=0)
+const NAMED_ENUMS: &str = "1.14";
+
+const fn def(key: &'static str, kind: PropertyKind, default: &'static str, introduced: &'static str, description: &'static str) -> PropertyDef {
+    PropertyDef { key, kind, default, introduced, removed: None, description }
+}
+
+const fn removed(def: PropertyDef, version: &'static str) -> PropertyDef {
+    PropertyDef { removed: Some(version), ..def }
+}
+
+// Propriétés vanilla, dans l'ordre d'écriture du fichier
+pub const PROPERTIES: &[PropertyDef] = &[
+    def("server-port", PORT, "25565", "1.0", "Port d'écoute du serveur"),
+    def("server-ip", PropertyKind::Text, "", "1.0", "Adresse IP d'écoute (vide = toutes)"),
+    def("motd", PropertyKind::Text, "A Minecraft Server", "1.0", "Message affiché dans la liste des serveurs"),
+    def("max-players", POSITIVE, "20", "1.0", "Nombre maximal de joueurs"),
+    def("online-mode", PropertyKind::Bool, "true", "1.0", "Vérifier les comptes auprès de Mojang"),
+    def("white-list", PropertyKind::Bool, "false", "1.0", "Activer la liste blanche"),
+    def("enforce-whitelist", PropertyKind::Bool, "false", "1.13", "Expulser les joueurs absents de la liste blanche au rechargement"),
+    def("difficulty", PropertyKind::Enum(&["peaceful", "easy", "normal", "hard"]), "easy", "1.0", "Difficulté"),
+    def("gamemode", PropertyKind::Enum(&["survival", "creative", "adventure", "spectator"]), "survival", "1.0", "Mode de jeu par défaut"),
+    def("force-gamemode", PropertyKind::Bool, "false", "1.5.2", "Imposer le mode de jeu par défaut à chaque connexion"),
+    def("hardcore", PropertyKind::Bool, "false", "1.0", "Mode hardcore"),
+    def("pvp", PropertyKind::Bool, "true", "1.0", "Combats entre joueurs"),
+    def("allow-flight", PropertyKind::Bool, "false", "1.0", "Autoriser le vol (mods, plugins)"),
+    def("allow-nether", PropertyKind::Bool, "true", "1.0", "Autoriser le Nether"),
+    def("spawn-monsters", PropertyKind::Bool, "true", "1.0", "Apparition des monstres"),
+    removed(def("spawn-animals", PropertyKind::Bool, "true", "1.0", "Apparition des animaux"), "1.21.2"),
+    removed(def("spawn-npcs", PropertyKind::Bool, "true", "1.0", "Apparition des villageois"), "1.21.2"),
+    def("spawn-protection", POSITIVE, "16", "1.0", "Rayon de protection du spawn (0 = désactivé)"),
+    def("view-distance", PropertyKind::Int { min: 2, max: 32 }, "10", "1.0", "Distance d'affichage en chunks"),
+    def("simulation-distance", PropertyKind::Int { min: 3, max: 32 }, "10", "1.18", "Distance de simulation en chunks"),
+    def("entity-broadcast-range-percentage", PropertyKind::Int { min: 10, max: 1000 }, "100", "1.16", "Distance d'envoi des entités (%)"),
+    def("player-idle-timeout", POSITIVE, "0", "1.6.1", "Minutes d'inactivité avant expulsion (0 = jamais)"),
+    def("pause-when-empty-seconds", PropertyKind::Int { min: i32::MIN as i64, max: i32::MAX as i64 }, "60", "1.21.2", "Secondes sans joueur avant la mise en pause"),
+    def("level-name", PropertyKind::Text, "world", "1.0", "Dossier du monde"),
+    def("level-seed", PropertyKind::Text, "", "1.0", "Graine du monde"),
+    def("level-type", PropertyKind::Text, "minecraft:normal", "1.0", "Type de monde (minecraft:normal, minecraft:flat...)"),
+    def("generator-settings", PropertyKind::Text, "{}", "1.4.2", "Réglages du générateur (JSON)"),
+    def("generate-structures", PropertyKind::Bool, "true", "1.0", "Générer les structures"),
+    def("max-world-size", PropertyKind::Int { min: 1, max: 29999984 }, "29999984", "1.8", "Rayon maximal de la bordure du monde"),
+    removed(def("max-build-height", PropertyKind::Int { min: 64, max: 256 }, "256", "1.0", "Hauteur maximale de construction"), "1.17"),
+    def("enable-command-block", PropertyKind::Bool, "false", "1.4.2", "Autoriser les blocs de commande"),
+    def("op-permission-level", PropertyKind::Int { min: 0, max: 4 }, "4", "1.7.2", "Niveau de permission des opérateurs"),
+    def("function-permission-level", PropertyKind::Int { min: 1, max: 4 }, "2", "1.14.4", "Niveau de permission des fonctions"),
+    def("broadcast-console-to-ops", PropertyKind::Bool, "true", "1.8", "Envoyer la sortie console aux opérateurs"),
+    def("broadcast-rcon-to-ops", PropertyKind::Bool, "true", "1.8", "Envoyer la sortie RCON aux opérateurs"),
+    removed(def("announce-player-achievements", PropertyKind::Bool, "true", "1.3.1", "Annoncer les succès"), "1.12"),
+    removed(def("snooper-enabled", PropertyKind::Bool, "true", "1.3.1", "Envoyer des statistiques à Mojang"), "1.18"),
+    def("enable-status", PropertyKind::Bool, "true", "1.16", "Répondre aux requêtes de statut (liste des serveurs)"),
+    def("hide-online-players", PropertyKind::Bool, "false", "1.18", "Masquer la liste des joueurs connectés"),
+    def("enable-query", PropertyKind::Bool, "false", "1.0", "Activer le protocole GameSpy4 (query)"),
+    def("query.port", PORT, "25565", "1.0", "Port du protocole query"),
+    def("enable-rcon", PropertyKind::Bool, "false", "1.0", "Activer la console à distance (RCON)"),
+    def("rcon.port", PORT, "25575", "1.0", "Port RCON"),
+    def("rcon.password", PropertyKind::Text, "", "1.0", "Mot de passe RCON"),
+    def("enable-jmx-monitoring", PropertyKind::Bool, "false", "1.16", "Exposer les métriques JMX"),
+    def("sync-chunk-writes", PropertyKind::Bool, "true", "1.16", "Écriture synchrone des chunks"),
+    def("region-file-compression", PropertyKind::Enum(&["deflate", "lz4", "none"]), "deflate", "1.20.5", "Compression des fichiers de région"),
+    def("network-compression-threshold", PropertyKind::Int { min: -1, max: i32::MAX as i64 }, "256", "1.8", "Taille minimale des paquets compressés (-1 = désactivé)"),
+    def("max-tick-time", PropertyKind::Int { min: -1, max: i64::MAX }, "60000", "1.8", "Durée maximale d'un tick avant arrêt du serveur (ms, -1 = désactivé)"),
+    def("max-chained-neighbor-updates", PropertyKind::Int { min: i32::MIN as i64, max: i32::MAX as i64 }, "1000000", "1.19", "Limite de mises à jour de blocs en chaîne"),
+    def("rate-limit", POSITIVE, "0", "1.16.2", "Paquets par seconde avant expulsion (0 = illimité)"),
+    def("use-native-transport", PropertyKind::Bool, "true", "1.8", "Utiliser epoll sous Linux"),
+    def("prevent-proxy-connections", PropertyKind::Bool, "false", "1.11", "Refuser les connexions via proxy/VPN"),
+    def("enforce-secure-profile", PropertyKind::Bool, "true", "1.19", "Exiger des clés de signature de chat Mojang"),
+    removed(def("previews-chat", PropertyKind::Bool, "false", "1.19", "Aperçu du chat"), "1.19.3"),
+    def("log-ips", PropertyKind::Bool, "true", "1.20.2", "Écrire les adresses IP des joueurs dans les logs"),
+    def("accepts-transfers", PropertyKind::Bool, "false", "1.20.5", "Accepter les transferts depuis d'autres serveurs"),
+    def("initial-enabled-packs", PropertyKind::Text, "vanilla", "1.19.3", "Packs de données activés à la création du monde"),
+    def("initial-disabled-packs", PropertyKind::Text, "", "1.19.3", "Packs de données désactivés à la création du monde"),
+    removed(def("texture-pack", PropertyKind::Text, "", "1.0", "Pack de textures"), "1.7.2"),
+    def("resource-pack", PropertyKind::Text, "", "1.7.2", "URL du pack de ressources"),
+    def("resource-pack-sha1", PropertyKind::Text, "", "1.8", "SHA-1 du pack de ressources"),
+    def("resource-pack-id", PropertyKind::Text, "", "1.20.3", "UUID du pack de ressources"),
+    def("resource-pack-prompt", PropertyKind::Text, "", "1.17", "Message de demande du pack de ressources"),
+    def("require-resource-pack", PropertyKind::Bool, "false", "1.17", "Expulser les joueurs qui refusent le pack"),
+    def("text-filtering-config", PropertyKind::Text, "", "1.17", "Configuration du filtrage du chat"),
+    def("bug-report-link", PropertyKind::Text, "", "1.21", "Lien de signalement de bugs"),
+];
+
+// Description d'une propriété pour l'éditeur de l'interface
+#[derive(Debug, Clone, Serialize)]
+pub struct PropertySchema {
+    pub key: String,
+    // "boolean", "integer", "string" ou "enum"
+    pub kind: String,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub values: Vec<String>,
+    pub default: String,
+    pub introduced: String,
+    pub removed: Option<String>,
+    pub description: String,
+    // Propriété lue par la version Minecraft demandée
+    pub available: bool,
+}
+
+// Problème détecté dans un server.properties existant
+#[derive(Debug, Clone, Serialize)]
+pub struct PropertyIssue {
+    pub key: String,
+    // "unknown", "obsolete", "too_new" ou "invalid"
+    pub kind: String,
+    pub message: String,
+}
+
+pub fn find(key: &str) -> Option<&'static PropertyDef> {
+    PROPERTIES.iter().find(|p| p.key == key)
+}
+
+// Les snapshots et versions non numériques ne permettent pas de conclure
+fn is_release(version: &str) -> bool {
+    version.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+impl PropertyDef {
+    pub fn available_in(&self, minecraft_version: &str) -> bool {
+        if !is_release(minecraft_version) {
+            return true;
+        }
+        compare_versions(minecraft_version, self.introduced) != Ordering::Less
+            && self
+                .removed
+                .map(|removed| compare_versions(minecraft_version, removed) == Ordering::Less)
+                .unwrap_or(true)
+    }
+
+    // Vérifier une valeur et la convertir dans la forme lue par la version du serveur
+    pub fn normalize(&self, value: &serde_json::Value, minecraft_version: Option<&str>) -> Result<String, String> {
+        let text = match value {
+            serde_json::Value::String(s) => s.trim().to_string(),
+            serde_json::Value::Bool(b) => b.to_string(),
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::Null => return Err(format!("{}: valeur manquante", self.key)),
+            _ => return Err(format!("{}: valeur non scalaire", self.key)),
+        };
+
+        match self.kind {
+            PropertyKind::Bool => match text.to_lowercase().as_str() {
+                "true" | "false" => Ok(text.to_lowercase()),
+                _ => Err(format!("{}: booléen attendu (true/false), reçu \"{}\"", self.key, text)),
+            },
+            PropertyKind::Int { min, max } => {
+                let number: i64 = text
+                    .parse()
+                    .map_err(|_| format!("{}: nombre entier attendu, reçu \"{}\"", self.key, text))?;
+                if number < min || number > max {
+                    return Err(format!("{}: {} hors limites ({} à {})", self.key, number, min, max));
+                }
+                Ok(number.to_string())
+            }
+            PropertyKind::Enum(values) => {
+                let lower = text.to_lowercase();
+                let numeric = lower.parse::<usize>().ok();
+                let index = values
+                    .iter()
+                    .position(|v| *v == lower)
+                    .or(numeric.filter(|index| *index < values.len()))
+                    .ok_or_else(|| format!("{}: valeur \"{}\" invalide (attendu: {})", self.key, text, values.join(", ")))?;
+                // Version inconnue ou snapshot : la forme reçue est conservée
+                let use_index = match minecraft_version.filter(|v| is_release(v)) {
+                    Some(version) => compare_versions(version, NAMED_ENUMS) == Ordering::Less,
+                    None => numeric.is_some(),
+                };
+                Ok(if use_index { index.to_string() } else { values[index].to_string() })
+            }
+            PropertyKind::Text => {
+                // Les retours à la ligne casseraient le format clé=valeur
+                if text.contains(['\n', '\r']) {
+                    return Err(format!("{}: retour à la ligne interdit", self.key));
+                }
+                if self.key == "resource-pack-sha1" && !text.is_empty()
+                    && (text.len() != 40 || !text.chars().all(|c| c.is_ascii_hexdigit()))
+                {
+                    return Err(format!("{}: empreinte SHA-1 de 40 caractères hexadécimaux attendue", self.key));
+                }
+                Ok(text)
+            }
+        }
+    }
+
+    fn to_schema(self, minecraft_version: Option<&str>) -> PropertySchema {
+        let (kind, min, max, values) = match self.kind {
+            PropertyKind::Bool => ("boolean", None, None, Vec::new()),
+            PropertyKind::Int { min, max } => ("integer", Some(min), Some(max), Vec::new()),
+            PropertyKind::Text => ("string", None, None, Vec::new()),
+            PropertyKind::Enum(values) => ("enum", None, None, values.iter().map(|v| v.to_string()).collect()),
+        };
+        PropertySchema {
+            key: self.key.to_string(),
+            kind: kind.to_string(),
+            min,
+            max,
+            values,
+            default: self.default.to_string(),
+            introduced: self.introduced.to_string(),
+            removed: self.removed.map(|r| r.to_string()),
+            description: self.description.to_string(),
+            available: minecraft_version.map(|v| self.available_in(v)).unwrap_or(true),
+        }
+    }
+}
+
+pub fn schema(minecraft_version: Option<&str>) -> Vec<PropertySchema> {
+    PROPERTIES.iter().map(|p| p.to_schema(minecraft_version)).collect()
+}
+
+// Modifications validées, avec les avertissements sur les propriétés ignorées par la version du serveur
+#[derive(Debug, Clone, Default)]
+pub struct ValidatedChanges {
+    pub changes: Vec<(String, String)>,
+    pub warnings: Vec<String>,
+}
+
+// Valider un ensemble de modifications ; toutes les erreurs sont renvoyées ensemble
+pub fn validate_changes(changes: &serde_json::Map<String, serde_json::Value>, minecraft_version: Option<&str>) -> Result<ValidatedChanges, String> {
+    let mut validated = ValidatedChanges::default();
+    let mut errors = Vec::new();
+
+    for (key, value) in changes {
+        // null : propriété laissée telle quelle (champ non renseigné dans l'interface)
+        if value.is_null() {
+            continue;
+        }
+        let key = key.trim();
+        if key.is_empty() || key.contains(['=', ':', '\n', '#']) {
+            errors.push(format!("Clé invalide: \"{}\"", key));
+            continue;
+        }
+        let definition = find(key);
+        if let (Some(definition), Some(version)) = (definition, minecraft_version) {
+            if !definition.available_in(version) {
+                validated.warnings.push(format!("{} n'est pas lue par Minecraft {}", definition.key, version));
+            }
+        }
+        let result = match definition {
+            Some(definition) => definition.normalize(value, minecraft_version),
+            // Propriété inconnue (mod, plugin) : seule une valeur scalaire sur une ligne est acceptée
+            None => match value {
+                serde_json::Value::String(s) if !s.contains(['\n', '\r']) => Ok(s.trim().to_string()),
+                serde_json::Value::Bool(b) => Ok(b.to_string()),
+                serde_json::Value::Number(n) => Ok(n.to_string()),
+                _ => Err(format!("{}: valeur invalide", key)),
+            },
+        };
+        match result {
+            Ok(text) => validated.changes.push((key.to_string(), text)),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(validated)
+    } else {
+        Err(format!("Propriétés invalides: {}", errors.join("; ")))
+    }
+}
+
+// Analyser les propriétés existantes pour une version donnée
+pub fn audit(properties: &[(String, String)], minecraft_version: Option<&str>) -> Vec<PropertyIssue> {
+    let mut issues = Vec::new();
+
+    for (key, value) in properties {
+        let Some(definition) = find(key) else {
+            issues.push(PropertyIssue {
+                key: key.clone(),
+                kind: "unknown".to_string(),
+                message: "Propriété inconnue de Minecraft vanilla (mod, plugin ou faute de frappe)".to_string(),
+            });
+            continue;
+        };
+
+        if let Err(e) = definition.normalize(&serde_json::Value::String(value.clone()), minecraft_version) {
+            issues.push(PropertyIssue { key: key.clone(), kind: "invalid".to_string(), message: e });
+        }
+
+        let Some(version) = minecraft_version.filter(|v| is_release(v)) else { continue };
+        if compare_versions(version, definition.introduced) == Ordering::Less {
+            issues.push(PropertyIssue {
+                key: key.clone(),
+                kind: "too_new".to_string(),
+                message: format!("Apparue en {}, ignorée par Minecraft {}", definition.introduced, version),
+            });
+        } else if let Some(removed) = definition.removed.filter(|r| compare_versions(version, r) != Ordering::Less) {
+            issues.push(PropertyIssue {
+                key: key.clone(),
+                kind: "obsolete".to_string(),
+                message: format!("Supprimée en {}, ignorée par Minecraft {}", removed, version),
+            });
+        }
+    }
+
+    issues
+}
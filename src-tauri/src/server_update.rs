@@ -0,0 +1,455 @@
+// Mises à jour transactionnelles : préparation dans un dossier à part, échange des fichiers,
+// démarrage de vérification et retour automatique aux fichiers précédents en cas d'échec
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::file_tree;
+use crate::properties_file::PropertiesFile;
+
+// Dossier de travail des mises à jour, à l'intérieur du dossier serveur
+const WORK_DIR: &str = ".nether-update";
+const HISTORY_FILE: &str = "nether-update-history.json";
+// Entrées jamais remplacées par une mise à jour (données du monde et réglages du joueur)
+const PRESERVED: [&str; 3] = ["user_jvm_args.txt", "eula.txt", "server.properties"];
+// Nombre de lignes de log conservées dans l'historique
+const LOG_TAIL: usize = 40;
+// Copie du serveur où a lieu le démarrage de vérification, dans le dossier de travail
+const VERIFY_DIR: &str = "verify";
+// Monde jetable de la copie de vérification : aucun monde n'est copié ni chargé
+const VERIFY_LEVEL: &str = ".nether-verify-world";
+// Dossiers du serveur inutiles à la vérification
+const VERIFY_SKIPPED: [&str; 3] = [WORK_DIR, "logs", "crash-reports"];
+const LAUNCH_SCRIPT: &str = "start.bat";
+
+// Journal écrit sur disque à chaque étape : permet de reprendre un échange interrompu
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct UpdateJournal {
+    id: String,
+    // Entrées du dossier serveur déplacées dans previous/
+    replaced: Vec<String>,
+    // Entrées de staging/ installées dans le dossier serveur
+    installed: Vec<String>,
+    // "staged", "swapping", "swapped", "finished" ou "rolled_back"
+    state: String,
+}
+
+pub struct UpdateTransaction {
+    server_dir: PathBuf,
+    work_dir: PathBuf,
+    journal: UpdateJournal,
+}
+
+// Une tentative de mise à jour dans nether-update-history.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateRecord {
+    pub id: String,
+    pub server_type: String,
+    pub from_version: Option<String>,
+    pub to_version: String,
+    pub started_at: String,
+    pub finished_at: String,
+    // "success", "rolled_back" ou "failed"
+    pub status: String,
+    pub verified: bool,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub log_tail: Vec<String>,
+}
+
+impl UpdateTransaction {
+    // Démarrer une transaction (une transaction interrompue est d'abord annulée)
+    pub fn begin(server_dir: &Path) -> Result<Self, String> {
+        recover(server_dir)?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let work_dir = server_dir.join(WORK_DIR).join(&id);
+        fs::create_dir_all(work_dir.join("staging"))
+            .map_err(|e| format!("Erreur création dossier de mise à jour: {}", e))?;
+        fs::create_dir_all(work_dir.join("previous"))
+            .map_err(|e| format!("Erreur création dossier de mise à jour: {}", e))?;
+
+        let transaction = UpdateTransaction {
+            server_dir: server_dir.to_path_buf(),
+            work_dir,
+            journal: UpdateJournal {
+                id,
+                state: "staged".to_string(),
+                ..Default::default()
+            },
+        };
+        transaction.save_journal()?;
+        Ok(transaction)
+    }
+
+    pub fn id(&self) -> &str {
+        &self.journal.id
+    }
+
+    // Dossier où préparer les nouveaux fichiers (même arborescence que le serveur)
+    pub fn staging(&self) -> PathBuf {
+        self.work_dir.join("staging")
+    }
+
+    // Entrées de premier niveau installées par swap()
+    pub fn installed(&self) -> &[String] {
+        &self.journal.installed
+    }
+
+    fn previous(&self) -> PathBuf {
+        self.work_dir.join("previous")
+    }
+
+    fn save_journal(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.journal)
+            .map_err(|e| format!("Erreur sérialisation journal: {}", e))?;
+        fs::write(self.work_dir.join("journal.json"), json)
+            .map_err(|e| format!("Erreur écriture journal de mise à jour: {}", e))
+    }
+
+    // Remplacer les fichiers du serveur par ceux de staging (renommages sur le même volume)
+    pub fn swap(&mut self) -> Result<(), String> {
+        let staging = self.staging();
+        let previous = self.previous();
+
+        let entries: Vec<String> = fs::read_dir(&staging)
+            .map_err(|e| format!("Erreur lecture staging: {}", e))?
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| !PRESERVED.contains(&name.as_str()) || !self.server_dir.join(name).exists())
+            .collect();
+
+        if entries.is_empty() {
+            return Err("Aucun fichier préparé pour la mise à jour".to_string());
+        }
+
+        self.journal.state = "swapping".to_string();
+        self.save_journal()?;
+
+        for name in entries {
+            let current = self.server_dir.join(&name);
+            if current.exists() {
+                fs::rename(&current, previous.join(&name))
+                    .map_err(|e| format!("Erreur mise de côté de {}: {}", name, e))?;
+                self.journal.replaced.push(name.clone());
+                self.save_journal()?;
+            }
+
+            fs::rename(staging.join(&name), &current)
+                .map_err(|e| format!("Erreur installation de {}: {}", name, e))?;
+            self.journal.installed.push(name);
+            self.save_journal()?;
+        }
+
+        self.journal.state = "swapped".to_string();
+        self.save_journal()?;
+        println!("🔁 Fichiers de mise à jour installés ({} entrées)", self.journal.installed.len());
+        Ok(())
+    }
+
+    // Revenir exactement aux fichiers d'avant la mise à jour
+    pub fn rollback(&mut self) -> Result<(), String> {
+        rollback_journal(&self.server_dir, &self.work_dir, &self.journal)?;
+        self.journal.state = "rolled_back".to_string();
+        let _ = self.save_journal();
+        let _ = fs::remove_dir_all(&self.work_dir);
+        println!("↩️ Mise à jour annulée, fichiers précédents restaurés");
+        Ok(())
+    }
+
+    // Valider la mise à jour et supprimer les anciens fichiers
+    pub fn finish(mut self) -> Result<(), String> {
+        self.journal.state = "finished".to_string();
+        self.save_journal()?;
+        fs::remove_dir_all(&self.work_dir)
+            .map_err(|e| format!("Erreur nettoyage dossier de mise à jour: {}", e))?;
+        cleanup_work_root(&self.server_dir);
+        Ok(())
+    }
+}
+
+fn rollback_journal(server_dir: &Path, work_dir: &Path, journal: &UpdateJournal) -> Result<(), String> {
+    // Ordre inverse : retirer les nouveaux fichiers puis remettre les anciens
+    for name in journal.installed.iter().rev() {
+        let path = server_dir.join(name);
+        let result = if path.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+        if let Err(e) = result {
+            if path.exists() {
+                return Err(format!("Erreur suppression de {}: {}", name, e));
+            }
+        }
+    }
+
+    for name in journal.replaced.iter().rev() {
+        fs::rename(work_dir.join("previous").join(name), server_dir.join(name))
+            .map_err(|e| format!("Erreur restauration de {}: {}", name, e))?;
+    }
+
+    cleanup_work_root(server_dir);
+    Ok(())
+}
+
+fn cleanup_work_root(server_dir: &Path) {
+    let root = server_dir.join(WORK_DIR);
+    if fs::read_dir(&root).map(|mut entries| entries.next().is_none()).unwrap_or(false) {
+        let _ = fs::remove_dir(&root);
+    }
+}
+
+// Annuler les transactions laissées en plan (application fermée pendant une mise à jour)
+pub fn recover(server_dir: &Path) -> Result<(), String> {
+    let root = server_dir.join(WORK_DIR);
+    let Ok(entries) = fs::read_dir(&root) else { return Ok(()) };
+
+    for entry in entries.flatten() {
+        let work_dir = entry.path();
+        let journal: Option<UpdateJournal> = fs::read_to_string(work_dir.join("journal.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+
+        if let Some(journal) = journal {
+            if journal.state == "swapping" || journal.state == "swapped" {
+                println!("⚠️ Mise à jour interrompue détectée ({}), restauration...", journal.id);
+                rollback_journal(server_dir, &work_dir, &journal)?;
+            }
+        }
+        let _ = fs::remove_dir_all(&work_dir);
+    }
+
+    cleanup_work_root(server_dir);
+    Ok(())
+}
+
+// Lancer le script de démarrage avec stdin/stdout/stderr redirigés
+#[cfg(windows)]
+fn spawn_launch_script(server_dir: &Path) -> std::io::Result<Child> {
+    Command::new("cmd")
+        .args(["/C", LAUNCH_SCRIPT])
+        .current_dir(server_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+}
+
+// Hors Windows, seule la ligne de lancement Java de start.bat est exécutée ("%%" de cmd ramené à "%")
+#[cfg(not(windows))]
+fn spawn_launch_script(server_dir: &Path) -> std::io::Result<Child> {
+    use std::os::unix::process::CommandExt;
+
+    let script = fs::read_to_string(server_dir.join(LAUNCH_SCRIPT))?;
+    let line = script
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with('"') && (line.contains(" -jar ") || line.contains(" @")))
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, format!("Ligne de lancement Java introuvable dans {}", LAUNCH_SCRIPT)))?;
+
+    // Groupe de processus dédié : l'arrêt forcé atteint aussi java lancé par le shell
+    Command::new("sh")
+        .args(["-c", &line.replace("%%", "%")])
+        .current_dir(server_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()
+}
+
+// Arrêt forcé du script et de java : cmd.exe / sh ne transmettent pas l'arrêt à leurs enfants
+#[cfg(windows)]
+fn kill_launch_tree(child: &mut Child) {
+    let _ = Command::new("taskkill")
+        .args(["/F", "/T", "/PID", &child.id().to_string()])
+        .output();
+    let _ = child.kill();
+}
+
+#[cfg(not(windows))]
+fn kill_launch_tree(child: &mut Child) {
+    let _ = Command::new("kill")
+        .args(["-KILL", &format!("-{}", child.id())])
+        .output();
+    let _ = child.kill();
+}
+
+// Copie jetable du serveur, sans ses mondes : tout ce que le démarrage écrit (version_history.json,
+// bukkit.yml, config/paper-*.yml, configs des mods...) reste dans la copie, supprimée ensuite
+struct VerifyCopy {
+    dir: PathBuf,
+}
+
+impl VerifyCopy {
+    fn create(server_dir: &Path) -> Result<Self, String> {
+        let dir = server_dir.join(WORK_DIR).join(VERIFY_DIR);
+        // Restes d'une vérification interrompue
+        let _ = fs::remove_dir_all(&dir);
+
+        let level = PropertiesFile::load_or_default(&server_dir.join("server.properties"))
+            .get("level-name")
+            .unwrap_or("world")
+            .to_string();
+        let worlds: Vec<String> = ["", "_nether", "_the_end"].iter().map(|suffix| format!("{}{}", level, suffix)).collect();
+        let skip = |path: &Path| {
+            if path.parent() != Some(server_dir) {
+                return false;
+            }
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            VERIFY_SKIPPED.contains(&name.as_str()) || worlds.contains(&name) || path.join("level.dat").exists()
+        };
+
+        let copy = VerifyCopy { dir };
+        file_tree::copy_tree_except(server_dir, &copy.dir, &skip)?;
+
+        let properties_path = copy.dir.join("server.properties");
+        let mut properties = PropertiesFile::load_or_default(&properties_path);
+        properties.set("level-name", VERIFY_LEVEL);
+        properties.save(&properties_path)?;
+        Ok(copy)
+    }
+}
+
+impl Drop for VerifyCopy {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.dir) {
+            println!("⚠️ Suppression de la copie de vérification impossible: {}", e);
+        }
+    }
+}
+
+// Démarrer une copie du serveur sur un monde jetable et attendre "Done (...)! For help, type "help"" :
+// le dossier serveur n'est jamais modifié par la vérification
+pub async fn verify_boot(server_dir: &Path, timeout: Duration) -> Result<Vec<String>, (String, Vec<String>)> {
+    let server_dir = server_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let copy = VerifyCopy::create(&server_dir).map_err(|e| (e, Vec::new()))?;
+        boot_blocking(&copy.dir, timeout)
+    })
+    .await
+    .map_err(|e| (format!("Erreur vérification: {}", e), Vec::new()))?
+}
+
+// Démarrer un serveur déjà isolé (clone de test) tel quel, monde compris
+pub async fn boot_until_ready(server_dir: &Path, timeout: Duration) -> Result<Vec<String>, (String, Vec<String>)> {
+    let server_dir = server_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || boot_blocking(&server_dir, timeout))
+        .await
+        .map_err(|e| (format!("Erreur vérification: {}", e), Vec::new()))?
+}
+
+fn boot_blocking(server_dir: &Path, timeout: Duration) -> Result<Vec<String>, (String, Vec<String>)> {
+    if !server_dir.join(LAUNCH_SCRIPT).exists() {
+        return Err(("Script de démarrage non trouvé pour la vérification".to_string(), Vec::new()));
+    }
+
+    println!("🧪 Démarrage de vérification du serveur...");
+
+    let mut child = spawn_launch_script(server_dir)
+        .map_err(|e| (format!("Erreur démarrage de vérification: {}", e), Vec::new()))?;
+
+    // stdout et stderr sont lus dans des threads et regroupés dans un canal
+    let (sender, receiver) = mpsc::channel::<String>();
+    if let Some(stdout) = child.stdout.take() {
+        let sender = sender.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                let _ = sender.send(line);
+            }
+        });
+    }
+    if let Some(stderr) = child.stderr.take() {
+        let sender = sender.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                let _ = sender.send(format!("[ERROR] {}", line));
+            }
+        });
+    }
+    drop(sender);
+
+    let started = Instant::now();
+    let mut log = Vec::new();
+    let mut ready = false;
+
+    while started.elapsed() < timeout {
+        match receiver.recv_timeout(Duration::from_millis(500)) {
+            Ok(line) => {
+                let done = line.contains("Done (") && line.contains("help");
+                push_log(&mut log, line);
+                if done {
+                    ready = true;
+                    break;
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if let Ok(Some(status)) = child.try_wait() {
+                    return Err((format!("Le serveur s'est arrêté pendant la vérification ({})", status), log));
+                }
+            }
+            // Plus aucune sortie : le processus est terminé
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    // Arrêt propre puis forcé si nécessaire
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(b"stop\n");
+        let _ = stdin.flush();
+    }
+    let stop_deadline = Instant::now() + Duration::from_secs(60);
+    loop {
+        if let Ok(line) = receiver.recv_timeout(Duration::from_millis(200)) {
+            push_log(&mut log, line);
+        }
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            _ if Instant::now() > stop_deadline => {
+                kill_launch_tree(&mut child);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    if ready {
+        println!("✅ Démarrage vérifié en {}s", started.elapsed().as_secs());
+        Ok(log)
+    } else if started.elapsed() >= timeout {
+        Err((format!("Le serveur n'a pas terminé son démarrage en {}s", timeout.as_secs()), log))
+    } else {
+        Err(("Le serveur s'est arrêté avant la fin du démarrage".to_string(), log))
+    }
+}
+
+fn push_log(log: &mut Vec<String>, line: String) {
+    log.push(line);
+    if log.len() > LOG_TAIL {
+        log.remove(0);
+    }
+}
+
+// ========== HISTORIQUE ==========
+
+pub fn history(server_dir: &Path) -> Vec<UpdateRecord> {
+    fs::read_to_string(server_dir.join(HISTORY_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn record(server_dir: &Path, entry: UpdateRecord) -> Result<(), String> {
+    let mut entries = history(server_dir);
+    entries.push(entry);
+
+    let json = serde_json::to_string_pretty(&entries)
+        .map_err(|e| format!("Erreur sérialisation historique: {}", e))?;
+    fs::write(server_dir.join(HISTORY_FILE), json)
+        .map_err(|e| format!("Erreur écriture historique: {}", e))
+}
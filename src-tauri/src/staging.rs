@@ -0,0 +1,411 @@
+// Clones de test : copie temporaire d'un serveur sur un port libre et sans réseau,
+// pour essayer une nouvelle version ou des mods avant de les appliquer au serveur réel
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use crate::app_paths;
+use crate::automation;
+use crate::file_tree;
+use crate::properties_file::PropertiesFile;
+use crate::server_update::{self, UpdateRecord, UpdateTransaction};
+
+// Entrées jamais copiées dans le clone (journaux, anciens rapports, transactions en cours)
+const SKIPPED: [&str; 5] = ["logs", "crash-reports", ".nether-update", "nether-update-history.json", "session.lock"];
+// Dossiers de région conservés en mode "spawn" : r.X.Z.mca avec |X| et |Z| <= SPAWN_REGIONS
+const SPAWN_REGIONS: i32 = 1;
+const REGION_DIRS: [&str; 3] = ["region", "entities", "poi"];
+const DEFAULT_BOOT_TIMEOUT_SECS: u64 = 300;
+// Nombre maximal de lignes d'erreur rapportées
+const MAX_ERRORS: usize = 20;
+
+// Changement proposé et réglages du clone
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StagingOptions {
+    // Nouvelle version (Minecraft, build ou loader "1.20.1-47.2.0")
+    pub version: Option<String>,
+    // Fichiers à ajouter dans mods/ (ou plugins/ pour la famille Paper)
+    #[serde(default)]
+    pub add_mods: Vec<String>,
+    // Noms de fichiers à retirer de mods/ (ou plugins/)
+    #[serde(default)]
+    pub remove_mods: Vec<String>,
+    // "full" (par défaut), "spawn" (régions autour du spawn) ou "none" (nouveau monde)
+    pub world: Option<String>,
+    pub boot_timeout_secs: Option<u64>,
+}
+
+// Un clone de test, enregistré dans Staging/<id>/staging.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StagingClone {
+    pub id: String,
+    pub server_name: String,
+    pub source_path: String,
+    pub path: String,
+    pub server_type: String,
+    pub port: u16,
+    pub from_version: Option<String>,
+    pub to_version: Option<String>,
+    // Entrées de premier niveau modifiées par le changement (reportées lors de la promotion)
+    pub touched: Vec<String>,
+    // Date de modification (ms) de chaque entrée copiée du serveur d'origine, hors mondes
+    #[serde(default)]
+    pub source_mtimes: HashMap<String, u64>,
+    pub created_at: String,
+    // "prepared", "ready", "failed"
+    pub status: String,
+    pub ready: bool,
+    pub boot_seconds: Option<u64>,
+    pub errors: Vec<String>,
+    pub crash_report: Option<String>,
+    pub log_tail: Vec<String>,
+}
+
+impl StagingClone {
+    pub fn dir(&self) -> PathBuf {
+        PathBuf::from(&self.path)
+    }
+}
+
+fn staging_root() -> Result<PathBuf, String> {
+    Ok(app_paths::app_dir()?.join("Staging"))
+}
+
+fn save(clone: &StagingClone) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(clone)
+        .map_err(|e| format!("Erreur sérialisation clone: {}", e))?;
+    fs::write(staging_root()?.join(&clone.id).join("staging.json"), json)
+        .map_err(|e| format!("Erreur écriture staging.json: {}", e))
+}
+
+pub fn load(id: &str) -> Result<StagingClone, String> {
+    let content = fs::read_to_string(staging_root()?.join(id).join("staging.json"))
+        .map_err(|_| format!("Clone de test {} introuvable", id))?;
+    serde_json::from_str(&content).map_err(|e| format!("Erreur lecture clone: {}", e))
+}
+
+pub fn list() -> Vec<StagingClone> {
+    let Ok(root) = staging_root() else { return Vec::new() };
+    let Ok(entries) = fs::read_dir(root) else { return Vec::new() };
+
+    let mut clones: Vec<StagingClone> = entries
+        .flatten()
+        .filter_map(|entry| load(&entry.file_name().to_string_lossy()).ok())
+        .collect();
+    clones.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    clones
+}
+
+// Nom du monde (level-name) lu dans server.properties
+fn level_name(server_dir: &Path) -> String {
+    PropertiesFile::load(&server_dir.join("server.properties"))
+        .ok()
+        .and_then(|properties| properties.get("level-name").map(|v| v.trim().to_string()))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "world".to_string())
+}
+
+// Fichier de région hors de la zone du spawn ("r.5.-3.mca")
+fn is_far_region(name: &str) -> bool {
+    let mut parts = name.split('.');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some("r"), Some(x), Some(z), Some("mca")) => match (x.parse::<i32>(), z.parse::<i32>()) {
+            (Ok(x), Ok(z)) => x.abs() > SPAWN_REGIONS || z.abs() > SPAWN_REGIONS,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// Date de modification la plus récente d'un fichier ou d'un dossier (ms)
+fn latest_mtime(path: &Path) -> u64 {
+    let own = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    if !path.is_dir() {
+        return own;
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| latest_mtime(&entry.path())).max().unwrap_or(0))
+        .unwrap_or(0)
+        .max(own)
+}
+
+// Fichier de région éloigné du spawn, ignoré en mode "spawn"
+fn is_far_region_file(path: &Path) -> bool {
+    let in_region_dir = path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .map(|name| REGION_DIRS.contains(&name.to_string_lossy().as_ref()))
+        .unwrap_or(false);
+    in_region_dir && path.file_name().map(|name| is_far_region(&name.to_string_lossy())).unwrap_or(false)
+}
+
+// Isoler le clone : port libre, mode hors ligne, écoute locale uniquement, RCON/query coupés
+fn isolate_properties(clone_dir: &Path, port: u16) -> Result<(), String> {
+    let path = clone_dir.join("server.properties");
+    let mut properties = PropertiesFile::load_or_default(&path);
+
+    let overrides = [
+        ("server-port", port.to_string()),
+        ("server-ip", "127.0.0.1".to_string()),
+        ("online-mode", "false".to_string()),
+        ("enable-rcon", "false".to_string()),
+        ("enable-query", "false".to_string()),
+    ];
+
+    for (key, value) in &overrides {
+        properties.set(key, value);
+    }
+
+    properties.save(&path)
+}
+
+fn mods_dir_name(server_type: &str) -> &'static str {
+    match server_type {
+        "paper" | "folia" | "purpur" => "plugins",
+        _ => "mods",
+    }
+}
+
+// Copier le serveur dans Staging/<id>/server et l'isoler du réseau
+pub fn create_clone(server_name: &str, server_path: &str, server_type: &str, options: &StagingOptions) -> Result<StagingClone, String> {
+    let source = Path::new(server_path);
+    if !source.join("server.properties").exists() && !source.join("start.bat").exists() {
+        return Err(format!("Dossier serveur invalide: {}", server_path));
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let clone_dir = staging_root()?.join(&id).join("server");
+
+    let world = options.world.as_deref().unwrap_or("full");
+    let level = level_name(source);
+    let world_dirs = [level.clone(), format!("{}_nether", level), format!("{}_the_end", level)];
+
+    println!("🧪 Création du clone de test de {} (monde: {})...", server_name, world);
+    fs::create_dir_all(&clone_dir)
+        .map_err(|e| format!("Erreur création dossier de test: {}", e))?;
+
+    let mut source_mtimes = HashMap::new();
+    let copied = (|| {
+        for entry in fs::read_dir(source).map_err(|e| format!("Erreur lecture dossier serveur: {}", e))?.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if SKIPPED.contains(&name.as_str()) {
+                continue;
+            }
+            let is_world = world_dirs.contains(&name);
+            if is_world && world == "none" {
+                continue;
+            }
+            if !is_world {
+                source_mtimes.insert(name.clone(), latest_mtime(&entry.path()));
+            }
+            let trim_regions = is_world && world == "spawn";
+            file_tree::copy_tree_except(&entry.path(), &clone_dir.join(&name), &|path| trim_regions && is_far_region_file(path))?;
+        }
+        Ok::<(), String>(())
+    })();
+    if let Err(e) = copied {
+        let _ = fs::remove_dir_all(staging_root()?.join(&id));
+        return Err(e);
+    }
+
+    let current_port = PropertiesFile::load(&source.join("server.properties"))
+        .ok()
+        .and_then(|properties| properties.get("server-port")?.trim().parse::<u16>().ok())
+        .unwrap_or(25565);
+    let port = automation::find_available_port(current_port.saturating_add(100))?;
+    isolate_properties(&clone_dir, port)?;
+
+    let from_version = fs::read_to_string(source.join("nether-config.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|config| config["version"].as_str().map(|v| v.to_string()));
+
+    let clone = StagingClone {
+        id,
+        server_name: server_name.to_string(),
+        source_path: server_path.to_string(),
+        path: clone_dir.to_string_lossy().to_string(),
+        server_type: server_type.to_string(),
+        port,
+        from_version,
+        to_version: None,
+        touched: Vec::new(),
+        source_mtimes,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        status: "prepared".to_string(),
+        ready: false,
+        boot_seconds: None,
+        errors: Vec::new(),
+        crash_report: None,
+        log_tail: Vec::new(),
+    };
+    save(&clone)?;
+
+    println!("Clone de test prêt sur le port {}", port);
+    Ok(clone)
+}
+
+// Appliquer les ajouts/suppressions de mods dans le clone
+pub fn apply_mod_changes(clone: &mut StagingClone, options: &StagingOptions) -> Result<(), String> {
+    if options.add_mods.is_empty() && options.remove_mods.is_empty() {
+        return Ok(());
+    }
+
+    let dir_name = mods_dir_name(&clone.server_type);
+    let mods_dir = clone.dir().join(dir_name);
+    fs::create_dir_all(&mods_dir)
+        .map_err(|e| format!("Erreur création dossier {}: {}", dir_name, e))?;
+
+    for name in &options.remove_mods {
+        if !file_tree::is_plain_name(name) {
+            return Err(format!("Nom de mod invalide: {}", name));
+        }
+        let path = mods_dir.join(name);
+        if !path.exists() {
+            return Err(format!("Mod non trouvé dans le clone: {}", name));
+        }
+        fs::remove_file(&path).map_err(|e| format!("Erreur suppression de {}: {}", name, e))?;
+    }
+
+    for file in &options.add_mods {
+        let source = Path::new(file);
+        let file_name = source
+            .file_name()
+            .ok_or(format!("Fichier de mod invalide: {}", file))?;
+        fs::copy(source, mods_dir.join(file_name))
+            .map_err(|e| format!("Erreur copie du mod {}: {}", file, e))?;
+    }
+
+    if !clone.touched.iter().any(|t| t == dir_name) {
+        clone.touched.push(dir_name.to_string());
+    }
+    save(clone)
+}
+
+// Enregistrer les entrées installées par une mise à jour appliquée au clone
+pub fn record_update(clone: &mut StagingClone, installed: &[String], version: &str) -> Result<(), String> {
+    for name in installed {
+        if !clone.touched.contains(name) {
+            clone.touched.push(name.clone());
+        }
+    }
+    clone.to_version = Some(version.to_string());
+    save(clone)
+}
+
+// Rapport de crash le plus récent généré pendant le démarrage
+fn latest_crash_report(clone_dir: &Path) -> Option<String> {
+    fs::read_dir(clone_dir.join("crash-reports"))
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().extension().map(|ext| ext == "txt").unwrap_or(false))
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .and_then(|entry| fs::read_to_string(entry.path()).ok())
+}
+
+// Démarrer le clone et rapporter l'état de préparation, les erreurs et le rapport de crash
+pub async fn boot(clone: &mut StagingClone, timeout_secs: Option<u64>) -> Result<(), String> {
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_BOOT_TIMEOUT_SECS));
+    let started = Instant::now();
+
+    let (ready, log) = match server_update::boot_until_ready(&clone.dir(), timeout).await {
+        Ok(log) => (true, log),
+        Err((e, log)) => {
+            clone.errors.push(e);
+            (false, log)
+        }
+    };
+
+    clone.ready = ready;
+    clone.boot_seconds = ready.then(|| started.elapsed().as_secs());
+    clone.errors.extend(
+        log.iter()
+            // Niveau de log du serveur ("[Server thread/ERROR]"), pas le préfixe [ERROR] ajouté à stderr
+            .filter(|line| line.contains("/ERROR]") || line.contains("Exception"))
+            .take(MAX_ERRORS)
+            .cloned(),
+    );
+    clone.crash_report = latest_crash_report(&clone.dir());
+    clone.log_tail = log;
+    clone.status = if ready && clone.crash_report.is_none() { "ready" } else { "failed" }.to_string();
+
+    println!("🧪 Clone de test {}: {}", clone.id, clone.status);
+    save(clone)
+}
+
+// Reporter le changement testé sur le serveur d'origine (échange transactionnel)
+pub fn promote(id: &str) -> Result<String, String> {
+    let clone = load(id)?;
+    if clone.status != "ready" {
+        return Err("Le clone de test n'a pas démarré correctement, promotion refusée".to_string());
+    }
+    if clone.touched.is_empty() {
+        return Err("Aucun changement à reporter sur le serveur d'origine".to_string());
+    }
+
+    let source = PathBuf::from(&clone.source_path);
+    // Le serveur d'origine a pu changer depuis la création du clone : ne pas écraser ces modifications
+    let modified: Vec<&str> = clone
+        .touched
+        .iter()
+        .filter(|name| {
+            let path = source.join(name.as_str());
+            path.exists() && clone.source_mtimes.get(name.as_str()).is_none_or(|recorded| latest_mtime(&path) > *recorded)
+        })
+        .map(|name| name.as_str())
+        .collect();
+    if !modified.is_empty() {
+        return Err(format!(
+            "Le serveur d'origine a été modifié depuis la création du clone ({}), créez un nouveau clone de test",
+            modified.join(", ")
+        ));
+    }
+
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let mut transaction = UpdateTransaction::begin(&source)?;
+
+    let staged = clone
+        .touched
+        .iter()
+        .try_for_each(|name| file_tree::copy_tree(&clone.dir().join(name), &transaction.staging().join(name)));
+    if let Err(e) = staged.and_then(|_| transaction.swap()) {
+        let _ = transaction.rollback();
+        return Err(e);
+    }
+
+    let record = UpdateRecord {
+        id: transaction.id().to_string(),
+        server_type: clone.server_type.clone(),
+        from_version: clone.from_version.clone(),
+        to_version: clone.to_version.clone().or(clone.from_version.clone()).unwrap_or_default(),
+        started_at,
+        finished_at: chrono::Utc::now().to_rfc3339(),
+        status: "success".to_string(),
+        // Démarrage vérifié sur le clone
+        verified: true,
+        error: None,
+        log_tail: clone.log_tail.clone(),
+    };
+    transaction.finish()?;
+    let _ = server_update::record(&source, record);
+
+    discard(id)?;
+    println!("✅ Changement du clone {} appliqué à {}", id, clone.server_name);
+    Ok(format!("Changement appliqué au serveur {}", clone.server_name))
+}
+
+// Supprimer le clone de test
+pub fn discard(id: &str) -> Result<(), String> {
+    let dir = staging_root()?.join(id);
+    if !dir.join("staging.json").exists() {
+        return Err(format!("Clone de test {} introuvable", id));
+    }
+    fs::remove_dir_all(&dir).map_err(|e| format!("Erreur suppression du clone: {}", e))
+}
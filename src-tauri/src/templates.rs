@@ -0,0 +1,358 @@
+// Modèles de serveur : enregistrer un serveur existant, l'exporter/importer en archive
+// et créer de nouveaux serveurs identiques à partir du modèle
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::app_paths;
+use crate::automation;
+use crate::file_tree;
+use crate::jvm_profile::{self, JvmProfile};
+use crate::properties_file::PropertiesFile;
+
+const MANIFEST_FILE: &str = "template.json";
+const FILES_DIR: &str = "files";
+// Version du format de manifest (archive .nethertemplate)
+const FORMAT_VERSION: u32 = 1;
+// Propriétés propres à chaque instance, jamais reprises du modèle
+const INSTANCE_PROPERTIES: [&str; 4] = ["server-port", "server-ip", "query.port", "rcon.port"];
+// Écart habituel entre le port du serveur et le port RCON (25565 / 25575)
+const RCON_PORT_OFFSET: u16 = 10;
+// Entrées de premier niveau jamais copiées dans un modèle
+const SKIPPED: [&str; 4] = ["logs", "crash-reports", "cache", "session.lock"];
+
+// Contenu de template.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateManifest {
+    pub format: u32,
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub server_type: String,
+    pub version: String,
+    pub created_at: String,
+    // Mémoire allouée (-Xmx de start.bat)
+    pub ram_mb: Option<u32>,
+    // Lignes de user_jvm_args.txt
+    #[serde(default)]
+    pub jvm_args: Vec<String>,
+    // Profil JVM du serveur d'origine (remplace jvm_args quand il est présent)
+    #[serde(default)]
+    pub jvm_profile: Option<JvmProfile>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+    // Entrées de premier niveau copiées dans files/ (mods, plugins, config, monde...)
+    #[serde(default)]
+    pub entries: Vec<String>,
+    #[serde(default)]
+    pub includes_world: bool,
+}
+
+// Ce qui est enregistré dans le modèle
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TemplateOptions {
+    pub name: String,
+    pub description: Option<String>,
+    // Dossiers de configuration à inclure ("config", "defaultconfigs", "kubejs"...)
+    #[serde(default)]
+    pub config_folders: Vec<String>,
+    #[serde(default)]
+    pub include_world: bool,
+}
+
+// Paramètres du nouveau serveur créé à partir d'un modèle
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TemplateOverrides {
+    pub name: String,
+    pub port: Option<u16>,
+    pub ram: Option<u32>,
+    pub motd: Option<String>,
+    pub max_players: Option<u32>,
+    // Acceptation explicite de l'EULA Minecraft pour le nouveau serveur
+    #[serde(default)]
+    pub accept_eula: bool,
+    #[serde(default)]
+    pub eula_accepted_by: Option<String>,
+}
+
+fn templates_root() -> Result<PathBuf, String> {
+    Ok(app_paths::app_dir()?.join("Templates"))
+}
+
+fn template_dir(id: &str) -> Result<PathBuf, String> {
+    Ok(templates_root()?.join(id))
+}
+
+pub fn load(id: &str) -> Result<TemplateManifest, String> {
+    let content = fs::read_to_string(template_dir(id)?.join(MANIFEST_FILE))
+        .map_err(|_| format!("Modèle {} introuvable", id))?;
+    serde_json::from_str(&content).map_err(|e| format!("Erreur lecture modèle: {}", e))
+}
+
+fn save_manifest(dir: &Path, manifest: &TemplateManifest) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Erreur sérialisation modèle: {}", e))?;
+    fs::write(dir.join(MANIFEST_FILE), json)
+        .map_err(|e| format!("Erreur écriture template.json: {}", e))
+}
+
+pub fn list() -> Vec<TemplateManifest> {
+    let Ok(root) = templates_root() else { return Vec::new() };
+    let Ok(entries) = fs::read_dir(root) else { return Vec::new() };
+
+    let mut templates: Vec<TemplateManifest> = entries
+        .flatten()
+        .filter_map(|entry| load(&entry.file_name().to_string_lossy()).ok())
+        .collect();
+    templates.sort_by_key(|t| t.name.to_lowercase());
+    templates
+}
+
+pub fn delete(id: &str) -> Result<(), String> {
+    let dir = template_dir(id)?;
+    if !dir.join(MANIFEST_FILE).exists() {
+        return Err(format!("Modèle {} introuvable", id));
+    }
+    fs::remove_dir_all(&dir).map_err(|e| format!("Erreur suppression du modèle: {}", e))
+}
+
+// Lire server.properties en clé/valeur (commentaires ignorés)
+pub fn read_properties(path: &Path) -> BTreeMap<String, String> {
+    PropertiesFile::load_or_default(path).entries().into_iter().collect()
+}
+
+// Un manifest importé n'est pas fiable : entrées limitées au premier niveau du serveur,
+// clés de propriétés et arguments JVM sans caractère qui ajouterait une ligne ou une clé
+fn validate_manifest(manifest: &TemplateManifest) -> Result<(), String> {
+    if let Some(entry) = manifest.entries.iter().find(|name| !file_tree::is_plain_name(name)) {
+        return Err(format!("Entrée de modèle invalide: {}", entry));
+    }
+    if let Some(key) = manifest
+        .properties
+        .keys()
+        .find(|key| key.trim().is_empty() || key.contains(['=', ':', '\n', '\r']))
+    {
+        return Err(format!("Propriété de modèle invalide: {:?}", key));
+    }
+    if let Some(arg) = manifest.jvm_args.iter().find(|arg| arg.contains(['\n', '\r'])) {
+        return Err(format!("Argument JVM de modèle invalide: {:?}", arg));
+    }
+    Ok(())
+}
+
+// Enregistrer un serveur existant comme modèle
+pub fn save_from_server(server_path: &str, server_type: &str, version: &str, options: &TemplateOptions) -> Result<TemplateManifest, String> {
+    let server_dir = Path::new(server_path);
+    if !server_dir.exists() {
+        return Err(format!("Dossier serveur introuvable: {}", server_path));
+    }
+    if options.name.trim().is_empty() {
+        return Err("Le nom du modèle est requis".to_string());
+    }
+
+    let mut properties = read_properties(&server_dir.join("server.properties"));
+    properties.retain(|key, _| !INSTANCE_PROPERTIES.contains(&key.as_str()));
+
+    let level = properties.get("level-name").cloned().unwrap_or_else(|| "world".to_string());
+    let world_dirs = [level.clone(), format!("{}_nether", level), format!("{}_the_end", level)];
+
+    let mut entries: Vec<String> = ["mods", "plugins"]
+        .iter()
+        .map(|s| s.to_string())
+        .chain(options.config_folders.iter().cloned())
+        .filter(|name| !SKIPPED.contains(&name.as_str()))
+        // Uniquement des dossiers de premier niveau du serveur
+        .filter(|name| file_tree::is_plain_name(name))
+        .filter(|name| server_dir.join(name).exists())
+        .collect();
+    if options.include_world {
+        entries.extend(world_dirs.iter().filter(|name| server_dir.join(name).exists()).cloned());
+    }
+    entries.sort();
+    entries.dedup();
+
+    let jvm_args: Vec<String> = fs::read_to_string(server_dir.join("user_jvm_args.txt"))
+        .unwrap_or_default()
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect();
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let dir = template_dir(&id)?;
+    fs::create_dir_all(dir.join(FILES_DIR))
+        .map_err(|e| format!("Erreur création dossier modèle: {}", e))?;
+
+    for name in &entries {
+        if let Err(e) = file_tree::copy_tree(&server_dir.join(name), &dir.join(FILES_DIR).join(name)) {
+            let _ = fs::remove_dir_all(&dir);
+            return Err(e);
+        }
+    }
+
+    let manifest = TemplateManifest {
+        format: FORMAT_VERSION,
+        id,
+        name: options.name.trim().to_string(),
+        description: options.description.clone(),
+        server_type: server_type.to_string(),
+        version: version.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        ram_mb: jvm_profile::current_heap_mb(server_dir),
+        jvm_args,
+        jvm_profile: jvm_profile::load(server_dir),
+        properties,
+        entries,
+        includes_world: options.include_world,
+    };
+    save_manifest(&dir, &manifest)?;
+
+    println!("📐 Modèle \"{}\" enregistré ({} entrées)", manifest.name, manifest.entries.len());
+    Ok(manifest)
+}
+
+// Appliquer le modèle sur un serveur tout juste créé (fichiers, propriétés, arguments JVM)
+pub fn apply(manifest: &TemplateManifest, server_dir: &Path, overrides: &TemplateOverrides, port: u16) -> Result<(), String> {
+    validate_manifest(manifest)?;
+    let files = template_dir(&manifest.id)?.join(FILES_DIR);
+
+    for name in &manifest.entries {
+        let target = server_dir.join(name);
+        // Remplacer le contenu par défaut (monde généré, dossier config vide...)
+        if target.is_dir() {
+            fs::remove_dir_all(&target).map_err(|e| format!("Erreur remplacement de {}: {}", name, e))?;
+        }
+        file_tree::copy_tree(&files.join(name), &target)?;
+    }
+
+    let properties_path = server_dir.join("server.properties");
+    // Appliquer les valeurs du modèle sur le fichier généré, sans perdre ses commentaires ni son ordre
+    let mut properties = PropertiesFile::load_or_default(&properties_path);
+    for (key, value) in &manifest.properties {
+        properties.set(key, value);
+    }
+    properties.set("server-port", &port.to_string());
+    let rcon_port = automation::find_available_port(port.saturating_add(RCON_PORT_OFFSET))?;
+    properties.set("rcon.port", &rcon_port.to_string());
+    if let Some(motd) = &overrides.motd {
+        properties.set("motd", motd);
+    }
+    if let Some(max_players) = overrides.max_players {
+        properties.set("max-players", &max_players.to_string());
+    }
+    properties.save(&properties_path)?;
+
+    // Avec un profil JVM, les arguments sont régénérés par l'appelant
+    if manifest.jvm_profile.is_none() && !manifest.jvm_args.is_empty() {
+        fs::write(server_dir.join("user_jvm_args.txt"), manifest.jvm_args.join("\n") + "\n")
+            .map_err(|e| format!("Erreur écriture user_jvm_args.txt: {}", e))?;
+    }
+
+    Ok(())
+}
+
+// Exporter un modèle en une seule archive (template.json + files/)
+pub fn export(id: &str, output_path: &str) -> Result<String, String> {
+    let dir = template_dir(id)?;
+    load(id)?;
+
+    let file = fs::File::create(output_path)
+        .map_err(|e| format!("Erreur création archive: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    add_to_zip(&mut zip, &dir, &dir, options)?;
+    zip.finish().map_err(|e| format!("Erreur finalisation archive: {}", e))?;
+
+    println!("Modèle exporté: {}", output_path);
+    Ok(output_path.to_string())
+}
+
+fn add_to_zip<W: Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    path: &Path,
+    base: &Path,
+    options: zip::write::FileOptions,
+) -> Result<(), String> {
+    if path.is_dir() {
+        for entry in fs::read_dir(path).map_err(|e| format!("Erreur lecture dossier: {}", e))?.flatten() {
+            add_to_zip(zip, &entry.path(), base, options)?;
+        }
+        return Ok(());
+    }
+
+    // Séparateurs "/" dans l'archive quel que soit le système
+    let relative = path
+        .strip_prefix(base)
+        .map_err(|e| format!("Erreur chemin relatif: {}", e))?
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    zip.start_file(relative, options)
+        .map_err(|e| format!("Erreur ajout fichier à l'archive: {}", e))?;
+    let content = fs::read(path).map_err(|e| format!("Erreur lecture fichier: {}", e))?;
+    zip.write_all(&content)
+        .map_err(|e| format!("Erreur écriture archive: {}", e))
+}
+
+// Importer une archive de modèle (un nouvel identifiant est attribué)
+pub fn import(archive_path: &str) -> Result<TemplateManifest, String> {
+    let file = fs::File::open(archive_path)
+        .map_err(|e| format!("Erreur ouverture archive: {}", e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Erreur lecture archive: {}", e))?;
+
+    let mut manifest: TemplateManifest = {
+        let mut entry = archive
+            .by_name(MANIFEST_FILE)
+            .map_err(|_| "Archive invalide: template.json manquant".to_string())?;
+        let mut content = String::new();
+        entry.read_to_string(&mut content)
+            .map_err(|e| format!("Erreur lecture template.json: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("template.json invalide: {}", e))?
+    };
+    if manifest.format > FORMAT_VERSION {
+        return Err(format!("Format de modèle {} non supporté par cette version", manifest.format));
+    }
+    validate_manifest(&manifest)?;
+
+    manifest.id = uuid::Uuid::new_v4().to_string();
+    let dir = template_dir(&manifest.id)?;
+
+    let extracted = (|| {
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)
+                .map_err(|e| format!("Erreur extraction: {}", e))?;
+            // Refuser les chemins qui sortent du dossier du modèle
+            let Some(relative) = entry.enclosed_name().map(|p| p.to_path_buf()) else { continue };
+            if !relative.starts_with(FILES_DIR) || entry.is_dir() {
+                continue;
+            }
+
+            let target = dir.join(&relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Erreur création dossier: {}", e))?;
+            }
+            let mut out = fs::File::create(&target)
+                .map_err(|e| format!("Erreur création fichier: {}", e))?;
+            std::io::copy(&mut entry, &mut out)
+                .map_err(|e| format!("Erreur extraction fichier: {}", e))?;
+        }
+        fs::create_dir_all(dir.join(FILES_DIR)).map_err(|e| format!("Erreur création dossier: {}", e))?;
+        save_manifest(&dir, &manifest)
+    })();
+    if let Err(e) = extracted {
+        let _ = fs::remove_dir_all(&dir);
+        return Err(e);
+    }
+
+    println!("📐 Modèle \"{}\" importé", manifest.name);
+    Ok(manifest)
+}
@@ -0,0 +1,415 @@
+// Lecture/édition des fichiers TOML de configuration des mods (format NightConfig de Forge/NeoForge) :
+// les valeurs modifiées sont réécrites en place, le reste du fichier (commentaires, tabulations) est conservé
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+pub struct TomlFile {
+    lines: Vec<String>,
+    newline: &'static str,
+    trailing_newline: bool,
+}
+
+// En-tête de table ([general], [general.performance])
+#[derive(Debug, Clone)]
+pub struct TomlTable {
+    pub path: Vec<String>,
+    // Lignes de commentaire juste au-dessus, sans le '#'
+    pub comments: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TomlEntry {
+    pub table: Vec<String>,
+    pub key: Vec<String>,
+    // Texte brut de la valeur (peut couvrir plusieurs lignes pour un tableau)
+    pub raw: String,
+    // None pour les valeurs non prises en charge (table en ligne, date, chaîne multiligne)
+    pub value: Option<serde_json::Value>,
+    pub comments: Vec<String>,
+    first_line: usize,
+    value_start: usize,
+    last_line: usize,
+    value_end: usize,
+}
+
+impl TomlEntry {
+    // Chemin complet "table.clé"
+    pub fn path(&self) -> String {
+        self.table.iter().chain(self.key.iter()).cloned().collect::<Vec<_>>().join(".")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TomlOutline {
+    pub tables: Vec<TomlTable>,
+    pub entries: Vec<TomlEntry>,
+}
+
+// Petit analyseur de valeurs TOML
+struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn skip_blank(&mut self) {
+        loop {
+            let trimmed = self.rest().trim_start();
+            self.pos = self.text.len() - trimmed.len();
+            if trimmed.starts_with('#') {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn value(&mut self) -> Option<serde_json::Value> {
+        self.skip_blank();
+        let rest = self.rest();
+        if rest.starts_with("\"\"\"") || rest.starts_with("'''") {
+            return None;
+        }
+        match rest.chars().next()? {
+            '"' => self.basic_string().map(serde_json::Value::String),
+            '\'' => {
+                let end = rest[1..].find('\'')? + 1;
+                self.pos += end + 1;
+                Some(serde_json::Value::String(rest[1..end].to_string()))
+            }
+            '[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_blank();
+                    if self.rest().starts_with(']') {
+                        self.pos += 1;
+                        return Some(serde_json::Value::Array(items));
+                    }
+                    items.push(self.value()?);
+                    self.skip_blank();
+                    if self.rest().starts_with(',') {
+                        self.pos += 1;
+                    } else if !self.rest().starts_with(']') {
+                        return None;
+                    }
+                }
+            }
+            _ => {
+                let len = rest.find(|c: char| c == ',' || c == ']' || c == '#' || c.is_whitespace()).unwrap_or(rest.len());
+                let token = &rest[..len];
+                self.pos += len;
+                scalar(token)
+            }
+        }
+    }
+
+    fn basic_string(&mut self) -> Option<String> {
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices().skip(1);
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Some(out);
+                }
+                '\\' => match chars.next()?.1 {
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    'r' => out.push('\r'),
+                    'b' => out.push('\x08'),
+                    'f' => out.push('\x0c'),
+                    escape @ ('u' | 'U') => {
+                        let len = if escape == 'u' { 4 } else { 8 };
+                        let hex: String = chars.by_ref().take(len).map(|(_, c)| c).collect();
+                        out.push(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)?);
+                    }
+                    other => out.push(other),
+                },
+                '\n' => return None,
+                c => out.push(c),
+            }
+        }
+        None
+    }
+}
+
+fn scalar(token: &str) -> Option<serde_json::Value> {
+    match token {
+        "true" => return Some(serde_json::Value::Bool(true)),
+        "false" => return Some(serde_json::Value::Bool(false)),
+        _ => {}
+    }
+    let digits = token.replace('_', "");
+    if let Ok(number) = digits.parse::<i64>() {
+        return Some(number.into());
+    }
+    // Les dates et heures TOML contiennent aussi des chiffres : seuls les nombres décimaux sont acceptés
+    if digits.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-')) {
+        return digits.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(serde_json::Value::Number);
+    }
+    None
+}
+
+// Écrire une valeur JSON en TOML (float : écrire les entiers avec ".0")
+pub fn format_value(value: &serde_json::Value, float: bool) -> Option<String> {
+    match value {
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        serde_json::Value::Number(n) if float => {
+            let number = n.as_f64()?;
+            Some(if number.fract() == 0.0 { format!("{:.1}", number) } else { number.to_string() })
+        }
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::String(s) => {
+            let mut out = String::from("\"");
+            for c in s.chars() {
+                match c {
+                    '"' => out.push_str("\\\""),
+                    '\\' => out.push_str("\\\\"),
+                    '\n' => out.push_str("\\n"),
+                    '\t' => out.push_str("\\t"),
+                    '\r' => out.push_str("\\r"),
+                    c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+                    c => out.push(c),
+                }
+            }
+            out.push('"');
+            Some(out)
+        }
+        serde_json::Value::Array(items) => {
+            let items: Option<Vec<String>> = items.iter().map(|item| format_value(item, false)).collect();
+            Some(format!("[{}]", items?.join(", ")))
+        }
+        _ => None,
+    }
+}
+
+// Clé pointée avant le '=' ("a.\"b c\".d") ; renvoie les segments et la position juste après le '='
+fn split_key(line: &str) -> Option<(Vec<String>, usize)> {
+    let mut segments = Vec::new();
+    let mut pos = 0;
+    loop {
+        pos += line[pos..].len() - line[pos..].trim_start().len();
+        let rest = &line[pos..];
+        let segment = match rest.chars().next()? {
+            '"' => {
+                let end = rest[1..].find('"')? + 1;
+                pos += end + 1;
+                rest[1..end].to_string()
+            }
+            '\'' => {
+                let end = rest[1..].find('\'')? + 1;
+                pos += end + 1;
+                rest[1..end].to_string()
+            }
+            _ => {
+                let len = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-')).unwrap_or(rest.len());
+                if len == 0 {
+                    return None;
+                }
+                pos += len;
+                rest[..len].to_string()
+            }
+        };
+        segments.push(segment);
+
+        pos += line[pos..].len() - line[pos..].trim_start().len();
+        match line[pos..].chars().next()? {
+            '.' => pos += 1,
+            '=' => return Some((segments, pos + 1)),
+            _ => return None,
+        }
+    }
+}
+
+impl TomlFile {
+    pub fn parse(content: &str) -> Self {
+        TomlFile {
+            lines: content.lines().map(|line| line.to_string()).collect(),
+            newline: if content.contains("\r\n") { "\r\n" } else { "\n" },
+            trailing_newline: content.is_empty() || content.ends_with('\n'),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+        Ok(Self::parse(&content))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_string()).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))
+    }
+
+    // Fin de la valeur commençant en (line, start) : ligne et position après son dernier caractère
+    fn value_end(&self, line: usize, start: usize) -> Option<(usize, usize)> {
+        let mut depth = 0i32;
+        let mut string: Option<&str> = None;
+        let mut end = (line, start);
+        let mut seen = false;
+
+        for (index, text) in self.lines.iter().enumerate().skip(line) {
+            let mut j = if index == line { start } else { 0 };
+            while j < text.len() {
+                let rest = &text[j..];
+                if let Some(delimiter) = string {
+                    if delimiter.starts_with('"') && rest.starts_with('\\') {
+                        // Le caractère échappé peut être multi-octets (\é)
+                        j += 1 + rest[1..].chars().next().map_or(0, char::len_utf8);
+                    } else if rest.starts_with(delimiter) {
+                        j += delimiter.len();
+                        string = None;
+                        end = (index, j);
+                    } else {
+                        j += rest.chars().next().map_or(1, char::len_utf8);
+                    }
+                    continue;
+                }
+
+                let c = rest.chars().next().unwrap_or(' ');
+                match c {
+                    '#' => break,
+                    ' ' | '\t' => {
+                        j += 1;
+                        continue;
+                    }
+                    '"' | '\'' => {
+                        let delimiter = if rest.starts_with("\"\"\"") {
+                            "\"\"\""
+                        } else if rest.starts_with("'''") {
+                            "'''"
+                        } else if c == '"' {
+                            "\""
+                        } else {
+                            "'"
+                        };
+                        string = Some(delimiter);
+                        seen = true;
+                        j += delimiter.len();
+                        continue;
+                    }
+                    '[' | '{' => depth += 1,
+                    ']' | '}' => depth -= 1,
+                    _ => {}
+                }
+                seen = true;
+                j += c.len_utf8();
+                end = (index, j);
+            }
+
+            // Une chaîne simple ne traverse pas les lignes
+            if string.is_some_and(|d| d.len() == 1) {
+                string = None;
+            }
+            if string.is_none() && depth <= 0 && seen {
+                return Some(end);
+            }
+        }
+
+        None
+    }
+
+    // Tables et valeurs du fichier, avec les commentaires qui les précèdent
+    pub fn outline(&self) -> TomlOutline {
+        let mut outline = TomlOutline::default();
+        let mut table: Vec<String> = Vec::new();
+        let mut array_table = false;
+        let mut comments: Vec<String> = Vec::new();
+        let mut i = 0;
+
+        while i < self.lines.len() {
+            let line = &self.lines[i];
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() {
+                comments.clear();
+            } else if let Some(comment) = trimmed.strip_prefix('#') {
+                comments.push(comment.trim().to_string());
+            } else if trimmed.starts_with('[') {
+                // Tableaux de tables ([[...]]) : ignorés, leurs clés ne sont pas adressables par chemin
+                array_table = trimmed.starts_with("[[");
+                let header = trimmed.trim_start_matches('[');
+                let header = &header[..header.find(']').unwrap_or(header.len())];
+                table = split_key(&format!("{}=", header)).map(|(path, _)| path).unwrap_or_default();
+                if !array_table {
+                    outline.tables.push(TomlTable { path: table.clone(), comments: std::mem::take(&mut comments) });
+                }
+                comments.clear();
+            } else if let Some((key, value_start)) = split_key(line) {
+                let Some((last_line, value_end)) = self.value_end(i, value_start) else {
+                    comments.clear();
+                    i += 1;
+                    continue;
+                };
+                let raw = if last_line == i {
+                    self.lines[i][value_start..value_end].trim().to_string()
+                } else {
+                    let mut parts = vec![&self.lines[i][value_start..]];
+                    parts.extend(self.lines[i + 1..last_line].iter().map(|l| l.as_str()));
+                    parts.push(&self.lines[last_line][..value_end]);
+                    parts.join("\n").trim().to_string()
+                };
+                if !array_table {
+                    let mut cursor = Cursor { text: &raw, pos: 0 };
+                    let value = cursor.value().filter(|_| {
+                        cursor.skip_blank();
+                        cursor.rest().is_empty()
+                    });
+                    outline.entries.push(TomlEntry {
+                        table: table.clone(),
+                        key,
+                        raw,
+                        value,
+                        comments: std::mem::take(&mut comments),
+                        first_line: i,
+                        value_start,
+                        last_line,
+                        value_end,
+                    });
+                }
+                comments.clear();
+                i = last_line;
+            } else {
+                comments.clear();
+            }
+            i += 1;
+        }
+
+        outline
+    }
+
+    pub fn get(&self, path: &str) -> Option<TomlEntry> {
+        self.outline().entries.into_iter().find(|entry| entry.path() == path)
+    }
+
+    // Remplacer la valeur d'une clé existante par un texte TOML ; renvoie true si le fichier change
+    pub fn set(&mut self, path: &str, value: &str) -> Result<bool, String> {
+        let entry = self.get(path).ok_or_else(|| format!("Clé introuvable: {}", path))?;
+        if entry.raw == value {
+            return Ok(false);
+        }
+
+        let first = &self.lines[entry.first_line];
+        let spacing = first[entry.value_start..].len() - first[entry.value_start..].trim_start().len();
+        let prefix = &first[..entry.value_start + spacing];
+        let suffix = &self.lines[entry.last_line][entry.value_end..];
+        let line = format!("{}{}{}", prefix, value, suffix);
+
+        self.lines.splice(entry.first_line..=entry.last_line, [line]);
+        Ok(true)
+    }
+}
+
+impl std::fmt::Display for TomlFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.lines.join(self.newline))?;
+        if self.trailing_newline && !self.lines.is_empty() {
+            write!(f, "{}", self.newline)?;
+        }
+        Ok(())
+    }
+}
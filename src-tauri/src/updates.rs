@@ -0,0 +1,449 @@
+// Détection des mises à jour : builds du loader pour la même version Minecraft,
+// puis versions Minecraft plus récentes (même branche d'abord)
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use std::path::Path;
+
+use crate::endpoints::{self, Upstream};
+use crate::paper_family::PaperProject;
+
+// Une mise à jour proposée
+#[derive(Debug, Clone, Serialize)]
+pub struct AvailableUpdate {
+    // Version à transmettre à update_server
+    pub version: String,
+    pub minecraft_version: String,
+    // Build du loader ou du serveur (Forge 47.3.0, NeoForge 20.4.80-beta...)
+    pub build: Option<String>,
+    // "recommended", "latest", "default", "experimental", "patch" ou "release"
+    pub channel: String,
+    pub changelog_url: Option<String>,
+    // Messages de commit du build (Paper, Purpur, Mohist)
+    pub changes: Vec<String>,
+    pub published_at: Option<String>,
+}
+
+// Rapport complet pour un serveur
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateReport {
+    pub server_type: String,
+    pub current_version: String,
+    pub minecraft_version: String,
+    pub build: Option<String>,
+    // Build installé introuvable : aucun build n'est présenté comme plus récent
+    pub build_unknown: bool,
+    // Nouveaux builds pour la même version Minecraft
+    pub build_updates: Vec<AvailableUpdate>,
+    // Versions Minecraft plus récentes : dernière de la même branche puis dernière tout court
+    pub minecraft_updates: Vec<AvailableUpdate>,
+}
+
+impl UpdateReport {
+    fn new(server_type: &str, current_version: &str, minecraft_version: &str, build: Option<String>) -> Self {
+        UpdateReport {
+            server_type: server_type.to_string(),
+            current_version: current_version.to_string(),
+            minecraft_version: minecraft_version.to_string(),
+            build,
+            build_unknown: false,
+            build_updates: Vec::new(),
+            minecraft_updates: Vec::new(),
+        }
+    }
+
+    // Mise à jour suggérée sans changer de branche Minecraft (build recommandé ou stable en priorité)
+    pub fn suggested(&self) -> Option<&AvailableUpdate> {
+        self.build_updates
+            .iter()
+            .find(|u| u.channel == "recommended" || u.channel == "default")
+            .or_else(|| self.build_updates.first())
+            .or_else(|| self.minecraft_updates.iter().find(|u| u.channel == "patch"))
+    }
+}
+
+// Comparer deux versions pointées ("1.20.1", "47.2.0", "20.4.80-beta") composante par composante
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parse = |v: &str| -> Vec<u64> {
+        v.split(['.', '-', '_'])
+            .map_while(|part| part.parse::<u64>().ok())
+            .collect()
+    };
+    let (left, right) = (parse(a), parse(b));
+
+    for i in 0..left.len().max(right.len()) {
+        let ordering = left.get(i).unwrap_or(&0).cmp(right.get(i).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    Ordering::Equal
+}
+
+// Branche Minecraft : "1.20.1" -> "1.20"
+fn minecraft_line(version: &str) -> String {
+    version.split('.').take(2).collect::<Vec<_>>().join(".")
+}
+
+fn is_newer(candidate: &str, current: &str) -> bool {
+    compare_versions(candidate, current) == Ordering::Greater
+}
+
+// Ajouter la dernière version de la même branche puis la dernière version tout court
+fn push_minecraft_updates<F>(report: &mut UpdateReport, versions: &[String], make: F)
+where
+    F: Fn(&str, &str) -> AvailableUpdate,
+{
+    let current = report.minecraft_version.clone();
+    let line = minecraft_line(&current);
+
+    let newest_patch = versions
+        .iter()
+        .filter(|v| minecraft_line(v) == line && is_newer(v, &current))
+        .max_by(|a, b| compare_versions(a, b));
+    let newest = versions
+        .iter()
+        .filter(|v| is_newer(v, &current))
+        .max_by(|a, b| compare_versions(a, b));
+
+    if let Some(patch) = newest_patch {
+        report.minecraft_updates.push(make(patch, "patch"));
+    }
+    if let Some(latest) = newest {
+        if Some(latest) != newest_patch {
+            report.minecraft_updates.push(make(latest, "release"));
+        }
+    }
+}
+
+// Nombre maximum de builds listés avec leurs changements
+const MAX_BUILD_UPDATES: usize = 20;
+
+pub async fn check(current_version: &str, server_type: &str, server_path: Option<&Path>) -> Result<UpdateReport, String> {
+    if let Some(project) = PaperProject::from_server_type(server_type) {
+        return check_paper_family(project, current_version, server_path).await;
+    }
+
+    match server_type {
+        "vanilla" => check_vanilla(current_version).await,
+        "forge" => check_forge(current_version).await,
+        "neoforge" => check_neoforge(current_version).await,
+        "mohist" => check_mohist(current_version, server_path).await,
+        _ => Ok(UpdateReport::new(server_type, current_version, current_version, None)),
+    }
+}
+
+// Séparer "1.20.1-196" en version Minecraft et numéro de build
+pub fn split_build(version: &str) -> (String, Option<u32>) {
+    match version.rsplit_once('-') {
+        Some((minecraft, build)) if minecraft.contains('.') => match build.parse::<u32>() {
+            Ok(build) => (minecraft.to_string(), Some(build)),
+            Err(_) => (version.to_string(), None),
+        },
+        _ => (version.to_string(), None),
+    }
+}
+
+// ========== VANILLA ==========
+
+async fn check_vanilla(current_version: &str) -> Result<UpdateReport, String> {
+    let mut report = UpdateReport::new("vanilla", current_version, current_version, None);
+    let releases = crate::version_catalog::version_ids("release").await?;
+
+    push_minecraft_updates(&mut report, &releases, |version, channel| AvailableUpdate {
+        version: version.to_string(),
+        minecraft_version: version.to_string(),
+        build: None,
+        channel: channel.to_string(),
+        changelog_url: Some(format!("https://minecraft.wiki/w/Java_Edition_{}", version)),
+        changes: Vec::new(),
+        published_at: None,
+    });
+
+    Ok(report)
+}
+
+// ========== FORGE ==========
+
+fn forge_changelog(minecraft_version: &str, build: &str) -> String {
+    endpoints::url(
+        Upstream::ForgeMaven,
+        &format!(
+            "/net/minecraftforge/forge/{mc}-{b}/forge-{mc}-{b}-changelog.txt",
+            mc = minecraft_version,
+            b = build
+        ),
+    )
+}
+
+fn forge_update(minecraft_version: &str, build: &str, channel: &str) -> AvailableUpdate {
+    AvailableUpdate {
+        version: format!("{}-{}", minecraft_version, build),
+        minecraft_version: minecraft_version.to_string(),
+        build: Some(build.to_string()),
+        channel: channel.to_string(),
+        changelog_url: Some(forge_changelog(minecraft_version, build)),
+        changes: Vec::new(),
+        published_at: None,
+    }
+}
+
+// Lire promotions_slim.json : {"promos": {"1.20.1-recommended": "47.2.0", "1.20.1-latest": "47.3.0"}}
+async fn forge_promotions() -> Result<HashMap<String, HashMap<String, String>>, String> {
+    let url = endpoints::url(Upstream::ForgeMaven, "/net/minecraftforge/forge/promotions_slim.json");
+    let data = endpoints::fetch_json(&url).await?;
+
+    let mut promotions: HashMap<String, HashMap<String, String>> = HashMap::new();
+    if let Some(promos) = data["promos"].as_object() {
+        for (key, build) in promos {
+            let (Some((minecraft, channel)), Some(build)) = (key.rsplit_once('-'), build.as_str()) else { continue };
+            promotions
+                .entry(minecraft.to_string())
+                .or_default()
+                .insert(channel.to_string(), build.to_string());
+        }
+    }
+
+    Ok(promotions)
+}
+
+async fn check_forge(current_version: &str) -> Result<UpdateReport, String> {
+    // Format Forge : "1.20.1-47.2.0" (le build peut être absent si seul le MC est connu)
+    let (minecraft, build) = match current_version.split_once('-') {
+        Some((mc, build)) => (mc.to_string(), Some(build.to_string())),
+        None => (current_version.to_string(), None),
+    };
+    let mut report = UpdateReport::new("forge", current_version, &minecraft, build.clone());
+    report.build_unknown = build.is_none();
+    let promotions = forge_promotions().await?;
+
+    if let (Some(channels), Some(build)) = (promotions.get(&minecraft), build.as_deref()) {
+        for channel in ["recommended", "latest"] {
+            let Some(candidate) = channels.get(channel) else { continue };
+            let newer = is_newer(candidate, build);
+            let duplicate = report.build_updates.iter().any(|u| u.build.as_deref() == Some(candidate.as_str()));
+            if newer && !duplicate {
+                report.build_updates.push(forge_update(&minecraft, candidate, channel));
+            }
+        }
+    }
+
+    // Build promu (recommandé, sinon dernier) ; une version Minecraft sans build n'est pas proposée
+    let promoted: HashMap<String, String> = promotions
+        .iter()
+        .filter_map(|(mc, channels)| {
+            let build = channels.get("recommended").or_else(|| channels.get("latest"))?;
+            (!build.is_empty()).then(|| (mc.clone(), build.clone()))
+        })
+        .collect();
+    let versions: Vec<String> = promoted.keys().cloned().collect();
+    push_minecraft_updates(&mut report, &versions, |mc, channel| forge_update(mc, &promoted[mc], channel));
+
+    Ok(report)
+}
+
+// ========== NEOFORGE ==========
+
+// NeoForge encode la version Minecraft dans sa propre version : 20.4.80 -> 1.20.4, 21.0.10 -> 1.21.
+// Seule exception, NeoForge 1.20.1 garde le format Forge : 1.20.1-47.1.84
+pub fn neoforge_minecraft_version(neoforge_version: &str) -> Option<String> {
+    if let Some((minecraft, _)) = neoforge_version.split_once('-').filter(|(prefix, _)| prefix.starts_with("1.")) {
+        return Some(minecraft.to_string());
+    }
+
+    let mut parts = neoforge_version.split('.');
+    let major = parts.next()?.parse::<u32>().ok()?;
+    let minor = parts.next()?.parse::<u32>().ok()?;
+    Some(if minor == 0 {
+        format!("1.{}", major)
+    } else {
+        format!("1.{}.{}", major, minor)
+    })
+}
+
+fn neoforge_update(version: &str, channel: &str) -> AvailableUpdate {
+    AvailableUpdate {
+        version: version.to_string(),
+        minecraft_version: neoforge_minecraft_version(version).unwrap_or_default(),
+        build: Some(version.to_string()),
+        channel: channel.to_string(),
+        changelog_url: Some(endpoints::url(
+            Upstream::NeoForgeMaven,
+            &format!("/releases/net/neoforged/neoforge/{v}/neoforge-{v}-changelog.txt", v = version),
+        )),
+        changes: Vec::new(),
+        published_at: None,
+    }
+}
+
+// Liste des versions publiées dans maven-metadata.xml
+async fn neoforge_versions() -> Result<Vec<String>, String> {
+    let url = endpoints::url(Upstream::NeoForgeMaven, "/releases/net/neoforged/neoforge/maven-metadata.xml");
+    let bytes = endpoints::fetch_bytes(&url).await?;
+    let xml = String::from_utf8_lossy(&bytes);
+
+    let re = regex::Regex::new(r"<version>([^<]+)</version>").map_err(|e| e.to_string())?;
+    Ok(re.captures_iter(&xml).map(|c| c[1].trim().to_string()).collect())
+}
+
+async fn check_neoforge(current_version: &str) -> Result<UpdateReport, String> {
+    let minecraft = neoforge_minecraft_version(current_version)
+        .ok_or(format!("Version NeoForge invalide: {}", current_version))?;
+    let mut report = UpdateReport::new("neoforge", current_version, &minecraft, Some(current_version.to_string()));
+    let versions = neoforge_versions().await?;
+
+    // Builds pour la même version Minecraft : le dernier stable est "recommended", le dernier tout court "latest"
+    let same_minecraft: Vec<&String> = versions
+        .iter()
+        .filter(|v| neoforge_minecraft_version(v).as_deref() == Some(minecraft.as_str()))
+        .collect();
+    let newest_stable = same_minecraft
+        .iter()
+        .filter(|v| !v.contains('-'))
+        .max_by(|a, b| compare_versions(a, b));
+    let newest = same_minecraft.iter().max_by(|a, b| compare_versions(a, b));
+
+    if let Some(stable) = newest_stable {
+        if is_newer(stable, current_version) {
+            report.build_updates.push(neoforge_update(stable, "recommended"));
+        }
+    }
+    if let Some(latest) = newest {
+        if is_newer(latest, current_version) && Some(latest) != newest_stable {
+            report.build_updates.push(neoforge_update(latest, "latest"));
+        }
+    }
+
+    // Versions Minecraft plus récentes, chacune avec son dernier build NeoForge
+    let mut latest_per_minecraft: HashMap<String, String> = HashMap::new();
+    for version in &versions {
+        let Some(mc) = neoforge_minecraft_version(version) else { continue };
+        let entry = latest_per_minecraft.entry(mc).or_insert_with(|| version.clone());
+        if is_newer(version, entry) {
+            *entry = version.clone();
+        }
+    }
+    let minecraft_versions: Vec<String> = latest_per_minecraft.keys().cloned().collect();
+    push_minecraft_updates(&mut report, &minecraft_versions, |mc, channel| {
+        neoforge_update(&latest_per_minecraft[mc], channel)
+    });
+
+    Ok(report)
+}
+
+// ========== PAPER / FOLIA / PURPUR ==========
+
+async fn check_paper_family(project: PaperProject, current_version: &str, server_path: Option<&Path>) -> Result<UpdateReport, String> {
+    // Build installé : "1.20.1-196" dans la config, sinon version_history.json ou nom du JAR
+    let (mut minecraft, mut build) = split_build(current_version);
+    if build.is_none() {
+        if let Some((installed_mc, installed_build)) = server_path.and_then(|p| crate::paper_family::installed_build(p, project)) {
+            minecraft = installed_mc;
+            build = Some(installed_build);
+        }
+    }
+
+    let mut report = UpdateReport::new(project.id(), current_version, &minecraft, build.map(|b| b.to_string()));
+    report.build_unknown = build.is_none();
+
+    let builds = match build {
+        Some(build) => crate::paper_family::builds_after(project, &minecraft, build, MAX_BUILD_UPDATES).await?,
+        None => Vec::new(),
+    };
+    for info in builds {
+        report.build_updates.push(AvailableUpdate {
+            version: format!("{}-{}", minecraft, info.build),
+            minecraft_version: minecraft.clone(),
+            build: Some(info.build.to_string()),
+            channel: info.channel,
+            changelog_url: None,
+            changes: info.changes,
+            published_at: info.time,
+        });
+    }
+
+    // Les API listent aussi les pré-versions : on ne garde que les versions numériques
+    let versions: Vec<String> = crate::paper_family::list_versions(project)
+        .await?
+        .into_iter()
+        .filter(|v| v.chars().all(|c| c.is_ascii_digit() || c == '.'))
+        .collect();
+    push_minecraft_updates(&mut report, &versions, |version, channel| AvailableUpdate {
+        version: version.to_string(),
+        minecraft_version: version.to_string(),
+        build: None,
+        channel: channel.to_string(),
+        changelog_url: None,
+        changes: Vec::new(),
+        published_at: None,
+    });
+
+    Ok(report)
+}
+
+// ========== MOHIST ==========
+
+async fn check_mohist(current_version: &str, server_path: Option<&Path>) -> Result<UpdateReport, String> {
+    let (minecraft, mut build) = split_build(current_version);
+    let builds = crate::mohist::list_builds(&minecraft).await?;
+    if build.is_none() {
+        build = server_path.and_then(|p| crate::mohist::installed_build(p, &minecraft, &builds));
+    }
+
+    let mut report = UpdateReport::new("mohist", current_version, &minecraft, build.map(|b| b.to_string()));
+    report.build_unknown = build.is_none();
+
+    for info in builds
+        .iter()
+        .filter(|b| build.map(|current| b.number > current).unwrap_or(false))
+        .take(MAX_BUILD_UPDATES)
+    {
+        let mut changes = Vec::new();
+        if let Some(sha) = &info.git_sha {
+            changes.push(format!("Commit {}", &sha[..sha.len().min(7)]));
+        }
+        if let Some(forge) = &info.forge_version {
+            changes.push(format!("Forge {}", forge));
+        }
+        report.build_updates.push(AvailableUpdate {
+            version: format!("{}-{}", minecraft, info.number),
+            minecraft_version: minecraft.clone(),
+            build: Some(info.number.to_string()),
+            channel: "default".to_string(),
+            changelog_url: info
+                .git_sha
+                .as_ref()
+                .map(|sha| format!("https://github.com/MohistMC/Mohist/commit/{}", sha)),
+            changes,
+            published_at: info.created_at.clone(),
+        });
+    }
+
+    let versions = crate::mohist::list_versions().await?;
+    push_minecraft_updates(&mut report, &versions, |version, channel| AvailableUpdate {
+        version: version.to_string(),
+        minecraft_version: version.to_string(),
+        build: None,
+        channel: channel.to_string(),
+        changelog_url: None,
+        changes: Vec::new(),
+        published_at: None,
+    });
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neoforge_minecraft_version_from_build() {
+        assert_eq!(neoforge_minecraft_version("20.4.80").as_deref(), Some("1.20.4"));
+        assert_eq!(neoforge_minecraft_version("21.0.10").as_deref(), Some("1.21"));
+        assert_eq!(neoforge_minecraft_version("20.2.3-beta").as_deref(), Some("1.20.2"));
+        assert_eq!(neoforge_minecraft_version("1.20.1-47.1.84").as_deref(), Some("1.20.1"));
+    }
+}
@@ -0,0 +1,331 @@
+// Catalogue des versions Minecraft basé sur le manifest Mojang, avec cache disque (TTL + ETag)
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::app_paths;
+use crate::downloader::Checksum;
+use crate::endpoints::{self, Fetched, Upstream};
+
+const MANIFEST_PATH: &str = "/mc/game/version_manifest_v2.json";
+// Le manifest est revalidé au plus une fois par heure
+const CATALOG_TTL_SECS: i64 = 3600;
+// Nombre de JSON de version récupérés en parallèle pour résoudre la version Java
+const JAVA_RESOLVE_BATCH: usize = 8;
+
+lazy_static::lazy_static! {
+    // Verrou sur version_catalog.json : revalidation du manifest et résolutions Java en parallèle
+    static ref CACHE_LOCK: Arc<Mutex<()>> = Arc::new(Mutex::new(()));
+}
+
+// Une version du manifest Mojang
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogVersion {
+    pub id: String,
+    // "release", "snapshot", "old_beta" ou "old_alpha"
+    pub kind: String,
+    pub release_time: String,
+    pub url: String,
+    pub sha1: Option<String>,
+    // javaVersion.majorVersion du JSON de version (connu une fois ce JSON récupéré)
+    pub java_major: Option<u32>,
+}
+
+// Contenu de cache/version_catalog.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CatalogCache {
+    etag: Option<String>,
+    fetched_at: i64,
+    latest_release: Option<String>,
+    latest_snapshot: Option<String>,
+    versions: Vec<CatalogVersion>,
+}
+
+// Catalogue renvoyé à l'interface
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionCatalog {
+    pub latest_release: Option<String>,
+    pub latest_snapshot: Option<String>,
+    pub fetched_at: i64,
+    // true si le manifest n'a pas pu être revalidé et que le cache expiré est utilisé
+    pub stale: bool,
+    pub versions: Vec<CatalogVersion>,
+}
+
+// Filtres du catalogue (par défaut : releases uniquement)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct VersionFilter {
+    #[serde(default)]
+    pub kinds: Vec<String>,
+    pub limit: Option<usize>,
+    // Récupérer les JSON de version manquants pour remplir java_major
+    #[serde(default)]
+    pub include_java: bool,
+}
+
+// Détails d'une version (JSON de version Mojang)
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionDetails {
+    pub id: String,
+    pub kind: String,
+    pub release_time: String,
+    pub java_major: u32,
+    pub server_url: Option<String>,
+    pub server_sha1: Option<String>,
+}
+
+impl VersionDetails {
+    pub fn server_checksum(&self) -> Option<Checksum> {
+        self.server_sha1
+            .as_deref()
+            .and_then(|sha1| Checksum::from_algorithm("sha1", sha1))
+    }
+}
+
+fn cache_path() -> Result<PathBuf, String> {
+    Ok(app_paths::app_dir()?.join("cache").join("version_catalog.json"))
+}
+
+// Cache absent : premier lancement ; dossier de données introuvable ou cache illisible : signalé
+fn load_cache() -> Option<CatalogCache> {
+    let path = cache_path().map_err(|e| println!("⚠️ Cache du catalogue indisponible: {}", e)).ok()?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(e) => {
+            println!("⚠️ Erreur lecture {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    serde_json::from_str(&content).map_err(|e| println!("⚠️ Cache du catalogue invalide, ignoré: {}", e)).ok()
+}
+
+fn save_cache(cache: &CatalogCache) -> Result<(), String> {
+    let path = cache_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Erreur création dossier cache: {}", e))?;
+    }
+    let json = serde_json::to_string(cache)
+        .map_err(|e| format!("Erreur sérialisation catalogue: {}", e))?;
+
+    // Écriture atomique : le cache est relu sans verrou
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, json)
+        .map_err(|e| format!("Erreur écriture catalogue: {}", e))?;
+    fs::rename(&tmp_path, &path)
+        .map_err(|e| format!("Erreur écriture catalogue: {}", e))
+}
+
+// Construire le cache à partir du manifest, en conservant les versions Java déjà connues
+fn parse_manifest(bytes: &[u8], etag: Option<String>, previous: Option<&CatalogCache>) -> Result<CatalogCache, String> {
+    let manifest: serde_json::Value = serde_json::from_slice(bytes)
+        .map_err(|e| format!("Erreur JSON manifest: {}", e))?;
+
+    let known_java = |id: &str| {
+        previous.and_then(|p| p.versions.iter().find(|v| v.id == id).and_then(|v| v.java_major))
+    };
+
+    let versions = manifest["versions"]
+        .as_array()
+        .ok_or("Versions non trouvées dans le manifest")?
+        .iter()
+        .filter_map(|v| {
+            let id = v["id"].as_str()?.to_string();
+            Some(CatalogVersion {
+                java_major: known_java(&id),
+                kind: v["type"].as_str().unwrap_or("release").to_string(),
+                release_time: v["releaseTime"].as_str().unwrap_or_default().to_string(),
+                url: v["url"].as_str()?.to_string(),
+                sha1: v["sha1"].as_str().map(|s| s.to_string()),
+                id,
+            })
+        })
+        .collect();
+
+    Ok(CatalogCache {
+        etag,
+        fetched_at: chrono::Utc::now().timestamp(),
+        latest_release: manifest["latest"]["release"].as_str().map(|s| s.to_string()),
+        latest_snapshot: manifest["latest"]["snapshot"].as_str().map(|s| s.to_string()),
+        versions,
+    })
+}
+
+// Charger le catalogue : cache disque si récent, sinon revalidation du manifest
+async fn load_catalog(force_refresh: bool) -> Result<(CatalogCache, bool), String> {
+    let cached = load_cache();
+    let now = chrono::Utc::now().timestamp();
+
+    if let Some(cache) = &cached {
+        let fresh = now - cache.fetched_at < CATALOG_TTL_SECS;
+        if (fresh && !force_refresh) || endpoints::is_offline() {
+            return Ok((cache.clone(), false));
+        }
+    }
+
+    let manifest_url = endpoints::url(Upstream::Mojang, MANIFEST_PATH);
+    let etag = cached.as_ref().and_then(|c| c.etag.as_deref());
+
+    match endpoints::fetch_conditional(&manifest_url, etag).await {
+        // Relire le cache sous verrou : des versions Java ont pu être résolues pendant la requête
+        Ok(Fetched::NotModified) => {
+            let _guard = CACHE_LOCK.lock().unwrap();
+            let mut cache = load_cache().or(cached).unwrap_or_default();
+            cache.fetched_at = now;
+            save_cache(&cache)?;
+            Ok((cache, false))
+        }
+        Ok(Fetched::Modified { bytes, etag }) => {
+            let _guard = CACHE_LOCK.lock().unwrap();
+            let cache = parse_manifest(&bytes, etag, load_cache().or(cached).as_ref())?;
+            save_cache(&cache)?;
+            println!("Catalogue Minecraft mis à jour: {} versions", cache.versions.len());
+            Ok((cache, false))
+        }
+        Err(e) => match cached {
+            // Pas de réseau : on garde l'ancien catalogue plutôt que d'échouer
+            Some(cache) => {
+                println!("⚠️ Manifest Mojang inaccessible, utilisation du cache: {}", e);
+                Ok((cache, true))
+            }
+            None => Err(format!("Erreur manifest: {}", e)),
+        },
+    }
+}
+
+// Lire javaVersion.majorVersion et le téléchargement serveur depuis le JSON d'une version
+async fn fetch_version_json(version: &CatalogVersion) -> Result<VersionDetails, String> {
+    let data = endpoints::fetch_json(&version.url)
+        .await
+        .map_err(|e| format!("Erreur détails {}: {}", version.id, e))?;
+
+    Ok(VersionDetails {
+        id: version.id.clone(),
+        kind: version.kind.clone(),
+        release_time: version.release_time.clone(),
+        // Les anciennes versions n'ont pas de champ javaVersion : le launcher utilise Java 8
+        java_major: data["javaVersion"]["majorVersion"].as_u64().map(|v| v as u32).unwrap_or(8),
+        server_url: data["downloads"]["server"]["url"].as_str().map(|s| s.to_string()),
+        server_sha1: data["downloads"]["server"]["sha1"].as_str().map(|s| s.to_string()),
+    })
+}
+
+// Enregistrer les versions Java résolues dans le cache disque
+fn remember_java(resolved: &[(String, u32)]) {
+    if resolved.is_empty() {
+        return;
+    }
+    let _guard = CACHE_LOCK.lock().unwrap();
+    let Some(mut cache) = load_cache() else { return };
+    for (id, major) in resolved {
+        if let Some(version) = cache.versions.iter_mut().find(|v| &v.id == id) {
+            version.java_major = Some(*major);
+        }
+    }
+    if let Err(e) = save_cache(&cache) {
+        println!("⚠️ {}", e);
+    }
+}
+
+pub async fn catalog(filter: VersionFilter, force_refresh: bool) -> Result<VersionCatalog, String> {
+    let (cache, stale) = load_catalog(force_refresh).await?;
+
+    let kinds = if filter.kinds.is_empty() {
+        vec!["release".to_string()]
+    } else {
+        filter.kinds.iter().map(|k| k.to_lowercase()).collect()
+    };
+
+    let mut versions: Vec<CatalogVersion> = cache
+        .versions
+        .iter()
+        .filter(|v| kinds.iter().any(|k| k == "all" || *k == v.kind))
+        .take(filter.limit.unwrap_or(usize::MAX))
+        .cloned()
+        .collect();
+
+    if filter.include_java && !endpoints::is_offline() {
+        let missing: Vec<CatalogVersion> = versions.iter().filter(|v| v.java_major.is_none()).cloned().collect();
+        let mut resolved = Vec::new();
+
+        for batch in missing.chunks(JAVA_RESOLVE_BATCH) {
+            let handles: Vec<_> = batch
+                .iter()
+                .cloned()
+                .map(|version| tokio::spawn(async move { fetch_version_json(&version).await }))
+                .collect();
+            for handle in handles {
+                if let Ok(Ok(details)) = handle.await {
+                    resolved.push((details.id, details.java_major));
+                }
+            }
+        }
+
+        for version in versions.iter_mut() {
+            if let Some((_, major)) = resolved.iter().find(|(id, _)| *id == version.id) {
+                version.java_major = Some(*major);
+            }
+        }
+        remember_java(&resolved);
+    }
+
+    Ok(VersionCatalog {
+        latest_release: cache.latest_release,
+        latest_snapshot: cache.latest_snapshot,
+        fetched_at: cache.fetched_at,
+        stale,
+        versions,
+    })
+}
+
+// Identifiants des versions d'un type donné, plus récentes en premier
+pub async fn version_ids(kind: &str) -> Result<Vec<String>, String> {
+    let filter = VersionFilter {
+        kinds: vec![kind.to_string()],
+        ..Default::default()
+    };
+    Ok(catalog(filter, false)
+        .await?
+        .versions
+        .into_iter()
+        .map(|v| v.id)
+        .collect())
+}
+
+// Détails complets d'une version (URL et SHA-1 du serveur, version Java requise)
+pub async fn version_details(id: &str) -> Result<VersionDetails, String> {
+    let (cache, _) = load_catalog(false).await?;
+    let version = cache
+        .versions
+        .iter()
+        .find(|v| v.id == id)
+        .ok_or(format!("Version {} non trouvée", id))?;
+
+    let details = fetch_version_json(version).await?;
+    if version.java_major != Some(details.java_major) {
+        remember_java(&[(details.id.clone(), details.java_major)]);
+    }
+
+    Ok(details)
+}
+
+// Version Java requise pour une version Minecraft (ou un loader "1.20.1-47.2.0")
+pub async fn required_java(minecraft_version: &str) -> Option<u32> {
+    let (cache, _) = load_catalog(false).await.ok()?;
+    let base = minecraft_version.split('-').next().unwrap_or(minecraft_version);
+    let version = cache
+        .versions
+        .iter()
+        .find(|v| v.id == minecraft_version)
+        .or_else(|| cache.versions.iter().find(|v| v.id == base))?;
+
+    if let Some(major) = version.java_major {
+        return Some(major);
+    }
+
+    let details = fetch_version_json(version).await.ok()?;
+    remember_java(&[(details.id.clone(), details.java_major)]);
+    Some(details.java_major)
+}
@@ -0,0 +1,264 @@
+// Édition ligne à ligne des fichiers YAML de configuration (bukkit.yml, spigot.yml, config/paper-*.yml) :
+// seules les valeurs scalaires ciblées sont réécrites, commentaires et mise en forme sont conservés
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+pub struct YamlFile {
+    lines: Vec<String>,
+    newline: &'static str,
+    trailing_newline: bool,
+}
+
+// Ligne "clé: valeur" reconnue
+struct KeyLine {
+    key: String,
+    // Position juste après les deux-points
+    value_start: usize,
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+// Ligne porteuse de contenu (ni vide, ni commentaire, ni séparateur de document)
+fn is_content(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty() && !trimmed.starts_with('#') && trimmed != "---" && trimmed != "..."
+}
+
+fn key_line(line: &str) -> Option<KeyLine> {
+    if !is_content(line) {
+        return None;
+    }
+    let indent = indent_of(line);
+    let body = &line[indent..];
+    // Élément de liste
+    if body == "-" || body.starts_with("- ") {
+        return None;
+    }
+
+    let (key, key_end) = match body.chars().next() {
+        Some(quote @ ('\'' | '"')) => {
+            let end = body[1..].find(quote)? + 1;
+            (body[1..end].to_string(), end + 1)
+        }
+        _ => {
+            let bytes = body.as_bytes();
+            let end = (0..bytes.len())
+                .find(|&i| bytes[i] == b':' && (i + 1 == bytes.len() || bytes[i + 1] == b' '))?;
+            (body[..end].trim_end().to_string(), end)
+        }
+    };
+
+    if !body[key_end..].starts_with(':') {
+        return None;
+    }
+    Some(KeyLine { key, value_start: indent + key_end + 1 })
+}
+
+// Séparer la valeur de ce qui la suit (espaces et commentaire de fin de ligne)
+fn split_value(rest: &str) -> (&str, &str) {
+    let mut quote = None;
+    let mut previous = ' ';
+    let mut comment = rest.len();
+    for (i, c) in rest.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if (c == '\'' || c == '"') && rest[..i].trim().is_empty() => quote = Some(c),
+            None if c == '#' && previous.is_whitespace() => {
+                comment = i;
+                break;
+            }
+            None => {}
+        }
+        previous = c;
+    }
+
+    let start = rest.len() - rest.trim_start().len();
+    let end = rest[..comment].trim_end().len().max(start);
+    (&rest[start..end], &rest[end..])
+}
+
+fn unquote(value: &str) -> String {
+    if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        return value[1..value.len() - 1].replace("''", "'");
+    }
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        return value[1..value.len() - 1]
+            .replace("\\\"", "\"")
+            .replace("\\n", "\n")
+            .replace("\\\\", "\\");
+    }
+    value.to_string()
+}
+
+// Écrire un texte en scalaire YAML, entre apostrophes s'il serait lu autrement (nombre, booléen, caractère spécial)
+pub fn quote(text: &str) -> String {
+    let plain = !text.is_empty()
+        && text.trim() == text
+        && text.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | ' '))
+        && !matches!(text.to_lowercase().as_str(), "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~")
+        && text.parse::<f64>().is_err();
+    if plain {
+        text.to_string()
+    } else {
+        format!("'{}'", text.replace('\'', "''"))
+    }
+}
+
+fn format_key(key: &str) -> String {
+    if key.contains([':', '#', '\'', '"']) || key.trim() != key {
+        format!("'{}'", key.replace('\'', "''"))
+    } else {
+        key.to_string()
+    }
+}
+
+impl YamlFile {
+    pub fn parse(content: &str) -> Self {
+        YamlFile {
+            lines: content.lines().map(|line| line.to_string()).collect(),
+            newline: if content.contains("\r\n") { "\r\n" } else { "\n" },
+            trailing_newline: content.is_empty() || content.ends_with('\n'),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Erreur lecture {}: {}", path.display(), e))?;
+        Ok(Self::parse(&content))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_string()).map_err(|e| format!("Erreur écriture {}: {}", path.display(), e))
+    }
+
+    // Début du bloc enfant d'une clé (ou du document) et indentation de la clé parente
+    fn block(&self, parent: Option<usize>) -> (usize, isize) {
+        match parent {
+            Some(i) => (i + 1, indent_of(&self.lines[i]) as isize),
+            None => (0, -1),
+        }
+    }
+
+    fn child(&self, parent: Option<usize>, key: &str) -> Option<usize> {
+        let (start, parent_indent) = self.block(parent);
+        let mut child_indent = None;
+
+        for (i, line) in self.lines.iter().enumerate().skip(start) {
+            if !is_content(line) {
+                continue;
+            }
+            let indent = indent_of(line);
+            if indent as isize <= parent_indent {
+                break;
+            }
+            // Seules les clés directement sous le parent comptent, pas celles des sous-sections
+            if indent != *child_indent.get_or_insert(indent) {
+                continue;
+            }
+            if key_line(line).is_some_and(|k| k.key == key) {
+                return Some(i);
+            }
+        }
+
+        None
+    }
+
+    // Ligne de la clé, ou la plus profonde des clés parentes trouvées et le nombre de segments résolus
+    fn locate(&self, path: &[&str]) -> Result<usize, (Option<usize>, usize)> {
+        let mut current = None;
+        for (depth, key) in path.iter().enumerate() {
+            match self.child(current, key) {
+                Some(i) => current = Some(i),
+                None => return Err((current, depth)),
+            }
+        }
+        current.ok_or((None, 0))
+    }
+
+    // Valeur scalaire d'un chemin pointé ("world-settings.default.view-distance")
+    pub fn get(&self, path: &str) -> Option<String> {
+        let segments: Vec<&str> = path.split('.').collect();
+        let line = &self.lines[self.locate(&segments).ok()?];
+        let key = key_line(line)?;
+        let (value, _) = split_value(&line[key.value_start..]);
+        if value.is_empty() || value.starts_with(['|', '>']) {
+            return None;
+        }
+        Some(unquote(value))
+    }
+
+    // Modifier (ou ajouter, avec ses sections parentes) une valeur scalaire déjà écrite en YAML.
+    // Renvoie true si le fichier change.
+    pub fn set(&mut self, path: &str, scalar: &str) -> Result<bool, String> {
+        let segments: Vec<&str> = path.split('.').collect();
+
+        match self.locate(&segments) {
+            Ok(i) => {
+                let line = &self.lines[i];
+                let key = key_line(line).ok_or_else(|| format!("{}: ligne illisible", path))?;
+                let (value, suffix) = split_value(&line[key.value_start..]);
+                if value.is_empty() || value.starts_with(['|', '>', '[', '{', '&', '*']) {
+                    return Err(format!("{}: n'est pas une valeur simple", path));
+                }
+                if value == scalar {
+                    return Ok(false);
+                }
+                self.lines[i] = format!("{} {}{}", &line[..key.value_start], scalar, suffix);
+                Ok(true)
+            }
+            Err((parent, depth)) => {
+                if let Some(p) = parent {
+                    let line = &self.lines[p];
+                    let has_value = key_line(line).is_some_and(|k| !split_value(&line[k.value_start..]).0.is_empty());
+                    if has_value {
+                        return Err(format!("{}: n'est pas une section", segments[..depth].join(".")));
+                    }
+                }
+
+                // Insérer à la fin du bloc parent, à l'indentation de ses clés
+                let (start, parent_indent) = self.block(parent);
+                let mut insert_at = start;
+                let mut indent = None;
+                for (i, line) in self.lines.iter().enumerate().skip(start) {
+                    if !is_content(line) {
+                        continue;
+                    }
+                    if indent_of(line) as isize <= parent_indent {
+                        break;
+                    }
+                    indent.get_or_insert(indent_of(line));
+                    insert_at = i + 1;
+                }
+                let indent = indent.unwrap_or(if parent.is_some() { parent_indent as usize + 2 } else { 0 });
+
+                let new_lines: Vec<String> = segments[depth..]
+                    .iter()
+                    .enumerate()
+                    .map(|(level, key)| {
+                        let padding = " ".repeat(indent + level * 2);
+                        if depth + level + 1 == segments.len() {
+                            format!("{}{}: {}", padding, format_key(key), scalar)
+                        } else {
+                            format!("{}{}:", padding, format_key(key))
+                        }
+                    })
+                    .collect();
+                self.lines.splice(insert_at..insert_at, new_lines);
+                Ok(true)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for YamlFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.lines.join(self.newline))?;
+        if self.trailing_newline && !self.lines.is_empty() {
+            write!(f, "{}", self.newline)?;
+        }
+        Ok(())
+    }
+}
@@ -107,7 +107,10 @@ export const UpdateChecker: React.FC<UpdateCheckerProps> = ({ version, serverTyp
   const checkUpdates = async () => {
     setChecking(true);
     try {
-      const newVersion = await invoke<string | null>('check_updates', { version, serverType });
+      // Le chemin permet de détecter le build installé (version_history.json, nom du JAR)
+      const savedServers = JSON.parse(localStorage.getItem('nether-client-servers') || '[]');
+      const serverPath = savedServers.find((s: any) => s.name === serverName)?.path ?? null;
+      const newVersion = await invoke<string | null>('check_updates', { version, serverType, serverPath });
       setUpdateAvailable(newVersion);
     } catch (error) {
       console.error('Erreur vérification mises à jour:', error);
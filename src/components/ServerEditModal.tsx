@@ -53,11 +53,14 @@ const ServerEditModal: React.FC<ServerEditModalProps> = ({ server, onClose, onSa
         'resource-pack': config.resourcePack,
       };
 
-      // Mettre à jour le fichier server.properties
-      await invoke('update_server_properties', {
+      // Mettre à jour le fichier server.properties (avertissements : propriétés ignorées par cette version)
+      const warnings = await invoke<string[]>('update_server_properties', {
         serverName: config.name,
         properties: properties
       });
+      if (warnings.length > 0) {
+        alert(`⚠️ ${warnings.join('\n')}`);
+      }
 
       const updatedServer: ServerType = {
         ...server,
@@ -46,6 +46,7 @@ const JavaBuilder: React.FC<JavaBuilderProps> = ({ onClose, onServerCreated }) =
     maxPlayers: 20,
     difficulty: 'normal' as const,
     gamemode: 'survival' as const,
+    acceptEula: false,
   });
 
   useEffect(() => {
@@ -119,6 +120,7 @@ const JavaBuilder: React.FC<JavaBuilderProps> = ({ onClose, onServerCreated }) =
         max_players: serverConfig.maxPlayers,
         difficulty: serverConfig.difficulty,
         gamemode: serverConfig.gamemode,
+        accept_eula: serverConfig.acceptEula,
       };
 
       const serverId = await invoke<string>('create_vanilla_server', { config });
@@ -475,6 +477,22 @@ const JavaBuilder: React.FC<JavaBuilderProps> = ({ onClose, onServerCreated }) =
                     </div>
                   </div>
 
+                  <label className="flex items-start space-x-3 p-4 bg-dark-800 rounded-lg cursor-pointer">
+                    <input
+                      type="checkbox"
+                      checked={serverConfig.acceptEula}
+                      onChange={(e) => handleConfigChange('acceptEula', e.target.checked)}
+                      className="mt-1"
+                    />
+                    <span className="text-sm text-dark-400">
+                      J'accepte le{' '}
+                      <a href="https://aka.ms/MinecraftEULA" target="_blank" rel="noreferrer" className="text-primary-400 underline">
+                        CLUF (EULA) de Minecraft
+                      </a>
+                      . Le serveur ne peut pas démarrer sans cette acceptation.
+                    </span>
+                  </label>
+
                   {error && (
                     <div className="flex items-center space-x-3 p-4 bg-red-500/20 border border-red-500/30 rounded-lg">
                       <AlertCircle className="w-5 h-5 text-red-400" />
@@ -521,7 +539,7 @@ const JavaBuilder: React.FC<JavaBuilderProps> = ({ onClose, onServerCreated }) =
                 {step === 2 && (
                   <button
                     onClick={createServer}
-                    disabled={loading || !serverConfig.name.trim()}
+                    disabled={loading || !serverConfig.name.trim() || !serverConfig.acceptEula}
                     className="btn-primary flex items-center space-x-2 disabled:opacity-50"
                   >
                     {loading ? (
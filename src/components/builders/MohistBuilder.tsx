@@ -47,6 +47,7 @@ const MohistBuilder: React.FC<MohistBuilderProps> = ({ onClose, onServerCreated
     maxPlayers: 20,
     difficulty: 'normal' as const,
     gamemode: 'survival' as const,
+    acceptEula: false,
   });
 
   useEffect(() => {
@@ -118,6 +119,7 @@ const MohistBuilder: React.FC<MohistBuilderProps> = ({ onClose, onServerCreated
         max_players: serverConfig.maxPlayers,
         difficulty: serverConfig.difficulty,
         gamemode: serverConfig.gamemode,
+        accept_eula: serverConfig.acceptEula,
       };
 
       console.log('🚀 Création du serveur MohistMC avec la configuration:', config);
@@ -501,6 +503,22 @@ const MohistBuilder: React.FC<MohistBuilderProps> = ({ onClose, onServerCreated
                   </div>
                 </div>
 
+                <label className="mt-6 flex items-start space-x-3 p-4 bg-dark-800 rounded-lg cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={serverConfig.acceptEula}
+                    onChange={(e) => handleConfigChange('acceptEula', e.target.checked)}
+                    className="mt-1"
+                  />
+                  <span className="text-sm text-dark-400">
+                    J'accepte le{' '}
+                    <a href="https://aka.ms/MinecraftEULA" target="_blank" rel="noreferrer" className="text-primary-400 underline">
+                      CLUF (EULA) de Minecraft
+                    </a>
+                    . Le serveur ne peut pas démarrer sans cette acceptation.
+                  </span>
+                </label>
+
                 {error && (
                   <div className="mt-6 bg-red-500/10 border border-red-500/20 rounded-lg p-4">
                     <div className="flex items-center space-x-2">
@@ -521,7 +539,7 @@ const MohistBuilder: React.FC<MohistBuilderProps> = ({ onClose, onServerCreated
                   
                   <button
                     onClick={createServer}
-                    disabled={loading || !serverConfig.name.trim()}
+                    disabled={loading || !serverConfig.name.trim() || !serverConfig.acceptEula}
                     className="px-6 py-2 bg-purple-600 hover:bg-purple-700 disabled:opacity-50 disabled:cursor-not-allowed text-white rounded-lg transition-colors flex items-center space-x-2"
                   >
                     {loading ? (
@@ -15,7 +15,7 @@ export interface Server {
   id: string;
   name: string;
   version: string;
-  type: 'vanilla' | 'forge' | 'neoforge' | 'mohist' | 'paper';
+  type: 'vanilla' | 'forge' | 'neoforge' | 'mohist' | 'paper' | 'folia' | 'purpur' | 'fabric' | 'quilt';
   port: number;
   ram: number;
   motd: string;